@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+
+use crate::history::{ApplicationRecord, History};
+
+const AIRTABLE_API: &str = "https://api.airtable.com/v0";
+// Airtable's limit on records per create request
+const BATCH_SIZE: usize = 10;
+
+// Creates one Airtable record per application
+#[derive(Serialize, Deserialize)]
+pub struct AirtableConfig {
+    pub base_id: String,
+    pub table: String,
+    // Environment variable holding a personal access token
+    #[serde(default = "default_token_env")]
+    pub token_env: String,
+    // Airtable column name -> application record field (job_id, job_title,
+    // url, applied_at, resume_variant, applicants, source, category, summary,
+    // status). Columns left out aren't sent.
+    #[serde(default = "default_fields")]
+    pub fields: BTreeMap<String, String>,
+}
+
+fn default_token_env() -> String {
+    "AIRTABLE_TOKEN".to_string()
+}
+
+fn default_fields() -> BTreeMap<String, String> {
+    [("Job", "job_title"), ("URL", "url"), ("Applied", "applied_at"), ("Status", "status")]
+        .into_iter()
+        .map(|(column, field)| (column.to_string(), field.to_string()))
+        .collect()
+}
+
+fn airtable_error(message: String) -> WebDriverError {
+    WebDriverError::UnknownError(WebDriverErrorInfo::new(message))
+}
+
+fn record_fields(config: &AirtableConfig, record: &ApplicationRecord) -> Value {
+    let record = serde_json::to_value(record).unwrap_or_default();
+    let mut fields = Map::new();
+    for (column, field) in &config.fields {
+        match record.get(field) {
+            Some(Value::Null) => {}
+            Some(value) => {
+                fields.insert(column.clone(), value.clone());
+            }
+            None => println!("Airtable column '{}' maps to unknown field '{}'", column, field),
+        }
+    }
+    json!({ "fields": fields })
+}
+
+// Sends the applications recorded since `since` (normally the start of the run)
+pub async fn export_applications(config: &AirtableConfig, history: &History, since: DateTime<Utc>) -> WebDriverResult<()> {
+    let records: Vec<Value> = history
+        .records
+        .iter()
+        .filter(|r| r.applied_at >= since)
+        .map(|r| record_fields(config, r))
+        .collect();
+    if records.is_empty() {
+        return Ok(());
+    }
+    let token = std::env::var(&config.token_env).map_err(|_| airtable_error(format!("Set {} to an Airtable access token", config.token_env)))?;
+
+    let mut url = reqwest::Url::parse(AIRTABLE_API).map_err(|e| airtable_error(e.to_string()))?;
+    url.path_segments_mut()
+        .map_err(|_| airtable_error(format!("{} can't take a path", AIRTABLE_API)))?
+        .push(&config.base_id)
+        .push(&config.table);
+
+    let client = reqwest::Client::new();
+    for batch in records.chunks(BATCH_SIZE) {
+        let response = client
+            .post(url.clone())
+            .bearer_auth(&token)
+            .json(&json!({ "records": batch, "typecast": true }))
+            .send()
+            .await
+            .map_err(|e| airtable_error(format!("Could not reach Airtable: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(airtable_error(format!("Creating Airtable records failed with {}: {}", status, body)));
+        }
+    }
+    println!("Added {} applications to Airtable table {}", records.len(), config.table);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Where to tell the user about a run that needs attention. The message is
+// posted as both `text` and `content`, which Slack and Discord webhooks
+// read respectively.
+#[derive(Serialize, Deserialize)]
+pub struct AlertConfig {
+    pub webhook_url: String,
+}
+
+// Always printed; posted too when a webhook is configured. A webhook that
+// can't be reached is reported and otherwise ignored.
+pub async fn send(config: Option<&AlertConfig>, message: &str) {
+    println!("ALERT: {}", message);
+    let Some(config) = config else {
+        return;
+    };
+    let body = json!({ "text": message, "content": message });
+    let result = reqwest::Client::new().post(&config.webhook_url).json(&body).send().await.and_then(|response| response.error_for_status());
+    if let Err(e) = result {
+        println!("Could not send the alert to the webhook: {}", e);
+    }
+}
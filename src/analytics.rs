@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::history::{ApplicationRecord, ApplicationStatus, History};
+use crate::{job_id_from_url, Job};
+
+// Applications with no response after this long get a follow-up reminder
+const FOLLOW_UP_AFTER_DAYS: i64 = 7;
+
+// Applications and responses for one resume variant or job category
+pub struct GroupStats {
+    pub name: String,
+    pub applications: usize,
+    pub responses: usize,
+}
+
+impl GroupStats {
+    pub fn response_rate(&self) -> f64 {
+        if self.applications == 0 {
+            return 0.0;
+        }
+        self.responses as f64 / self.applications as f64
+    }
+}
+
+fn stats_by(history: &History, key: impl Fn(&ApplicationRecord) -> Option<&str>) -> Vec<GroupStats> {
+    let mut groups: BTreeMap<&str, GroupStats> = BTreeMap::new();
+    for record in &history.records {
+        let Some(name) = key(record) else {
+            continue;
+        };
+        let stats = groups.entry(name).or_insert_with(|| GroupStats {
+            name: name.to_string(),
+            applications: 0,
+            responses: 0,
+        });
+        stats.applications += 1;
+        if record.status.is_response() {
+            stats.responses += 1;
+        }
+    }
+    groups.into_values().collect()
+}
+
+pub fn variant_stats(history: &History) -> Vec<GroupStats> {
+    stats_by(history, |record| record.resume_variant.as_deref())
+}
+
+pub fn category_stats(history: &History) -> Vec<GroupStats> {
+    stats_by(history, |record| record.category.as_deref())
+}
+
+fn print_report(heading: &str, stats: Vec<GroupStats>) {
+    if stats.is_empty() {
+        return;
+    }
+
+    println!("{}:", heading);
+    for s in stats {
+        println!(
+            "  {:<20} {:>4} applied, {:>4} responses ({:.1}%)",
+            s.name,
+            s.applications,
+            s.responses,
+            s.response_rate() * 100.0
+        );
+    }
+}
+
+pub fn print_variant_report(history: &History) {
+    print_report("Resume variant response rates", variant_stats(history));
+}
+
+pub fn print_category_report(history: &History) {
+    print_report("Job category response rates", category_stats(history));
+}
+
+pub fn print_follow_up_reminders(history: &History) {
+    let cutoff = Utc::now() - Duration::days(FOLLOW_UP_AFTER_DAYS);
+    let due: Vec<&ApplicationRecord> = history
+        .records
+        .iter()
+        .filter(|r| r.status == ApplicationStatus::Applied && r.applied_at < cutoff)
+        .collect();
+    if due.is_empty() {
+        return;
+    }
+
+    println!("No response after {} days, consider following up:", FOLLOW_UP_AFTER_DAYS);
+    for record in due {
+        println!("  {} ({}, applied {})", record.job_title, record.url, record.applied_at.format("%Y-%m-%d"));
+        if let Some(summary) = &record.summary {
+            println!("    {}", summary);
+        }
+    }
+}
+
+// Applications and responses in the `days` before `end`
+fn window_counts(records: &[ApplicationRecord], end: DateTime<Utc>, days: i64) -> (usize, usize) {
+    let start = end - Duration::days(days);
+    let records: Vec<&ApplicationRecord> = records
+        .iter()
+        .filter(|r| r.applied_at >= start && r.applied_at < end)
+        .collect();
+    let responses = records.iter().filter(|r| r.status.is_response()).count();
+    (records.len(), responses)
+}
+
+// Applications in each of the `days` days before `end`, oldest first
+pub fn daily_applications(records: &[ApplicationRecord], end: DateTime<Utc>, days: i64) -> Vec<usize> {
+    (0..days).rev().map(|ago| window_counts(records, end - Duration::days(ago), 1).0).collect()
+}
+
+// Share of each week's applications that got a response, oldest week
+// first; None for a week without applications
+pub fn weekly_response_rates(records: &[ApplicationRecord], end: DateTime<Utc>, weeks: i64) -> Vec<Option<f64>> {
+    (0..weeks)
+        .rev()
+        .map(|ago| match window_counts(records, end - Duration::weeks(ago), 7) {
+            (0, _) => None,
+            (applied, responses) => Some(responses as f64 / applied as f64),
+        })
+        .collect()
+}
+
+// `dice_blast status`
+pub fn print_status(history: &History, scraped: &[Job]) {
+    let count = |status: ApplicationStatus| history.records.iter().filter(|r| r.status == status).count();
+    let queued = scraped
+        .iter()
+        .filter(|job| !history.records.iter().any(|r| r.job_id == job_id_from_url(&job.url)))
+        .count();
+
+    println!("Pipeline:");
+    println!("  {:<20} {:>5}", "queued", queued);
+    println!("  {:<20} {:>5}", "applied", history.records.len());
+    println!("  {:<20} {:>5}", "awaiting response", count(ApplicationStatus::Applied));
+    println!("  {:<20} {:>5}", "responded", count(ApplicationStatus::Responded));
+    println!("  {:<20} {:>5}", "interviewing", count(ApplicationStatus::Interviewing));
+    println!("  {:<20} {:>5}", "rejected", count(ApplicationStatus::Rejected));
+
+    // Each window is compared with the one just before it
+    let now = Utc::now();
+    println!("Trend:");
+    for days in [7, 30] {
+        let (applied, responses) = window_counts(&history.records, now, days);
+        let (prev_applied, prev_responses) = window_counts(&history.records, now - Duration::days(days), days);
+        println!(
+            "  last {:>2} days: {:>4} applied ({:+}), {:>4} responses ({:+})",
+            days,
+            applied,
+            applied as i64 - prev_applied as i64,
+            responses,
+            responses as i64 - prev_responses as i64
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::JobSource;
+
+    fn record(applied_at: &str, status: ApplicationStatus) -> ApplicationRecord {
+        ApplicationRecord {
+            job_id: "2b9d5bc5-3c4d-4b6b-9a1e-6c1e9f1a2b3c".to_string(),
+            job_title: "Rust Engineer".to_string(),
+            url: "https://www.dice.com/job-detail/2b9d5bc5-3c4d-4b6b-9a1e-6c1e9f1a2b3c".to_string(),
+            applied_at: DateTime::parse_from_rfc3339(applied_at).unwrap().with_timezone(&Utc),
+            resume_variant: None,
+            applicants: None,
+            source: JobSource::Search,
+            category: None,
+            summary: None,
+            status,
+            description_hash: None,
+            company: None,
+            snapshot: None,
+        }
+    }
+
+    #[test]
+    fn trends_count_back_from_the_end() {
+        let records = vec![
+            record("2026-10-01T09:00:00Z", ApplicationStatus::Rejected),
+            record("2026-10-01T15:00:00Z", ApplicationStatus::Applied),
+            record("2026-10-14T09:00:00Z", ApplicationStatus::Applied),
+            record("2026-10-15T09:00:00Z", ApplicationStatus::Interviewing),
+        ];
+        let end = DateTime::parse_from_rfc3339("2026-10-16T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(daily_applications(&records, end, 3), vec![0, 1, 1]);
+        assert_eq!(weekly_response_rates(&records, end, 3), vec![Some(0.5), None, Some(0.5)]);
+    }
+
+    #[test]
+    fn status_updates_move_the_variant_rate() {
+        let dir = std::env::temp_dir().join(format!("dice_blast-analytics-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(crate::history::HISTORY_FILE);
+        let mut history = History::load(&path).unwrap();
+        for (id, variant) in [("a1", "short"), ("a2", "short"), ("b1", "long")] {
+            let mut record = record("2026-10-01T09:00:00Z", ApplicationStatus::Applied);
+            record.job_id = id.to_string();
+            record.resume_variant = Some(variant.to_string());
+            history.record(record).unwrap();
+        }
+        let rate = |history: &History, name: &str| variant_stats(history).into_iter().find(|s| s.name == name).unwrap().response_rate();
+        assert_eq!(rate(&history, "short"), 0.0);
+
+        assert!(history.set_status("a1", ApplicationStatus::Interviewing).unwrap());
+        assert!(!history.set_status("zz", ApplicationStatus::Rejected).unwrap());
+        assert_eq!(rate(&history, "short"), 0.5);
+        assert_eq!(rate(&history, "long"), 0.0);
+
+        // Stored, not just changed in memory
+        let reloaded = History::load(&path).unwrap();
+        assert_eq!(rate(&reloaded, "short"), 0.5);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
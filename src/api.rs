@@ -0,0 +1,261 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thirtyfour::prelude::*;
+use tokio::sync::mpsc;
+
+use crate::archive::PostingArchive;
+use crate::discovery::{self, JobSource};
+use crate::history::{self, ApplicationStatus, History};
+use crate::rng::RunRng;
+use crate::zero_results::ZeroResultLog;
+use crate::{build_search_url, consent, filter_jobs, get_job_detail_ids, open_job_urls, paths, queued_to_apply, run_limit, session, sync, Config, Job, SearchQuery};
+
+// Local HTTP API for driving the bot from other programs, served by
+// `dice_blast serve` (not by `daemon`, which runs on its schedule alone).
+// Every request must carry `Authorization: Bearer <token>`.
+#[derive(Serialize, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default = "default_bind")]
+    pub bind: String,
+    pub token: String,
+}
+
+fn default_bind() -> String {
+    "127.0.0.1:8787".to_string()
+}
+
+enum Command {
+    Search(Box<SearchQuery>),
+    Apply,
+}
+
+struct ApiState {
+    token: String,
+    commands: mpsc::Sender<Command>,
+}
+
+// A job posted to /jobs: a Dice job URL or a bare job ID, and optionally
+// the title to show until the job page is opened
+#[derive(Deserialize)]
+struct SubmittedJob {
+    job: String,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Submitted {
+    id: String,
+    queued: bool,
+    queue_length: usize,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    applications: usize,
+    responses: usize,
+    queued: usize,
+    by_status: HashMap<ApplicationStatus, usize>,
+}
+
+// Compares digests byte by byte without stopping at the first difference,
+// so response timing says nothing about how much of a guess was right
+fn same_secret(given: &[u8], expected: &[u8]) -> bool {
+    let (given, expected) = (Sha256::digest(given), Sha256::digest(expected));
+    given.iter().zip(expected.iter()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// Each request opens its own connection to the tracking database; the
+// queue lives there so it survives restarts and `apply --from-queue` sees it
+fn open_history() -> Result<History, StatusCode> {
+    History::load(&paths::state(history::HISTORY_FILE)).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn authorize(state: &ApiState, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let expected = format!("Bearer {}", state.token);
+    match headers.get("authorization") {
+        Some(value) if same_secret(value.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+async fn enqueue_search(State(state): State<Arc<ApiState>>, headers: HeaderMap, Json(search): Json<SearchQuery>) -> Result<StatusCode, StatusCode> {
+    authorize(&state, &headers)?;
+    state.commands.send(Command::Search(Box::new(search))).await.map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+// Bookmarklets run on dice.com, so the browser asks before sending the
+// Authorization header cross-origin
+fn with_cors(response: impl IntoResponse) -> axum::response::Response {
+    let mut response = response.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("https://www.dice.com"));
+    headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, HeaderValue::from_static("POST, OPTIONS"));
+    headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, HeaderValue::from_static("authorization, content-type"));
+    response
+}
+
+async fn jobs_preflight() -> axum::response::Response {
+    with_cors(StatusCode::NO_CONTENT)
+}
+
+pub fn submitted_job_id(input: &str) -> Option<String> {
+    let input = input.trim().to_lowercase();
+    if let Some(captures) = discovery::job_detail_pattern().captures(&input) {
+        return Some(captures[1].to_string());
+    }
+    let bare_id = Regex::new(r"^[a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12}$").unwrap();
+    bare_id.is_match(&input).then_some(input)
+}
+
+// Whether the job was new to the queue, and how long the queue is now
+fn queue_submitted(job: Job) -> std::io::Result<(bool, usize)> {
+    let mut history = History::load(&paths::state(history::HISTORY_FILE))?;
+    let queued = history.enqueue(&[job])? > 0;
+    Ok((queued, history.queue_len()?))
+}
+
+// Queues a job found outside the bot; it's applied to on the next /apply,
+// `apply --from-queue` or scheduled daemon run
+async fn submit_job(State(state): State<Arc<ApiState>>, headers: HeaderMap, Json(submitted): Json<SubmittedJob>) -> axum::response::Response {
+    if let Err(status) = authorize(&state, &headers) {
+        return with_cors(status);
+    }
+    let Some(id) = submitted_job_id(&submitted.job) else {
+        return with_cors((StatusCode::UNPROCESSABLE_ENTITY, format!("\"{}\" is not a Dice job URL or ID", submitted.job)));
+    };
+    let title = submitted.title.filter(|t| !t.trim().is_empty()).unwrap_or_else(|| format!("Dice job {}", id));
+
+    let Ok((queued, queue_length)) = queue_submitted(discovery::discovered_job(&id, title, JobSource::Submitted)) else {
+        return with_cors(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    if queued {
+        println!("API queued submitted job {}", id);
+    }
+    with_cors(Json(Submitted { id, queued, queue_length }))
+}
+
+async fn list_queue(State(state): State<Arc<ApiState>>, headers: HeaderMap) -> Result<Json<Vec<Job>>, StatusCode> {
+    authorize(&state, &headers)?;
+    let queue = open_history()?.queued_jobs().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(queue))
+}
+
+async fn trigger_apply(State(state): State<Arc<ApiState>>, headers: HeaderMap) -> Result<StatusCode, StatusCode> {
+    authorize(&state, &headers)?;
+    state.commands.send(Command::Apply).await.map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn stats(State(state): State<Arc<ApiState>>, headers: HeaderMap) -> Result<Json<Stats>, StatusCode> {
+    authorize(&state, &headers)?;
+    let history = open_history()?;
+    let mut by_status = HashMap::new();
+    for record in &history.records {
+        *by_status.entry(record.status).or_insert(0) += 1;
+    }
+    Ok(Json(Stats {
+        applications: history.records.len(),
+        responses: history.records.iter().filter(|r| r.status.is_response()).count(),
+        queued: history.queue_len().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        by_status,
+    }))
+}
+
+async fn run_search(driver: &WebDriver, config: &Config, history: &mut History, remote_applied: &BTreeSet<String>, zero_results: &mut ZeroResultLog, search: &SearchQuery) -> WebDriverResult<()> {
+    let url = build_search_url(search);
+    session::navigate(driver, &config.timeouts, &url).await?;
+    let jobs = get_job_detail_ids(driver, &config.timeouts, 1).await?;
+    if jobs.is_empty() {
+        if zero_results.should_report(&url) {
+            println!("API search '{}' returned no results", search.q);
+        }
+        return Ok(());
+    }
+    zero_results.clear(&url);
+    let archive = PostingArchive::load(history)?;
+    let mut rng = RunRng::new(config.seed);
+    let jobs = filter_jobs(config, history, &archive, remote_applied, &mut rng, jobs).await;
+
+    let added = history.enqueue(&jobs)?;
+    println!("API search '{}' queued {} new jobs", search.q, added);
+    Ok(())
+}
+
+// Works through the same queue as `apply --from-queue`: jobs leave it once
+// applied to or turned down on their job page; those past the run limit
+// and those that failed stay for the next /apply
+async fn run_apply(driver: &WebDriver, config: &Config, history: &mut History, remote_applied: &BTreeSet<String>) -> WebDriverResult<()> {
+    let consent = consent::require_consent()?;
+    let jobs = queued_to_apply(history, remote_applied)?;
+    println!("API apply triggered for {} queued jobs", jobs.len());
+    let mut rng = RunRng::new(config.seed);
+    let result = open_job_urls(driver, config, history, jobs, "", run_limit(config, &consent, None), &mut rng).await;
+    println!("{} jobs left in the queue", history.queue_len()?);
+    result
+}
+
+// Serves the API in the background while this task owns the browser and
+// works through commands one at a time.
+pub async fn serve(driver: &WebDriver, config: &Config, history: &mut History, api: &ApiConfig) -> WebDriverResult<()> {
+    let remote_applied = match &config.sync {
+        Some(sync_config) => sync::pull(sync_config).await?,
+        None => Default::default(),
+    };
+    let (commands, mut receiver) = mpsc::channel(16);
+    let mut zero_results = ZeroResultLog::new(config.zero_result_quiet_mins);
+    let state = Arc::new(ApiState {
+        token: api.token.clone(),
+        commands,
+    });
+
+    let app = Router::new()
+        .route("/searches", post(enqueue_search))
+        .route("/jobs", post(submit_job).options(jobs_preflight))
+        .route("/queue", get(list_queue))
+        .route("/apply", post(trigger_apply))
+        .route("/stats", get(stats))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(&api.bind).await?;
+    println!("API listening on http://{}", api.bind);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            println!("API server stopped: {}", e);
+        }
+    });
+
+    while let Some(command) = receiver.recv().await {
+        let result = match command {
+            Command::Search(search) => run_search(driver, config, history, &remote_applied, &mut zero_results, &search).await,
+            Command::Apply => run_apply(driver, config, history, &remote_applied).await,
+        };
+        // A failed command shouldn't take the server down with it
+        if let Err(e) = result {
+            println!("API command failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_must_match_exactly() {
+        assert!(same_secret(b"Bearer s3cret", b"Bearer s3cret"));
+        assert!(!same_secret(b"Bearer s3cre", b"Bearer s3cret"));
+        assert!(!same_secret(b"Bearer s3cret ", b"Bearer s3cret"));
+        assert!(!same_secret(b"", b"Bearer s3cret"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::io::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::history::History;
+use crate::{job_id_from_url, Job};
+
+// What to do with a job that looks like a repost of one we've already seen
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RepostPolicy {
+    // Drop it from the queue
+    Skip,
+    // Treat it like any new posting
+    Reapply,
+    // Keep it, but log which posting it repeats
+    #[default]
+    Flag,
+}
+
+// A posting the scraper has seen, as kept in the jobs table
+pub struct ArchivedPosting {
+    pub job_id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub first_seen: DateTime<Utc>,
+}
+
+// Every posting the scraper has ever seen, kept so reposts (same company and
+// title under a new ID) can be recognised
+pub struct PostingArchive {
+    pub postings: Vec<ArchivedPosting>,
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn repost_key(title: &str, company: Option<&str>) -> Option<(String, String)> {
+    let company = company?;
+    Some((normalize(company), normalize(title)))
+}
+
+impl PostingArchive {
+    pub fn load(history: &History) -> Result<PostingArchive> {
+        let postings = history
+            .tracked_jobs()?
+            .into_iter()
+            .map(|job| ArchivedPosting {
+                job_id: job.job_id,
+                title: job.title,
+                company: job.company,
+                first_seen: job.first_seen,
+            })
+            .collect();
+        Ok(PostingArchive { postings })
+    }
+
+    // A posting with the same company and title but a different ID, seen
+    // before this one was. Jobs are recorded before they're filtered, so
+    // postings first seen in the same run never count as each other's
+    // repost. Jobs without a company can't be matched reliably and never are.
+    pub fn find_repost(&self, job: &Job) -> Option<&ArchivedPosting> {
+        let key = repost_key(&job.job_title, job.company.as_deref())?;
+        let id = job_id_from_url(&job.url);
+        let seen = self.postings.iter().find(|p| p.job_id == id).map_or_else(Utc::now, |p| p.first_seen);
+        self.postings
+            .iter()
+            .find(|p| p.job_id != id && p.first_seen < seen && repost_key(&p.title, p.company.as_deref()).as_ref() == Some(&key))
+    }
+}
+
+pub fn repost_reason(archive: &PostingArchive, job: &Job) -> Option<String> {
+    let original = archive.find_repost(job)?;
+    Some(format!("repost of {} (first seen {})", original.job_id, original.first_seen.format("%Y-%m-%d")))
+}
+
+pub fn handle_reposts(archive: &PostingArchive, policy: RepostPolicy, jobs: Vec<Job>) -> Vec<Job> {
+    let mut kept = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        if let Some(reason) = repost_reason(archive, &job) {
+            match policy {
+                RepostPolicy::Skip => {
+                    println!("Skipping {}: {}", job.job_title, reason);
+                    continue;
+                }
+                RepostPolicy::Flag => println!("Possible repost: {} is a {}", job.job_title, reason),
+                RepostPolicy::Reapply => {}
+            }
+        }
+        kept.push(job);
+    }
+    kept
+}
@@ -0,0 +1,19 @@
+use serde_json::json;
+use thirtyfour::prelude::*;
+
+use crate::Job;
+
+// Assist mode: the searches, filters and dedupe pick the jobs and the user
+// applies to them by hand. The first `max_tabs` matching jobs open in new
+// tabs of the signed-in browser window; nothing is clicked or submitted.
+pub async fn open_in_tabs(driver: &WebDriver, jobs: &[Job], max_tabs: usize) -> WebDriverResult<()> {
+    for job in jobs.iter().take(max_tabs) {
+        driver.execute("window.open(arguments[0], '_blank');", vec![json!(job.url)]).await?;
+        println!("Opened {}: {}", job.job_title, job.url);
+    }
+    let opened = jobs.len().min(max_tabs);
+    if opened < jobs.len() {
+        println!("Opened the first {} of {} matching jobs (assist_tabs)", opened, jobs.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::collections::{BTreeSet, HashSet};
+use thirtyfour::prelude::*;
+
+use crate::archive::PostingArchive;
+use crate::budget::RunBudget;
+use crate::history::History;
+use crate::{consent, filter_trace, job_id_from_url, run_limit, sync, Config, Job};
+
+// What a run would do with each job: "APPLY ..." or "SKIP ...". Goes
+// through the same checks and caps as filter_jobs and apply_batch, in
+// order, with the budget filling up as jobs are counted as applied.
+fn decisions(config: &Config, history: &History, archive: &PostingArchive, remote_applied: &BTreeSet<String>, jobs: &[Job], limit: Option<usize>) -> Vec<String> {
+    let mut budget = RunBudget::new(config, history);
+    let mut seen = HashSet::new();
+    let mut would_apply = 0;
+    jobs.iter()
+        .map(|job| {
+            if !seen.insert(job_id_from_url(&job.url)) {
+                return "SKIP   duplicate of an earlier job in this set".to_string();
+            }
+            match filter_trace::decide(config, history, archive, &budget, remote_applied, job) {
+                Err(reason) => format!("SKIP   {}", reason),
+                Ok(_) if limit.is_some_and(|limit| would_apply >= limit) => "SKIP   per-run application limit reached".to_string(),
+                Ok(later) => {
+                    would_apply += 1;
+                    budget.record(job);
+                    if later.is_empty() {
+                        format!("APPLY  passed all filters (source: {})", job.source)
+                    } else {
+                        format!("APPLY  passed card checks; job page still checks {} (source: {})", later.join(", "), job.source)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// Replays the apply decisions over the last scraped job set without touching
+// a browser, printing what would be applied to and why.
+pub async fn run_audit(config: &Config, history: &History) -> WebDriverResult<()> {
+    let jobs = history.latest_jobs()?;
+    let limit = consent::load_consent().map(|c| run_limit(config, &c, None));
+    if jobs.is_empty() {
+        println!("No scraped jobs recorded; run a search first");
+        return Ok(());
+    }
+    println!("Auditing {} jobs from the last run", jobs.len());
+    if limit.is_none() {
+        println!("No consent recorded: a real run would refuse to apply to anything.");
+    }
+    let remote_applied = match &config.sync {
+        Some(sync_config) => sync::pull(sync_config).await?,
+        None => Default::default(),
+    };
+    let archive = PostingArchive::load(history)?;
+
+    let decisions = decisions(config, history, &archive, &remote_applied, &jobs, limit);
+    for (job, decision) in jobs.iter().zip(&decisions) {
+        println!("{:<60} {}", job.job_title, decision);
+    }
+    let would_apply = decisions.iter().filter(|decision| decision.starts_with("APPLY")).count();
+    println!("Would apply to {} of {} jobs", would_apply, jobs.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+    use crate::history::{ApplicationRecord, HISTORY_FILE};
+
+    #[test]
+    fn replays_history_and_blacklist_checks() {
+        let dir = std::env::temp_dir().join(format!("dice_blast-audit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        let job = |id: &str, company: &str| {
+            let mut job = discovered_job(id, format!("Rust Engineer {}", id), JobSource::Search);
+            job.company = Some(company.to_string());
+            job
+        };
+        let jobs = vec![job("a1", "Initech"), job("b1", "Evil Corp"), job("c1", "Globex"), job("d1", "Hooli")];
+        history.record_jobs(&jobs).unwrap();
+        let applied: ApplicationRecord = serde_json::from_value(json!({
+            "job_id": "a1",
+            "job_title": "Rust Engineer a1",
+            "url": "https://www.dice.com/job-detail/a1",
+            "applied_at": "2024-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        history.record(applied).unwrap();
+
+        let config = crate::test_config(json!({ "blacklist": { "companies": ["evil corp"] } }));
+        let archive = PostingArchive::load(&history).unwrap();
+        let stored = history.latest_jobs().unwrap();
+        let decisions = decisions(&config, &history, &archive, &BTreeSet::new(), &stored, Some(1));
+        assert_eq!(decisions.len(), 4);
+        assert!(decisions[0].starts_with("SKIP   already applied"), "{}", decisions[0]);
+        assert!(decisions[1].starts_with("SKIP   company Evil Corp is blacklisted"), "{}", decisions[1]);
+        assert!(decisions[2].starts_with("APPLY"), "{}", decisions[2]);
+        assert_eq!(decisions[3], "SKIP   per-run application limit reached");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use chrono::Utc;
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, ZipArchive, ZipWriter};
+
+use crate::consent::CONSENT_FILE;
+use crate::history::{self, HISTORY_FILE, LEGACY_ARCHIVE_FILE, LEGACY_HISTORY_FILE};
+use crate::paths::{self, CONFIG_FILES, COOKIES_FILE};
+use crate::saved_searches::IMPORT_FILE;
+use crate::session::FINGERPRINT_FILE;
+use crate::state_file;
+
+// Every piece of local state worth carrying to another machine, relative to
+// the state directory. Missing files are skipped on backup.
+const STATE_FILES: [&str; 8] = [
+    COOKIES_FILE,
+    FINGERPRINT_FILE,
+    HISTORY_FILE,
+    // Still restored from backups made before the database
+    LEGACY_HISTORY_FILE,
+    LEGACY_ARCHIVE_FILE,
+    CONSENT_FILE,
+    IMPORT_FILE,
+    "visual_refs/hashes.json",
+];
+
+const PASSPHRASE_ENV: &str = "DICE_BLAST_BACKUP_PASSPHRASE";
+
+fn passphrase() -> io::Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    print!("Backup passphrase: ");
+    io::stdout().flush()?;
+    let mut passphrase = String::new();
+    io::stdin().read_line(&mut passphrase)?;
+    let passphrase = passphrase.trim_end_matches(['\r', '\n']).to_string();
+    if passphrase.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "An empty passphrase is not allowed"));
+    }
+    Ok(passphrase)
+}
+
+// (name inside the archive, file on disk); names are relative so a backup
+// restores into whichever directories the other machine uses
+fn backup_entries() -> Vec<(&'static str, PathBuf)> {
+    let config = CONFIG_FILES.iter().map(|name| (*name, paths::config(name)));
+    let state = STATE_FILES.iter().map(|name| (*name, paths::state(name)));
+    config.chain(state).collect()
+}
+
+fn zip_error(err: zip::result::ZipError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+// Writes all state files into an AES-256 encrypted zip
+pub fn backup(target: Option<&str>) -> io::Result<()> {
+    let default_target = format!("dice_blast-backup-{}.zip", Utc::now().format("%Y%m%d-%H%M%S"));
+    let target = target.unwrap_or(&default_target);
+    let passphrase = passphrase()?;
+
+    let mut writer = ZipWriter::new(File::create(target)?);
+    let options = SimpleFileOptions::default().with_aes_encryption(AesMode::Aes256, &passphrase);
+
+    let mut included = 0;
+    for (name, path) in backup_entries() {
+        // The database may be open in a run or the API server
+        let contents = if name == HISTORY_FILE { history::snapshot(&path) } else { fs::read(&path) };
+        let Ok(contents) = contents else {
+            continue;
+        };
+        writer.start_file(name, options).map_err(zip_error)?;
+        writer.write_all(&contents)?;
+        println!("  added {}", path.display());
+        included += 1;
+    }
+    writer.finish().map_err(zip_error)?;
+
+    println!("Backed up {} files to {}", included, target);
+    Ok(())
+}
+
+// Restores every file in the archive. Existing files are kept alongside as
+// `<file>.pre-restore` in case the wrong archive was picked.
+pub fn restore(source: &str) -> io::Result<()> {
+    let passphrase = passphrase()?;
+    let mut archive = ZipArchive::new(File::open(source)?).map_err(zip_error)?;
+
+    for (name, destination) in backup_entries() {
+        let mut entry = match archive.by_name_decrypt(name, passphrase.as_bytes()) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => continue,
+            Err(zip::result::ZipError::InvalidPassword) => {
+                return Err(io::Error::new(io::ErrorKind::PermissionDenied, "Wrong backup passphrase"));
+            }
+            Err(e) => return Err(zip_error(e)),
+        };
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+
+        if destination.exists() {
+            fs::copy(&destination, format!("{}.pre-restore", destination.display()))?;
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        state_file::write_atomic(&destination, &contents)?;
+        println!("  restored {}", destination.display());
+    }
+
+    println!("Restore from {} complete", source);
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::skills::contains_term;
+use crate::Job;
+
+// Jobs never to apply to. Matching ignores case; keywords and company names
+// match as whole words, so "Meta" doesn't catch "Metadata Inc".
+#[derive(Serialize, Deserialize, Default)]
+pub struct Blacklist {
+    #[serde(default)]
+    pub companies: Vec<String>,
+    #[serde(default)]
+    pub title_keywords: Vec<String>,
+    // Cards don't carry the description, so these are checked by fetching
+    // each job's page before applying, and again on the open job page
+    #[serde(default)]
+    pub description_keywords: Vec<String>,
+}
+
+pub fn first_match<'a>(terms: &'a [String], text: &str) -> Option<&'a str> {
+    let text = text.to_lowercase();
+    terms
+        .iter()
+        .map(|term| term.trim())
+        .find(|term| contains_term(&text, &term.to_lowercase()))
+}
+
+// Why a job's search card is blacklisted, if it is
+pub fn card_reason(blacklist: &Blacklist, job: &Job) -> Option<String> {
+    if let Some(company) = job.company.as_deref() {
+        if let Some(name) = first_match(&blacklist.companies, company) {
+            return Some(format!("company {} is blacklisted ({})", company, name));
+        }
+    }
+    first_match(&blacklist.title_keywords, &job.job_title).map(|keyword| format!("title contains blacklisted keyword \"{}\"", keyword))
+}
+
+pub fn description_reason(blacklist: &Blacklist, description: Option<&str>) -> Option<String> {
+    first_match(&blacklist.description_keywords, description?).map(|keyword| format!("description contains blacklisted keyword \"{}\"", keyword))
+}
+
+pub fn apply_blacklist(blacklist: &Blacklist, jobs: Vec<Job>) -> Vec<Job> {
+    jobs.into_iter()
+        .filter(|job| match card_reason(blacklist, job) {
+            Some(reason) => {
+                println!("Skipping {}: {}", job.job_title, reason);
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+
+    fn blacklist() -> Blacklist {
+        Blacklist {
+            companies: vec!["Acme Staffing".to_string(), "Meta".to_string()],
+            title_keywords: vec!["Salesforce".to_string()],
+            description_keywords: vec!["W2 only".to_string()],
+        }
+    }
+
+    fn job(title: &str, company: &str) -> Job {
+        let mut job = discovered_job("2b9d5bc5-3c4d-4b6b-9a1e-6c1e9f1a2b3c", title.to_string(), JobSource::Search);
+        job.company = Some(company.to_string());
+        job
+    }
+
+    #[test]
+    fn companies_match_whole_words_ignoring_case() {
+        let blacklist = blacklist();
+        assert!(card_reason(&blacklist, &job("Rust Engineer", "ACME STAFFING LLC")).is_some());
+        assert!(card_reason(&blacklist, &job("Rust Engineer", "Meta")).is_some());
+        assert!(card_reason(&blacklist, &job("Rust Engineer", "Metadata Inc")).is_none());
+    }
+
+    #[test]
+    fn title_and_description_keywords() {
+        let blacklist = blacklist();
+        assert!(card_reason(&blacklist, &job("Salesforce Administrator", "Initech")).is_some());
+        assert!(description_reason(&blacklist, Some("This role is w2 only, no C2C")).is_some());
+        assert!(description_reason(&blacklist, Some("C2C welcome")).is_none());
+        assert!(description_reason(&blacklist, None).is_none());
+    }
+}
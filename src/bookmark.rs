@@ -0,0 +1,51 @@
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+
+use crate::rng::RunRng;
+use crate::timeouts::TimeoutConfig;
+use crate::{session, Job};
+
+// Finds the job page's save control and clicks it unless the job is
+// already saved. Returns "saved", "already_saved" or "missing".
+const SAVE_SCRIPT: &str = r#"
+    const selectors = ['[data-cy="saveJobButton"]', '[data-testid*="save-job"]', 'button[aria-label*="Save" i]', 'button[aria-label*="Unsave" i]'];
+    const button = selectors.map(s => document.querySelector(s)).find(el => el);
+    if (!button) return 'missing';
+    const label = ((button.getAttribute('aria-label') || '') + ' ' + button.innerText).toLowerCase();
+    if (button.getAttribute('aria-pressed') === 'true' || label.includes('unsave') || label.includes('saved')) return 'already_saved';
+    button.click();
+    return 'saved';
+"#;
+
+// Bookmark mode: adds the matching jobs to Dice's Saved Jobs instead of
+// applying, so they can be reviewed later from any device. Stops after
+// `limit` newly saved jobs.
+pub async fn save_jobs(driver: &WebDriver, timeouts: &TimeoutConfig, jobs: &[Job], limit: Option<usize>, rng: &mut RunRng) -> WebDriverResult<()> {
+    let (mut saved, mut already, mut missing) = (0, 0, 0);
+    for job in jobs {
+        if limit.is_some_and(|limit| saved >= limit) {
+            println!("Reached the limit of {} saved jobs", saved);
+            break;
+        }
+        session::navigate(driver, timeouts, &job.url).await?;
+        sleep(timeouts.page_load()).await;
+        let outcome: String = driver.execute(SAVE_SCRIPT, vec![]).await?.convert()?;
+        match outcome.as_str() {
+            "saved" => {
+                saved += 1;
+                println!("Saved {}", job.job_title);
+            }
+            "already_saved" => {
+                already += 1;
+                println!("{} is already in Saved Jobs", job.job_title);
+            }
+            _ => {
+                missing += 1;
+                println!("No save button on {} ({})", job.job_title, job.url);
+            }
+        }
+        sleep(rng.jitter(timeouts.navigation(), timeouts.jitter())).await;
+    }
+    println!("Saved {} jobs to Dice ({} were already saved, {} had no save button)", saved, already, missing);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::history::History;
+use crate::query_budget::QueryBudget;
+use crate::{category, Config, Job};
+
+// At most `max_applications` to one company within `days`, counting earlier
+// runs, so one employer doesn't get a pile of near-identical applications
+#[derive(Serialize, Deserialize)]
+pub struct CompanyCap {
+    pub max_applications: usize,
+    #[serde(default = "default_cap_days")]
+    pub days: u32,
+}
+
+fn default_cap_days() -> u32 {
+    7
+}
+
+// Company names differ in case and spacing between cards
+fn company_key(company: &str) -> String {
+    company.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+// Per-search, per-category and per-company application caps for one run.
+// Categories are keyed by name.
+pub struct RunBudget<'a> {
+    config: &'a Config,
+    queries: QueryBudget,
+    by_category: HashMap<String, usize>,
+    // Applications within the company cap's window, this run included
+    by_company: HashMap<String, usize>,
+}
+
+impl<'a> RunBudget<'a> {
+    pub fn new(config: &'a Config, history: &History) -> RunBudget<'a> {
+        let mut by_company = HashMap::new();
+        if let Some(cap) = &config.company_cap {
+            let since = Utc::now() - Duration::days(cap.days.into());
+            for record in history.records.iter().filter(|record| record.applied_at >= since) {
+                if let Some(company) = &record.company {
+                    *by_company.entry(company_key(company)).or_insert(0) += 1;
+                }
+            }
+        }
+        RunBudget {
+            config,
+            queries: QueryBudget::new(config),
+            by_category: HashMap::new(),
+            by_company,
+        }
+    }
+
+    // Why the job can't be applied to under this run's caps, if it can't
+    pub fn exhausted(&self, job: &Job) -> Option<String> {
+        if let Some(reason) = self.queries.exhausted(job) {
+            return Some(reason);
+        }
+        if let (Some(cap), Some(company)) = (&self.config.company_cap, &job.company) {
+            let applied = self.by_company.get(&company_key(company)).copied().unwrap_or(0);
+            if applied >= cap.max_applications {
+                return Some(format!("already applied to {} {} times in {} days (company_cap)", company, applied, cap.days));
+            }
+        }
+        let category = category::classify(&self.config.categories, &job.job_title)?;
+        let limit = *self.config.categories.max_applications.get(&category)?;
+        if self.by_category.get(&category).copied().unwrap_or(0) >= limit {
+            return Some(format!("category {} reached its max_applications ({})", category, limit));
+        }
+        None
+    }
+
+    pub fn record(&mut self, job: &Job) {
+        self.queries.record(job);
+        if let Some(category) = category::classify(&self.config.categories, &job.job_title) {
+            *self.by_category.entry(category).or_insert(0) += 1;
+        }
+        if let Some(company) = &job.company {
+            *self.by_company.entry(company_key(company)).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+    use crate::history::ApplicationRecord;
+
+    fn config() -> Config {
+        crate::test_config(json!({
+            "max_applications": 1,
+            "company_cap": { "max_applications": 2, "days": 7 },
+            "categories": { "max_applications": { "Data": 1 } },
+        }))
+    }
+
+    fn job(id: &str, title: &str, company: &str, query: Option<&str>) -> Job {
+        let mut job = discovered_job(id, title.to_string(), JobSource::Search);
+        job.company = Some(company.to_string());
+        job.query = query.map(str::to_string);
+        job
+    }
+
+    fn history(companies: &[&str]) -> History {
+        let dir = std::env::temp_dir().join(format!("dice_blast-budget-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut history = History::load(&dir.join("dice_blast.db")).unwrap();
+        for (i, company) in companies.iter().enumerate() {
+            let record: ApplicationRecord = serde_json::from_value(json!({
+                "job_id": format!("h{}", i),
+                "job_title": "Rust Engineer",
+                "url": format!("https://www.dice.com/job-detail/h{}", i),
+                "applied_at": Utc::now(),
+                "company": company,
+            }))
+            .unwrap();
+            history.record(record).unwrap();
+        }
+        history
+    }
+
+    #[test]
+    fn per_search_and_category_caps() {
+        let config = config();
+        let history = history(&[]);
+        let mut budget = RunBudget::new(&config, &history);
+        let first = job("j1", "Rust Engineer", "Initech", Some("rust"));
+        assert!(budget.exhausted(&first).is_none());
+        budget.record(&first);
+        assert!(budget.exhausted(&job("j2", "Rust Developer", "Globex", Some("rust"))).is_some());
+        assert!(budget.exhausted(&job("j3", "Rust Developer", "Globex", Some("golang"))).is_none());
+
+        let data = job("j4", "Data Engineer", "Globex", None);
+        assert!(budget.exhausted(&data).is_none());
+        budget.record(&data);
+        assert!(budget.exhausted(&job("j5", "Data Analyst", "Hooli", None)).is_some());
+    }
+
+    #[test]
+    fn company_cap_counts_earlier_runs() {
+        let config = config();
+        let history = history(&["Initech", "initech  "]);
+        let budget = RunBudget::new(&config, &history);
+        assert!(budget.exhausted(&job("j1", "Rust Engineer", "INITECH", None)).is_some());
+        assert!(budget.exhausted(&job("j2", "Rust Engineer", "Globex", None)).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::skills::contains_term;
+
+// Built-in title keywords per category, checked after the user's own rules.
+// Keywords match whole words, so "ml" doesn't match inside "html".
+const DEFAULT_RULES: &[(&str, &[&str])] = &[
+    ("Security", &["security", "appsec", "soc analyst", "penetration", "identity", "cyber"]),
+    ("Management", &["manager", "director", "head of", "vp", "scrum master", "program manager"]),
+    ("DevOps", &["devops", "sre", "site reliability", "platform engineer", "infrastructure", "cloud", "kubernetes"]),
+    ("Data", &["data", "machine learning", "ml", "analytics", "etl", "bi developer"]),
+    ("Backend", &["backend", "back-end", "back end", "java", "golang", "python", ".net", "software engineer"]),
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct CategoryRule {
+    pub category: String,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct CategoryConfig {
+    // Checked before the built-in rules, so they can add categories or
+    // override where a title lands
+    pub rules: Vec<CategoryRule>,
+    // Applications allowed per category in one run
+    pub max_applications: HashMap<String, usize>,
+}
+
+// First matching category for a job title, or None if nothing matches
+pub fn classify(config: &CategoryConfig, job_title: &str) -> Option<String> {
+    let title = job_title.to_lowercase();
+    let user = config
+        .rules
+        .iter()
+        .find(|rule| rule.keywords.iter().any(|k| contains_term(&title, k.trim().to_lowercase().as_str())))
+        .map(|rule| rule.category.clone());
+    user.or_else(|| {
+        DEFAULT_RULES
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| contains_term(&title, k)))
+            .map(|(category, _)| category.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_match_whole_words() {
+        let config = CategoryConfig::default();
+        assert_eq!(classify(&config, "ML Engineer").as_deref(), Some("Data"));
+        assert_eq!(classify(&config, "HTML Email Developer"), None);
+        assert_eq!(classify(&config, "Platform Engineer").as_deref(), Some("DevOps"));
+        assert_eq!(classify(&config, "Salesforce Platform Specialist"), None);
+        assert_eq!(classify(&config, "Senior .NET Developer").as_deref(), Some("Backend"));
+    }
+
+    #[test]
+    fn user_rules_come_first() {
+        let config = CategoryConfig {
+            rules: vec![CategoryRule { category: "Rust".to_string(), keywords: vec!["Rust".to_string()] }],
+            ..Default::default()
+        };
+        assert_eq!(classify(&config, "Rust Software Engineer").as_deref(), Some("Rust"));
+        assert_eq!(classify(&config, "Trust Software Engineer").as_deref(), Some("Backend"));
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+
+use crate::cron::Schedule;
+use crate::history::ApplicationStatus;
+
+#[derive(Parser)]
+#[command(name = "dice_blast", about = "Search Dice and apply to Easy Apply jobs")]
+pub struct Cli {
+    #[arg(long, global = true, help = "Config file (.json, .toml or .yaml); defaults to config.json, then config.toml, config.yaml in the config directory")]
+    pub config: Option<PathBuf>,
+    #[arg(long, global = true, help = "Config directory [default: ~/.config/dice_blast]")]
+    pub config_dir: Option<PathBuf>,
+    #[arg(long, global = true, help = "Directory for cookies, history and other state [default: ~/.local/state/dice_blast]")]
+    pub state_dir: Option<PathBuf>,
+    #[arg(long, global = true, help = "Run Chrome without a window")]
+    pub headless: bool,
+    #[arg(long, global = true, help = "RNG seed, to replay a previous run's timing")]
+    pub seed: Option<u64>,
+    #[arg(short, long, global = true, action = ArgAction::Count, help = "More output; -vv shows every filter's verdict for each job")]
+    pub verbose: u8,
+    #[arg(long, global = true, help = "Append a JSON line for every scraped job and application attempt to this file")]
+    pub jsonl: Option<PathBuf>,
+    // No subcommand searches and applies, like `apply`
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Answer a few questions and write a config file")]
+    Init,
+    #[command(about = "Log in to Dice in the browser and save the session cookies")]
+    Login,
+    #[command(about = "Run the searches and save matching jobs without applying")]
+    Search,
+    #[command(about = "Run the searches and add matching jobs to the queue for `apply --from-queue`")]
+    Scrape,
+    #[command(about = "Run the searches and apply to the matching jobs")]
+    Apply {
+        #[arg(long, help = "Stop after this many applications (capped by the consent limit)")]
+        limit: Option<usize>,
+        #[arg(long, help = "Apply to jobs queued by `scrape` instead of searching")]
+        from_queue: bool,
+    },
+    #[command(about = "Show response rates from the application history")]
+    Report,
+    #[command(about = "Show counts per application state and recent trends, or record an employer's answer with `status <job> <state>`")]
+    Status {
+        #[arg(help = "Job ID or URL of an application")]
+        job: Option<String>,
+        #[arg(value_enum, requires = "job", help = "Where the application stands now")]
+        state: Option<ApplicationStatus>,
+    },
+    #[command(about = "Record consent for automatic applications")]
+    Consent,
+    #[command(about = "Rank skills by demand and average pay across the scrape-only dataset")]
+    SkillsReport {
+        #[arg(long, default_value_t = 25, help = "How many skills to list")]
+        top: usize,
+    },
+    #[command(about = "Show salary percentiles by title and location from the scrape-only dataset")]
+    SalaryReport {
+        #[arg(long, default_value_t = 3, help = "Leave out title and location pairs with fewer priced postings")]
+        min_postings: usize,
+    },
+    #[command(about = "Check the config and history for problems")]
+    Audit,
+    #[command(about = "Walk every result page and write a dataset; never applies")]
+    ScrapeOnly {
+        #[arg(long, help = "Result pages to walk per search; overrides max_pages")]
+        max_pages: Option<usize>,
+    },
+    #[command(about = "Re-run the searches on an interval and announce new jobs")]
+    Watch,
+    #[command(about = "Stay running and search and apply on a cron schedule")]
+    Daemon {
+        #[arg(long, value_parser = Schedule::parse, help = "Cron expression in local time: minute hour day month weekday, e.g. \"0 9,14 * * MON-FRI\"")]
+        schedule: Schedule,
+    },
+    #[command(about = "Import saved searches from the Dice dashboard")]
+    ImportSavedSearches,
+    #[command(about = "Stay running and serve the local HTTP API, searching and applying on request (separate from `daemon`)")]
+    Serve,
+    #[command(about = "Talk to the browser extension over native messaging (started by the browser)")]
+    NativeHost {
+        // Browsers pass the calling extension's origin and, on Windows, a
+        // parent window handle; neither is needed
+        #[arg(hide = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        caller: Vec<String>,
+    },
+    #[command(about = "Print the native messaging host manifest for the browser extension")]
+    NativeHostManifest {
+        #[arg(help = "Chrome extension ID, or the Firefox add-on ID")]
+        extension_id: String,
+    },
+    #[command(about = "Compare this tool's apply URL with the one Dice builds")]
+    VerifyApplyUrl { job: String },
+    #[command(about = "Write every tracked job and application to a file, e.g. for a spreadsheet")]
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv, help = "Output format")]
+        format: ExportFormat,
+        #[arg(long, help = "File to write [default: standard output]")]
+        output: Option<PathBuf>,
+    },
+    #[command(about = "Compare two run reports")]
+    DiffRuns { run_a: String, run_b: String },
+    #[command(about = "Write an encrypted backup of the state files")]
+    Backup { path: Option<String> },
+    #[command(about = "Restore state files from a backup")]
+    Restore { source: String },
+    #[command(about = "Bundle logs and recent failures for a bug report")]
+    DebugBundle,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+}
@@ -0,0 +1,81 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::from_reader;
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+
+use crate::paths;
+use crate::state_file;
+
+pub const CONSENT_FILE: &str = "consent.json";
+const CONSENT_PHRASE: &str = "I AGREE";
+const DEFAULT_MAX_APPLICATIONS: u32 = 25;
+
+// One-time acknowledgment required before the bot submits anything on the
+// user's behalf. Guards against someone else running it on a shared machine.
+#[derive(Serialize, Deserialize)]
+pub struct Consent {
+    pub acknowledged_by: String,
+    pub acknowledged_at: DateTime<Utc>,
+    pub max_applications_per_run: u32,
+}
+
+pub fn prompt(question: &str) -> String {
+    print!("{}", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    let _ = io::stdin().read_line(&mut answer);
+    answer.trim().to_string()
+}
+
+pub fn load_consent() -> Option<Consent> {
+    let file = File::open(paths::state(CONSENT_FILE)).ok()?;
+    from_reader(BufReader::new(file)).ok()
+}
+
+// Interactive `dice_blast consent` flow
+pub fn record_consent() -> io::Result<()> {
+    println!("dice_blast will submit job applications on Dice using your logged-in session.");
+    println!("Applications cannot be withdrawn through this tool once submitted.");
+
+    let answer = prompt(&format!("Type '{}' to confirm you authorize automatic applications: ", CONSENT_PHRASE));
+    if answer != CONSENT_PHRASE {
+        println!("Consent not recorded.");
+        return Ok(());
+    }
+
+    let limit = prompt(&format!("Maximum applications per run [{}]: ", DEFAULT_MAX_APPLICATIONS));
+    let max_applications_per_run = if limit.is_empty() {
+        DEFAULT_MAX_APPLICATIONS
+    } else {
+        match limit.parse() {
+            Ok(value) => value,
+            Err(_) => {
+                println!("'{}' is not a number. Consent not recorded.", limit);
+                return Ok(());
+            }
+        }
+    };
+
+    let consent = Consent {
+        acknowledged_by: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default(),
+        acknowledged_at: Utc::now(),
+        max_applications_per_run,
+    };
+    let path = paths::state(CONSENT_FILE);
+    state_file::write_json_atomic(&path, &consent)?;
+    println!("Consent recorded in {} (limit {} applications per run).", path.display(), max_applications_per_run);
+    Ok(())
+}
+
+pub fn require_consent() -> WebDriverResult<Consent> {
+    match load_consent() {
+        Some(consent) => Ok(consent),
+        None => {
+            println!("Automatic applications are disabled until you run `dice_blast consent`.");
+            Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("No consent recorded".to_string())))
+        }
+    }
+}
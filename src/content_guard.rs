@@ -0,0 +1,110 @@
+use std::sync::LazyLock;
+use chrono::{Duration, Utc};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use thirtyfour::prelude::*;
+
+use crate::filters::JobFilters;
+use crate::history::History;
+use crate::{job_id_from_url, Job};
+
+// The description body on a job page, or null if it isn't there
+const DESCRIPTION_SCRIPT: &str = r#"
+    const el = document.querySelector('[data-testid="jobDescriptionHtml"], #jobDescription, [class*="job-description"]');
+    return el ? el.innerText : null;
+"#;
+
+// HTML entities the JSON-LD description may still carry
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&#?\w+;").unwrap());
+
+// Hash of a description, recorded with the application. Only the words
+// count, lowercased, so the JSON-LD text and the page's innerText hash the
+// same despite their different bullets, punctuation and entities, and
+// reformatted reposts still match.
+pub fn description_hash(text: &str) -> String {
+    let text = ENTITY.replace_all(text, " ");
+    let normalized = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    Sha256::digest(normalized.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// The open job page's description text
+pub async fn description_text(driver: &WebDriver) -> WebDriverResult<Option<String>> {
+    let text: Option<String> = driver.execute(DESCRIPTION_SCRIPT, vec![]).await?.convert()?;
+    Ok(text.filter(|text| !text.trim().is_empty()))
+}
+
+
+// Agencies repost the same description under new IDs, which the ID-based
+// dedupe can't see. Refuses a job whose description matches one applied to
+// within job_filters.duplicate_description_days.
+pub fn duplicate_reason(filters: &JobFilters, history: &History, job: &Job, hash: Option<&str>) -> Option<String> {
+    let days = filters.duplicate_description_days?;
+    let hash = hash?;
+    let since = Utc::now() - Duration::days(days.into());
+    let id = job_id_from_url(&job.url);
+    history
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.job_id != id && record.applied_at >= since && record.description_hash.as_deref() == Some(hash))
+        .map(|record| format!("same description as {} ({}), applied {}", record.job_title, record.job_id, record.applied_at.format("%Y-%m-%d")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job_posting::strip_html;
+
+    #[test]
+    fn hash_ignores_where_the_description_came_from() {
+        // JSON-LD HTML after job_posting::details, and the same page's innerText
+        let json_ld = strip_html("<p>We&rsquo;re hiring!</p><ul><li>Rust &amp; Tokio</li><li>5+ years</li></ul>");
+        let inner_text = "We’re hiring!\n\n• Rust & Tokio\n• 5+ years\n";
+        assert_eq!(description_hash(&json_ld), description_hash(inner_text));
+        assert_ne!(description_hash(inner_text), description_hash("We're hiring! Go & Tokio, 5+ years"));
+    }
+
+    #[test]
+    fn hash_ignores_case_and_whitespace() {
+        assert_eq!(description_hash("Build  firmware\nin RUST."), description_hash("build firmware in rust."));
+        assert_eq!(description_hash("x").len(), 64);
+    }
+
+    #[test]
+    fn duplicates_are_found_by_description_within_the_window() {
+        let dir = std::env::temp_dir().join(format!("dice_blast-guard-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut history = History::load(&dir.join(crate::history::HISTORY_FILE)).unwrap();
+        let hash = description_hash("Build firmware in Rust.");
+        let record = |job_id: &str, days_ago: i64| {
+            serde_json::from_value(serde_json::json!({
+                "job_id": job_id,
+                "job_title": "Firmware Engineer",
+                "url": format!("https://www.dice.com/job-detail/{}", job_id),
+                "applied_at": Utc::now() - Duration::days(days_ago),
+                "description_hash": hash,
+            }))
+            .unwrap()
+        };
+        history.record(record("old", 30)).unwrap();
+        let filters = JobFilters { duplicate_description_days: Some(14), ..Default::default() };
+        let job = |job_id: &str| crate::discovery::discovered_job(job_id, "Firmware Engineer".to_string(), crate::discovery::JobSource::Search);
+
+        // Applied to outside the window
+        assert!(duplicate_reason(&filters, &history, &job("new"), Some(&hash)).is_none());
+
+        history.record(record("recent", 3)).unwrap();
+        let reason = duplicate_reason(&filters, &history, &job("new"), Some(&hash)).unwrap();
+        assert!(reason.contains("(recent)"), "{}", reason);
+        // The same posting isn't a duplicate of itself
+        assert!(duplicate_reason(&filters, &history, &job("recent"), Some(&hash)).is_none());
+        assert!(duplicate_reason(&filters, &history, &job("new"), Some(&description_hash("Something else"))).is_none());
+        assert!(duplicate_reason(&JobFilters::default(), &history, &job("new"), Some(&hash)).is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
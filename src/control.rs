@@ -0,0 +1,70 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use tokio::sync::watch;
+
+// Pause/resume for interactive runs. A listener thread watches the terminal
+// for `p` and `r` and publishes the paused state on a watch channel that the
+// apply loops check between jobs. The terminal stays in line mode so log
+// output is unaffected, which means each key is followed by Enter.
+pub struct PauseControl {
+    paused: watch::Receiver<bool>,
+    stop: Arc<AtomicBool>,
+}
+
+impl PauseControl {
+    // None when stdin isn't a terminal (API server, cron, piped input)
+    pub fn spawn() -> Option<PauseControl> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        let (sender, paused) = watch::channel(false);
+        let stop = Arc::new(AtomicBool::new(false));
+        let listener_stop = stop.clone();
+        thread::spawn(move || {
+            // Polling lets the thread notice `stop` instead of blocking on stdin
+            // forever and swallowing the next prompt's input
+            while !listener_stop.load(Ordering::Relaxed) {
+                if !event::poll(Duration::from_millis(200)).unwrap_or(false) {
+                    continue;
+                }
+                let Ok(Event::Key(key)) = event::read() else {
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('p') if !*sender.borrow() => {
+                        println!("Pausing after the current job. Press r to resume.");
+                        let _ = sender.send(true);
+                    }
+                    KeyCode::Char('r') if *sender.borrow() => {
+                        println!("Resuming.");
+                        let _ = sender.send(false);
+                    }
+                    _ => {}
+                }
+            }
+        });
+        println!("Press p then Enter to pause after the current job, r then Enter to resume.");
+        Some(PauseControl { paused, stop })
+    }
+
+    pub async fn wait_if_paused(&mut self) {
+        if *self.paused.borrow() {
+            println!("Paused.");
+        }
+        // An error means the listener is gone; carry on rather than hang
+        let _ = self.paused.wait_for(|paused| !paused).await;
+    }
+}
+
+impl Drop for PauseControl {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+// No expression that can match at all goes this long without matching
+const SEARCH_YEARS: i32 = 5;
+
+// A five-field cron expression: minute, hour, day of month, month, day of
+// week. Fields take `*`, numbers, names (JAN, MON), ranges, steps and
+// comma lists; Sunday is 0 or 7. As in cron, when both day fields are
+// restricted a day matching either one counts.
+#[derive(Clone, Debug)]
+pub struct Schedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+fn value(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let value = match names.iter().position(|name| name.eq_ignore_ascii_case(text)) {
+        // Month names count from 1, weekday names from 0
+        Some(index) => index as u32 + min,
+        None => text.parse().map_err(|_| format!("'{}' is not a number or a name", text))?,
+    };
+    if value < min || value > max {
+        return Err(format!("{} is outside {}-{}", value, min, max));
+    }
+    Ok(value)
+}
+
+// The field's allowed values as bits
+fn field(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("'{}' is not a valid step", step)),
+            },
+            None => (part, None),
+        };
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((first, last)) => (value(first, min, max, names)?, value(last, min, max, names)?),
+            // "5/15" runs from 5 to the end of the field
+            None if step.is_some() => (value(range, min, max, names)?, max),
+            None => {
+                let single = value(range, min, max, names)?;
+                (single, single)
+            }
+        };
+        if first > last {
+            return Err(format!("range {} runs backwards", range));
+        }
+        for value in (first..=last).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl Schedule {
+    pub fn parse(expression: &str) -> Result<Schedule, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("expected 5 fields (minute hour day month weekday), found {}", fields.len()));
+        };
+        let weekdays = field(weekday, 0, 7, &WEEKDAYS).map_err(|e| format!("day of week: {}", e))?;
+        let schedule = Schedule {
+            expression: fields.join(" "),
+            minutes: field(minute, 0, 59, &[]).map_err(|e| format!("minute: {}", e))?,
+            hours: field(hour, 0, 23, &[]).map_err(|e| format!("hour: {}", e))?,
+            days: field(day, 1, 31, &[]).map_err(|e| format!("day of month: {}", e))?,
+            months: field(month, 1, 12, &MONTHS).map_err(|e| format!("month: {}", e))?,
+            // 7 is another name for Sunday
+            weekdays: (weekdays | (weekdays >> 7)) & 0x7f,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        };
+        if schedule.next_after(Local::now()).is_none() {
+            return Err(format!("'{}' never matches a real date", schedule.expression));
+        }
+        Ok(schedule)
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    // The first matching minute after `after`
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let end = start.with_year(start.year() + SEARCH_YEARS)?;
+        let mut time = start;
+        while time < end {
+            if self.months & (1 << time.month()) == 0 || !self.day_matches(time.date()) {
+                time = NaiveDateTime::from(time.date().succ_opt()?);
+                continue;
+            }
+            if self.hours & (1 << time.hour()) == 0 {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << time.minute()) != 0 {
+                // A time skipped by a daylight saving change is skipped here too
+                if let Some(local) = Local.from_local_datetime(&time).earliest() {
+                    return Some(local);
+                }
+            }
+            time += Duration::minutes(1);
+        }
+        None
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Local> {
+        Local.from_local_datetime(&NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()).unwrap()
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        let schedule = Schedule::parse(expression).unwrap();
+        schedule.next_after(at(after)).unwrap().format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    #[test]
+    fn weekday_mornings_and_afternoons() {
+        // 2026-10-16 is a Friday
+        assert_eq!(next("0 9,14 * * MON-FRI", "2026-10-16 08:30"), "2026-10-16 09:00");
+        assert_eq!(next("0 9,14 * * MON-FRI", "2026-10-16 09:00"), "2026-10-16 14:00");
+        assert_eq!(next("0 9,14 * * MON-FRI", "2026-10-16 15:00"), "2026-10-19 09:00");
+    }
+
+    #[test]
+    fn steps_ranges_and_names() {
+        assert_eq!(next("*/15 * * * *", "2026-10-16 10:07"), "2026-10-16 10:15");
+        assert_eq!(next("30 8-18/4 * * *", "2026-10-16 12:31"), "2026-10-16 16:30");
+        assert_eq!(next("0 0 1 jan *", "2026-10-16 00:00"), "2027-01-01 00:00");
+        assert_eq!(next("0 12 * * 7", "2026-10-16 00:00"), "2026-10-18 12:00");
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // The 20th (a Tuesday) or any Saturday, whichever comes first
+        assert_eq!(next("0 6 20 * SAT", "2026-10-16 07:00"), "2026-10-17 06:00");
+        assert_eq!(next("0 6 20 * SAT", "2026-10-17 07:00"), "2026-10-20 06:00");
+    }
+
+    #[test]
+    fn bad_expressions() {
+        assert!(Schedule::parse("0 9 * *").is_err());
+        assert!(Schedule::parse("60 9 * * *").is_err());
+        assert!(Schedule::parse("0 9 * * FUNDAY").is_err());
+        assert!(Schedule::parse("0 18-9 * * *").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("0 0 30 FEB *").is_err());
+    }
+}
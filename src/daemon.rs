@@ -0,0 +1,47 @@
+use chrono::{Local, Utc};
+use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+use tokio::time::Duration;
+
+use crate::cron::Schedule;
+use crate::history::History;
+use crate::run_report::StageTimings;
+use crate::zero_results::ZeroResultLog;
+use crate::{apply_queued, search_and_apply, Config, RunMode};
+
+// Sleeps are cut into steps no longer than this, so a machine waking from
+// suspend notices a run that came due while it slept
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+// `dice_blast daemon --schedule "0 9,14 * * MON-FRI"`: keeps the browser
+// session open and searches and applies each time the schedule comes due,
+// in local time, then applies to the queued jobs. A failed run is reported and the daemon waits for the
+// next one; a run due while another is going is skipped.
+pub async fn run(driver: &WebDriver, config: &Config, history: &mut History, url: &str, schedule: &Schedule) -> WebDriverResult<()> {
+    println!("Running on the schedule \"{}\"", schedule);
+    let mut zero_results = ZeroResultLog::new(config.zero_result_quiet_mins);
+    loop {
+        let Some(next) = schedule.next_after(Local::now()) else {
+            return Err(WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("The schedule \"{}\" has no more runs", schedule))));
+        };
+        println!("Next run at {}", next.format("%Y-%m-%d %H:%M"));
+        while let Ok(remaining) = (next - Local::now()).to_std() {
+            sleep(remaining.min(MAX_SLEEP)).await;
+        }
+
+        println!("Scheduled run starting at {}", Local::now().format("%Y-%m-%d %H:%M"));
+        let started = Utc::now();
+        let mut result = search_and_apply(driver, config, history, url, RunMode::Apply { limit: None }, StageTimings::default(), Some(&mut zero_results)).await;
+        // Then whatever `scrape` or the API's /jobs queued since the last run
+        if result.is_ok() && history.queue_len().unwrap_or(0) > 0 {
+            result = apply_queued(driver, config, history, None, StageTimings::default()).await;
+        }
+        let applied = history.records.iter().filter(|record| record.applied_at >= started).count();
+        let minutes = (Utc::now() - started).num_minutes();
+        match result {
+            Ok(()) => println!("Scheduled run finished in {} minutes: {} applications", minutes, applied),
+            Err(e) => println!("Scheduled run failed after {} minutes and {} applications: {}", minutes, applied, e),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io::BufReader;
+use regex::Regex;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use thirtyfour::support::sleep;
+
+use crate::rng::RunRng;
+use crate::timeouts::TimeoutConfig;
+use crate::{blacklist, job_posting, paths};
+use crate::{Config, Cookie, Job};
+
+// The opening tag of the description block on a server-rendered job page
+const DESCRIPTION_OPEN: &str = r#"(?is)<div[^>]*data-testid="jobDescriptionHtml"[^>]*>"#;
+
+// The description block's inner HTML, up to the </div> that closes it
+// rather than the first one after it
+fn description_block(html: &str) -> Option<&str> {
+    let open = Regex::new(DESCRIPTION_OPEN).unwrap().find(html)?;
+    let divs = Regex::new(r"(?i)<(/?)div\b[^>]*?(/?)>").unwrap();
+    let mut depth = 1;
+    for tag in divs.captures_iter(&html[open.end()..]) {
+        if !tag[1].is_empty() {
+            depth -= 1;
+        } else if tag[2].is_empty() {
+            depth += 1;
+        }
+        if depth == 0 {
+            let close = tag.get(0).unwrap();
+            return Some(&html[open.end()..open.end() + close.start()]);
+        }
+    }
+    None
+}
+
+// Visible text of a job page: the description block if it's there,
+// otherwise the whole body, with tags, scripts and common entities removed
+fn page_text(html: &str) -> String {
+    let html = description_block(html).map(str::to_string).unwrap_or_else(|| {
+        let scripts = Regex::new(r"(?is)<(script|style)[^>]*>.*?</(script|style)>").unwrap();
+        scripts.replace_all(html, " ").into_owned()
+    });
+    let tags = Regex::new(r"<[^>]+>").unwrap();
+    let text = tags.replace_all(&html, " ");
+    let text = text.replace("&nbsp;", " ").replace("&amp;", "&").replace("&#39;", "'").replace("&quot;", "\"");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The saved session, so pages come back as they do in the browser
+fn cookie_header() -> Option<HeaderValue> {
+    let file = File::open(paths::state(paths::COOKIES_FILE)).ok()?;
+    let cookies: Vec<Cookie> = serde_json::from_reader(BufReader::new(file)).ok()?;
+    let pairs: Vec<String> = cookies.iter().map(|cookie| format!("{}={}", cookie.name, cookie.value)).collect();
+    HeaderValue::from_str(&pairs.join("; ")).ok()
+}
+
+// Fetches job pages outside the browser, one at a time with the run's
+// navigation delay and jitter between them
+pub struct PageFetcher<'a> {
+    client: reqwest::Client,
+    timeouts: &'a TimeoutConfig,
+    rng: &'a mut RunRng,
+    fetched: usize,
+}
+
+impl<'a> PageFetcher<'a> {
+    pub fn new(timeouts: &'a TimeoutConfig, rng: &'a mut RunRng) -> PageFetcher<'a> {
+        let mut headers = HeaderMap::new();
+        if let Some(cookies) = cookie_header() {
+            headers.insert(header::COOKIE, cookies);
+        }
+        let client = reqwest::Client::builder()
+            .timeout(timeouts.page_load())
+            .default_headers(headers)
+            .build()
+            .unwrap_or_default();
+        PageFetcher { client, timeouts, rng, fetched: 0 }
+    }
+
+    pub async fn html(&mut self, job: &Job) -> Option<String> {
+        if self.fetched > 0 {
+            sleep(self.rng.jitter(self.timeouts.navigation(), self.timeouts.jitter())).await;
+        }
+        self.fetched += 1;
+        self.client.get(&job.url).send().await.ok()?.error_for_status().ok()?.text().await.ok()
+    }
+
+    // The JobPosting's description when the page has one
+    pub async fn description(&mut self, job: &Job) -> Option<String> {
+        let html = self.html(job).await?;
+        let posted = job_posting::from_html(&html).and_then(|posting| job_posting::details(&posting).description);
+        Some(posted.unwrap_or_else(|| page_text(&html)))
+    }
+}
+
+// Fetches each job's description between searching and applying and drops
+// the ones containing a blacklist.description_keywords phrase ("W2 only",
+// "Top Secret clearance"), so rejected jobs are never opened. Jobs whose
+// page can't be fetched are kept; the job page check catches them later.
+pub async fn drop_by_description(config: &Config, rng: &mut RunRng, jobs: Vec<Job>) -> Vec<Job> {
+    if config.blacklist.description_keywords.is_empty() || jobs.is_empty() {
+        return jobs;
+    }
+    println!("Reading {} job descriptions", jobs.len());
+    let mut fetcher = PageFetcher::new(&config.timeouts, rng);
+    let mut kept = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        let description = fetcher.description(&job).await;
+        match blacklist::description_reason(&config.blacklist, description.as_deref()) {
+            Some(reason) => println!("Skipping {}: {}", job.job_title, reason),
+            None => kept.push(job),
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_description_block() {
+        let html = r#"<html><body><nav>Sign in</nav><div class="card"><div data-testid="jobDescriptionHtml"><p>Must be a US citizen.</p><ul><li>W2&nbsp;only</li></ul></div></div><footer>Similar jobs</footer></body></html>"#;
+        assert_eq!(page_text(html), "Must be a US citizen. W2 only");
+    }
+
+    #[test]
+    fn keeps_nested_blocks_whole() {
+        let html = r#"<div data-testid="jobDescriptionHtml"><div><p>About us</p></div><div class="req"><p>Requirements</p><br/><div>W2 only</div></div></div><div>Similar jobs</div>"#;
+        assert_eq!(page_text(html), "About us Requirements W2 only");
+    }
+
+    #[test]
+    fn falls_back_to_the_body_without_scripts() {
+        let html = r#"<html><head><script>var phrase = "top secret";</script></head><body><h1>Rust Engineer</h1><p>Remote &amp; flexible</p></body></html>"#;
+        assert_eq!(page_text(html), "Rust Engineer Remote & flexible");
+    }
+}
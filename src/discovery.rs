@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::fmt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{canonical_job_url, job_id_from_url, Job};
+
+// Where a job was discovered, recorded alongside each application
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JobSource {
+    #[default]
+    Search,
+    Sitemap,
+    Feed,
+    // Posted to the API's /jobs endpoint, e.g. from a bookmarklet
+    Submitted,
+}
+
+impl fmt::Display for JobSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JobSource::Search => write!(f, "search"),
+            JobSource::Sitemap => write!(f, "sitemap"),
+            JobSource::Feed => write!(f, "feed"),
+            JobSource::Submitted => write!(f, "submitted"),
+        }
+    }
+}
+
+// Extra discovery sources merged with the search results
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryConfig {
+    pub sitemap_urls: Vec<String>,
+    pub feed_urls: Vec<String>,
+    // Sitemaps can list tens of thousands of postings; cap what we take from each source
+    pub max_jobs_per_source: usize,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        DiscoveryConfig {
+            sitemap_urls: Vec::new(),
+            feed_urls: Vec::new(),
+            max_jobs_per_source: 50,
+        }
+    }
+}
+
+pub fn job_detail_pattern() -> Regex {
+    Regex::new(r"https?://(?:www\.)?dice\.com/job-detail/([a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12})").unwrap()
+}
+
+fn strip_cdata(text: &str) -> &str {
+    text.trim()
+        .trim_start_matches("<![CDATA[")
+        .trim_end_matches("]]>")
+        .trim()
+}
+
+pub fn discovered_job(id: &str, title: String, source: JobSource) -> Job {
+    Job {
+        page_number: 0,
+        job_title: title,
+        url: canonical_job_url(id),
+        company: None,
+        location: None,
+        salary: None,
+        posted_age: None,
+        applicants: None,
+        source,
+        query: None,
+    }
+}
+
+// Sitemaps only carry URLs, so titles fall back to the job ID
+fn parse_sitemap(xml: &str, limit: usize) -> Vec<Job> {
+    let loc_pattern = Regex::new(r"<loc>(.*?)</loc>").unwrap();
+    let detail_pattern = job_detail_pattern();
+    loc_pattern
+        .captures_iter(xml)
+        .filter_map(|cap| detail_pattern.captures(strip_cdata(&cap[1])).map(|m| m[1].to_string()))
+        .take(limit)
+        .map(|id| {
+            let title = format!("Dice job {}", id);
+            discovered_job(&id, title, JobSource::Sitemap)
+        })
+        .collect()
+}
+
+fn parse_feed(xml: &str, limit: usize) -> Vec<Job> {
+    let item_pattern = Regex::new(r"(?s)<item>(.*?)</item>").unwrap();
+    let title_pattern = Regex::new(r"(?s)<title>(.*?)</title>").unwrap();
+    let link_pattern = Regex::new(r"(?s)<link>(.*?)</link>").unwrap();
+    let detail_pattern = job_detail_pattern();
+
+    item_pattern
+        .captures_iter(xml)
+        .filter_map(|item| {
+            let link = link_pattern.captures(&item[1])?;
+            let id = detail_pattern.captures(strip_cdata(&link[1]))?[1].to_string();
+            let title = title_pattern
+                .captures(&item[1])
+                .map(|t| strip_cdata(&t[1]).to_string())
+                .unwrap_or_else(|| format!("Dice job {}", id));
+            Some(discovered_job(&id, title, JobSource::Feed))
+        })
+        .take(limit)
+        .collect()
+}
+
+async fn fetch(url: &str) -> Option<String> {
+    match reqwest::get(url).await {
+        Ok(resp) => resp.text().await.ok(),
+        Err(e) => {
+            println!("Could not fetch {}: {}", url, e);
+            None
+        }
+    }
+}
+
+pub async fn discover_jobs(config: &DiscoveryConfig) -> Vec<Job> {
+    let mut jobs = Vec::new();
+    for url in &config.sitemap_urls {
+        if let Some(xml) = fetch(url).await {
+            let found = parse_sitemap(&xml, config.max_jobs_per_source);
+            println!("Found {} jobs in sitemap {}", found.len(), url);
+            jobs.extend(found);
+        }
+    }
+    for url in &config.feed_urls {
+        if let Some(xml) = fetch(url).await {
+            let found = parse_feed(&xml, config.max_jobs_per_source);
+            println!("Found {} jobs in feed {}", found.len(), url);
+            jobs.extend(found);
+        }
+    }
+    jobs
+}
+
+// Appends jobs that aren't already in the list, matched by job ID
+pub fn merge_jobs(jobs: &mut Vec<Job>, discovered: Vec<Job>) {
+    let mut seen: HashSet<String> = jobs.iter().map(|job| job_id_from_url(&job.url).to_string()).collect();
+    let before = jobs.len();
+    for job in discovered {
+        if seen.insert(job_id_from_url(&job.url).to_string()) {
+            jobs.push(job);
+        }
+    }
+    println!("Merged {} new jobs into the job list", jobs.len() - before);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(jobs: &[Job]) -> Vec<&str> {
+        jobs.iter().map(|job| job_id_from_url(&job.url)).collect()
+    }
+
+    #[test]
+    fn reads_job_ids_from_a_sitemap() {
+        let jobs = parse_sitemap(include_str!("../tests/fixtures/dice_sitemap.xml"), 50);
+        assert_eq!(ids(&jobs), vec!["f0767d15-68a2-4c23-95c6-5685dedf2d2d", "3b1e7c2a-9d4f-4e8b-a6c5-0f2d9e8b7a61", "8c4d2e1f-5a6b-4c7d-9e8f-1a2b3c4d5e6f"]);
+        assert!(jobs.iter().all(|job| job.source == JobSource::Sitemap));
+        assert_eq!(jobs[0].job_title, "Dice job f0767d15-68a2-4c23-95c6-5685dedf2d2d");
+        assert_eq!(parse_sitemap(include_str!("../tests/fixtures/dice_sitemap.xml"), 1).len(), 1);
+    }
+
+    #[test]
+    fn reads_job_ids_and_titles_from_a_feed() {
+        let jobs = parse_feed(include_str!("../tests/fixtures/dice_feed.xml"), 50);
+        assert_eq!(ids(&jobs), vec!["f0767d15-68a2-4c23-95c6-5685dedf2d2d", "3b1e7c2a-9d4f-4e8b-a6c5-0f2d9e8b7a61"]);
+        assert!(jobs.iter().all(|job| job.source == JobSource::Feed));
+        assert_eq!(jobs[0].job_title, "Senior Rust Engineer");
+        assert_eq!(jobs[1].job_title, "Dice job 3b1e7c2a-9d4f-4e8b-a6c5-0f2d9e8b7a61");
+    }
+
+    #[test]
+    fn malformed_feeds_yield_nothing() {
+        assert!(parse_feed(include_str!("../tests/fixtures/dice_feed_malformed.xml"), 50).is_empty());
+        assert!(parse_sitemap(include_str!("../tests/fixtures/dice_feed_malformed.xml"), 50).is_empty());
+        assert!(parse_feed("", 50).is_empty());
+    }
+
+    #[test]
+    fn merged_jobs_keep_the_source_that_found_them_first() {
+        let mut jobs = vec![discovered_job("f0767d15-68a2-4c23-95c6-5685dedf2d2d", "Senior Rust Engineer".to_string(), JobSource::Search)];
+        merge_jobs(&mut jobs, parse_sitemap(include_str!("../tests/fixtures/dice_sitemap.xml"), 50));
+        merge_jobs(&mut jobs, parse_feed(include_str!("../tests/fixtures/dice_feed.xml"), 50));
+        let sources: Vec<JobSource> = jobs.iter().map(|job| job.source).collect();
+        assert_eq!(sources, vec![JobSource::Search, JobSource::Sitemap, JobSource::Sitemap]);
+    }
+}
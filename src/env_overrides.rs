@@ -0,0 +1,161 @@
+use serde_json::{Map, Value};
+
+use crate::validate::KNOWN_TOP_LEVEL;
+
+const PREFIX: &str = "DICE_BLAST_";
+// Separates nested keys: DICE_BLAST_TIMEOUTS__PAGE_LOAD_SECS
+const NESTING: &str = "__";
+// Env vars with the prefix that are read elsewhere, not config fields
+const NOT_CONFIG: [&str; 1] = ["DICE_BLAST_BACKUP_PASSPHRASE"];
+
+// Compares keys ignoring case and punctuation, so Q matches "q" and
+// FILTERS_EMPLOYMENT_TYPE matches "filters.employmentType"
+fn normalize(key: &str) -> String {
+    key.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+// Strings stay strings when the file already has a string there; anything
+// else is read as JSON when it parses, so numbers, booleans and arrays work
+fn parse_value(raw: &str, existing: Option<&Value>) -> Value {
+    if let Some(Value::String(_)) = existing {
+        return Value::String(raw.to_string());
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+// The variable that sets a top-level field, splitting camelCase and dots
+// into words: countryCode -> DICE_BLAST_COUNTRY_CODE
+pub fn var_for(field: &str) -> String {
+    let mut name = PREFIX.to_string();
+    let mut after_lower = false;
+    for c in field.chars() {
+        if c == '.' || c == '_' {
+            name.push('_');
+            after_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && after_lower {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+        after_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+    }
+    name
+}
+
+// Sets `path` to `raw`, reusing a key already in the object. A missing key
+// is taken from `fields` (the config's own names at the top level, where
+// serde renames some of them); below that fields are snake_case. Returns
+// false when the path names no field.
+fn set_path(object: &mut Map<String, Value>, path: &[&str], raw: &str, fields: Option<&[&str]>) -> bool {
+    let Some((first, rest)) = path.split_first() else {
+        return false;
+    };
+    let wanted = normalize(first);
+    let existing = object.keys().find(|key| normalize(key) == wanted).cloned();
+    let key = match (existing, fields) {
+        (Some(key), _) => key,
+        (None, Some(fields)) => match fields.iter().find(|field| normalize(field) == wanted) {
+            Some(field) => field.to_string(),
+            None => return false,
+        },
+        (None, None) => first.to_lowercase(),
+    };
+
+    if rest.is_empty() {
+        let value = parse_value(raw, object.get(&key));
+        object.insert(key, value);
+        return true;
+    }
+    let child = object.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    if !child.is_object() {
+        *child = Value::Object(Map::new());
+    }
+    match child {
+        Value::Object(child) => set_path(child, rest, raw, None),
+        _ => false,
+    }
+}
+
+fn apply_vars(document: &mut Value, mut vars: Vec<(String, String)>) {
+    let Value::Object(object) = document else {
+        return;
+    };
+    vars.sort();
+    for (name, raw) in vars {
+        let path: Vec<&str> = name[PREFIX.len()..].split(NESTING).collect();
+        if set_path(object, &path, &raw, Some(&KNOWN_TOP_LEVEL)) {
+            println!("Config override from {}", name);
+        } else {
+            println!("Warning: {} matches no config field and is ignored", name);
+        }
+    }
+}
+
+// Layers DICE_BLAST_* environment variables over the config document
+pub fn apply(document: &mut Value) {
+    let vars = std::env::vars()
+        .filter(|(name, _)| name.starts_with(PREFIX) && !NOT_CONFIG.contains(&name.as_str()))
+        .collect();
+    apply_vars(document, vars);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn applied(document: Value, vars: &[(&str, &str)]) -> Value {
+        let mut document = document;
+        apply_vars(&mut document, vars.iter().map(|(name, raw)| (name.to_string(), raw.to_string())).collect());
+        document
+    }
+
+    #[test]
+    fn overrides_existing_keys() {
+        let document = applied(json!({ "q": "rust", "countryCode": "US" }), &[("DICE_BLAST_Q", "golang"), ("DICE_BLAST_COUNTRYCODE", "CA")]);
+        assert_eq!(document, json!({ "q": "golang", "countryCode": "CA" }));
+    }
+
+    #[test]
+    fn adds_missing_keys_under_their_config_names() {
+        let document = applied(
+            json!({}),
+            &[
+                ("DICE_BLAST_COUNTRY_CODE", "US"),
+                ("DICE_BLAST_FILTERS_EMPLOYMENT_TYPE", "FULLTIME"),
+                ("DICE_BLAST_FILTERS_EASY_APPLY", "true"),
+                ("DICE_BLAST_NOT_A_FIELD", "1"),
+            ],
+        );
+        assert_eq!(document, json!({ "countryCode": "US", "filters.employmentType": "FULLTIME", "filters.easyApply": true }));
+    }
+
+    #[test]
+    fn nested_paths() {
+        let document = applied(
+            json!({ "timeouts": { "page_load_secs": 30 } }),
+            &[("DICE_BLAST_TIMEOUTS__PAGE_LOAD_SECS", "60"), ("DICE_BLAST_JOB_FILTERS__MIN_SALARY", "120000")],
+        );
+        assert_eq!(document, json!({ "timeouts": { "page_load_secs": 60 }, "job_filters": { "min_salary": 120000 } }));
+    }
+
+    #[test]
+    fn strings_stay_strings() {
+        let document = applied(
+            json!({ "q": "rust" }),
+            &[("DICE_BLAST_Q", "42"), ("DICE_BLAST_MAX_PAGES", "3"), ("DICE_BLAST_LANGUAGE", "en"), ("DICE_BLAST_SEARCHES", "[]")],
+        );
+        assert_eq!(document, json!({ "q": "42", "max_pages": 3, "language": "en", "searches": [] }));
+    }
+
+    #[test]
+    fn every_field_has_a_variable() {
+        for field in KNOWN_TOP_LEVEL {
+            let name = var_for(field);
+            let mut document = json!({});
+            apply_vars(&mut document, vec![(name.clone(), "\"x\"".to_string())]);
+            assert!(document.get(field).is_some(), "{} doesn't set {}", name, field);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::{job_id_from_url, Job};
+
+// `--jsonl <path>`: one JSON object per line for every scraped job and
+// every application attempt, appended and flushed as it happens so other
+// tools can tail the file
+static SINK: OnceLock<Mutex<File>> = OnceLock::new();
+
+pub fn open(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    if SINK.set(Mutex::new(file)).is_err() {
+        println!("The JSON Lines output was already open; ignoring {}", path.display());
+    }
+    Ok(())
+}
+
+// A failed write is reported and otherwise ignored; it shouldn't stop a run
+fn emit(event: Value) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let mut file = sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Err(e) = writeln!(file, "{}", event).and_then(|()| file.flush()) {
+        println!("Could not write to the JSON Lines output: {}", e);
+    }
+}
+
+pub fn jobs_scraped(jobs: &[Job]) {
+    if SINK.get().is_none() {
+        return;
+    }
+    for job in jobs {
+        let mut event = json!({ "event": "job", "at": Utc::now(), "job_id": job_id_from_url(&job.url) });
+        if let (Some(event), Value::Object(fields)) = (event.as_object_mut(), json!(job)) {
+            event.extend(fields);
+        }
+        emit(event);
+    }
+}
+
+// `outcome` is "applied", "skipped" or "failed", as in the tracking
+// database; `error` is the failure, or the reason for a skip
+pub fn application(job: &Job, outcome: &str, error: Option<&str>) {
+    emit(json!({
+        "event": "application",
+        "at": Utc::now(),
+        "job_id": job_id_from_url(&job.url),
+        "job_title": job.job_title,
+        "url": job.url,
+        "company": job.company,
+        "outcome": outcome,
+        "error": error,
+    }));
+}
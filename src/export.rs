@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::cli::ExportFormat;
+use crate::history::{ApplicationRecord, History, TrackedJob};
+
+const COLUMNS: [&str; 8] = ["title", "company", "location", "salary", "url", "status", "applied_at", "first_seen"];
+
+// Quoted only when it has to be, doubling any quotes inside. Cells that a
+// spreadsheet would run as a formula get a leading ' so they stay text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn csv_line(fields: &[&str]) -> String {
+    fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",")
+}
+
+// One row per tracked job, with its latest application if there is one,
+// then applications whose job isn't tracked (made before the database)
+fn rows(jobs: &[TrackedJob], records: &[ApplicationRecord]) -> Vec<[String; 8]> {
+    // Records are oldest first, so the last one per job wins
+    let latest: HashMap<&str, &ApplicationRecord> = records.iter().map(|record| (record.job_id.as_str(), record)).collect();
+    let tracked: HashSet<&str> = jobs.iter().map(|job| job.job_id.as_str()).collect();
+    let status = |record: &ApplicationRecord| serde_json::to_value(record.status).ok().and_then(|value| value.as_str().map(str::to_string)).unwrap_or_default();
+    let mut rows: Vec<[String; 8]> = jobs
+        .iter()
+        .map(|job| {
+            let record = latest.get(job.job_id.as_str()).copied();
+            [
+                job.title.clone(),
+                job.company.clone().unwrap_or_default(),
+                job.location.clone().unwrap_or_default(),
+                job.salary.clone().unwrap_or_default(),
+                job.url.clone(),
+                record.map_or_else(|| "not applied".to_string(), status),
+                record.map(|record| record.applied_at.to_rfc3339()).unwrap_or_default(),
+                job.first_seen.to_rfc3339(),
+            ]
+        })
+        .collect();
+    for record in records.iter().filter(|record| !tracked.contains(record.job_id.as_str())) {
+        rows.push([
+            record.job_title.clone(),
+            record.company.clone().unwrap_or_default(),
+            String::new(),
+            String::new(),
+            record.url.clone(),
+            status(record),
+            record.applied_at.to_rfc3339(),
+            String::new(),
+        ]);
+    }
+    rows
+}
+
+fn write_csv(out: &mut impl Write, rows: &[[String; 8]]) -> io::Result<()> {
+    writeln!(out, "{}", csv_line(&COLUMNS))?;
+    for row in rows {
+        writeln!(out, "{}", csv_line(&row.each_ref().map(String::as_str)))?;
+    }
+    out.flush()
+}
+
+// `dice_blast export --format csv [--output jobs.csv]`
+pub fn export(history: &History, format: ExportFormat, output: Option<&Path>) -> io::Result<()> {
+    let rows = rows(&history.tracked_jobs()?, &history.records);
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match format {
+        ExportFormat::Csv => write_csv(&mut out, &rows)?,
+    }
+    if let Some(path) = output {
+        println!("Exported {} rows to {}", rows.len(), path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_line(&["Rust Engineer", "Acme, Inc.", "say \"hi\"", ""]), "Rust Engineer,\"Acme, Inc.\",\"say \"\"hi\"\"\",");
+    }
+
+    #[test]
+    fn formulas_are_kept_as_text() {
+        assert_eq!(csv_line(&["=HYPERLINK(\"x\")", "+1", "-2", "@SUM(A1)", "C++"]), "\"'=HYPERLINK(\"\"x\"\")\",'+1,'-2,'@SUM(A1),C++");
+    }
+
+    fn record(job_id: &str, applied_at: &str, status: &str) -> ApplicationRecord {
+        serde_json::from_value(serde_json::json!({
+            "job_id": job_id,
+            "job_title": "Rust Engineer",
+            "url": format!("https://www.dice.com/job-detail/{}", job_id),
+            "applied_at": applied_at,
+            "status": status,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rows_join_jobs_with_their_latest_application() {
+        let job = |job_id: &str| TrackedJob {
+            job_id: job_id.to_string(),
+            title: format!("Job {}", job_id),
+            url: format!("https://www.dice.com/job-detail/{}", job_id),
+            company: Some("Acme".to_string()),
+            location: None,
+            salary: None,
+            first_seen: "2024-01-01T00:00:00Z".parse().unwrap(),
+        };
+        let jobs = [job("a"), job("b")];
+        let records = [
+            record("a", "2024-01-02T00:00:00Z", "applied"),
+            record("old", "2023-06-01T00:00:00Z", "applied"),
+            record("a", "2024-01-03T00:00:00Z", "interviewing"),
+        ];
+        let rows = rows(&jobs, &records);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][0], "Job a");
+        assert_eq!(rows[0][5], "interviewing");
+        assert_eq!(rows[0][6], "2024-01-03T00:00:00+00:00");
+        assert_eq!(rows[1][0], "Job b");
+        assert_eq!(rows[1][5], "not applied");
+        assert_eq!(rows[1][6], "");
+        assert_eq!(rows[2][4], "https://www.dice.com/job-detail/old");
+        assert_eq!(rows[2][7], "");
+    }
+}
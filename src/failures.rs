@@ -0,0 +1,78 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use chrono::Utc;
+use thirtyfour::prelude::*;
+
+use crate::har;
+use crate::paths;
+use crate::state_file;
+use crate::{job_id_from_url, Job};
+
+pub const FAILURES_DIR: &str = "failures";
+
+// Writes a report with the panic message, location and backtrace before the
+// default hook prints it, so unattended runs leave something behind
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = format!(
+            "panic: {}\nlocation: {}\n\nbacktrace:\n{}\n",
+            info.payload_as_str().unwrap_or("(non-string payload)"),
+            info.location().map(|l| l.to_string()).unwrap_or_default(),
+            Backtrace::force_capture()
+        );
+        let path = paths::state(FAILURES_DIR).join(format!("{}-panic.txt", Utc::now().format("%Y%m%d-%H%M%S")));
+        match fs::create_dir_all(paths::state(FAILURES_DIR)).and_then(|_| state_file::write_atomic(&path, report.as_bytes())) {
+            Ok(()) => eprintln!("Panic report written to {}", path.display()),
+            Err(e) => eprintln!("Could not write panic report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+// Saves a screenshot and a short text report for a job that failed to apply,
+// and with `har` set, the page's network activity.
+// Best effort: a failure while capturing is logged, never propagated.
+pub async fn capture_failure(driver: &WebDriver, job: &Job, error: &WebDriverError, har: bool) -> Option<PathBuf> {
+    if let Err(e) = fs::create_dir_all(paths::state(FAILURES_DIR)) {
+        println!("Could not create {}: {}", paths::state(FAILURES_DIR).display(), e);
+        return None;
+    }
+    let stem = format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S"), job_id_from_url(&job.url));
+    let base = paths::state(FAILURES_DIR).join(stem);
+
+    let current_url = driver.current_url().await.map(|u| u.to_string()).unwrap_or_default();
+    let report = format!(
+        "job: {}\nurl: {}\npage: {}\nerror: {}\n",
+        job.job_title, job.url, current_url, error
+    );
+    if let Err(e) = state_file::write_atomic(&base.with_extension("txt"), report.as_bytes()) {
+        println!("Could not write failure report: {}", e);
+    }
+
+    match driver.screenshot(&base.with_extension("png")).await {
+        Ok(()) => println!("Saved failure screenshot to {}", base.with_extension("png").display()),
+        Err(e) => println!("Could not capture failure screenshot: {}", e),
+    }
+    if har {
+        match har::save_har(driver, &base.with_extension("har")).await {
+            Ok(()) => println!("Saved failure HAR to {}", base.with_extension("har").display()),
+            Err(e) => println!("Could not capture failure HAR: {}", e),
+        }
+    }
+    Some(base)
+}
+
+// Saves the markup of a search card that couldn't be parsed, so selector
+// changes can be diagnosed from a debug bundle
+pub fn capture_card_html(page_number: usize, card_number: usize, html: &str, error: &str) {
+    let name = format!("{}-page{}-card{}.html", Utc::now().format("%Y%m%d-%H%M%S"), page_number, card_number);
+    let path = paths::state(FAILURES_DIR).join(name);
+    let contents = format!("<!-- error: {} -->\n{}\n", error.replace("--", "- -"), html);
+    match fs::create_dir_all(paths::state(FAILURES_DIR)).and_then(|_| state_file::write_atomic(&path, contents.as_bytes())) {
+        Ok(()) => println!("Saved the card's HTML to {}", path.display()),
+        Err(e) => println!("Could not save card HTML: {}", e),
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+
+use crate::archive::{self, PostingArchive, RepostPolicy};
+use crate::budget::RunBudget;
+use crate::history::History;
+use crate::seniority::{self, GuardAction};
+use crate::{applied_reason, blacklist, filters, job_id_from_url, salary, synced_reason, third_party, whitelist, workplace, Config, Job};
+
+// With -vv, every check filter_jobs and apply_batch make is run against
+// every job, through the same functions, without stopping at the first
+// failure, so it's clear exactly why a job was or wasn't applied to. Checks
+// that need the job's page or description show up here as "later".
+enum Verdict {
+    Off,
+    Pass(String),
+    Warn(String),
+    Fail(String),
+    Later,
+}
+
+fn check(reason: Option<String>, passed: &str) -> Verdict {
+    match reason {
+        Some(reason) => Verdict::Fail(reason),
+        None => Verdict::Pass(passed.to_string()),
+    }
+}
+
+fn evaluate(config: &Config, history: &History, archive: &PostingArchive, budget: &RunBudget, remote_applied: &BTreeSet<String>, job: &Job) -> Vec<(&'static str, Verdict)> {
+    let job_filters = &config.job_filters;
+    let repost = match archive::repost_reason(archive, job) {
+        Some(reason) => match config.repost_policy {
+            RepostPolicy::Skip => Verdict::Fail(reason),
+            RepostPolicy::Flag => Verdict::Warn(reason),
+            RepostPolicy::Reapply => Verdict::Pass(reason),
+        },
+        None => Verdict::Pass("not seen before under another ID".to_string()),
+    };
+    let whitelist = match &config.whitelist {
+        Some(list) => check(whitelist::whitelist_reason(list, job), "listed"),
+        None => Verdict::Off,
+    };
+    let max_applicants = match (job_filters.max_applicants, job.applicants) {
+        (None, _) => Verdict::Off,
+        (Some(_), None) => Verdict::Pass("card shows no applicant count".to_string()),
+        (Some(max), Some(applicants)) => check(filters::applicants_reason(job_filters, job), &format!("{} applicants, max {}", applicants, max)),
+    };
+    let min_salary = match (job_filters.min_salary, job.salary.as_deref()) {
+        (None, _) => Verdict::Off,
+        (Some(_), None) => Verdict::Pass("card shows no pay".to_string()),
+        (Some(min), salary) => check(salary::below_minimum(job_filters, salary), &format!("{}, min {}", salary.unwrap_or_default(), min)),
+    };
+    let seniority = match config.profile.seniority {
+        None => Verdict::Off,
+        Some(profile) => match seniority::guard_reason(&job_filters.seniority, profile, &job.job_title) {
+            None => Verdict::Pass("within max_gap of the profile".to_string()),
+            Some((GuardAction::Warn, reason)) => Verdict::Warn(reason),
+            Some((GuardAction::Skip, reason)) => Verdict::Fail(reason),
+        },
+    };
+    let third_party = if !job_filters.skip_third_party {
+        Verdict::Off
+    } else {
+        third_party::company_reason(job).map_or(Verdict::Later, Verdict::Fail)
+    };
+    let later_if = |on: bool| if on { Verdict::Later } else { Verdict::Off };
+    vec![
+        ("repost", repost),
+        ("already_applied", check(applied_reason(history, job), "not in the application history")),
+        ("blacklist", check(blacklist::card_reason(&config.blacklist, job), "no company or title match")),
+        ("whitelist", whitelist),
+        ("title", check(filters::title_reason(job_filters, &job.job_title), "matches title patterns")),
+        ("max_applicants", max_applicants),
+        ("min_salary", min_salary),
+        ("seniority", seniority),
+        ("third_party", third_party),
+        ("description", later_if(!config.blacklist.description_keywords.is_empty())),
+        ("synced_applied", check(synced_reason(remote_applied, job), "not in the synced applied set")),
+        ("budget", check(budget.exhausted(job), "within search, category and company caps")),
+        ("workplace", later_if(!workplace::filter_for(config, job).is_empty())),
+        ("job_page_salary", later_if(job_filters.min_salary.is_some())),
+        ("duplicate", later_if(job_filters.duplicate_description_days.is_some())),
+    ]
+}
+
+// The first check a job fails, or, when it passes them all, the checks
+// still to be made on its page
+pub fn decide(config: &Config, history: &History, archive: &PostingArchive, budget: &RunBudget, remote_applied: &BTreeSet<String>, job: &Job) -> Result<Vec<&'static str>, String> {
+    let mut later = Vec::new();
+    for (name, verdict) in evaluate(config, history, archive, budget, remote_applied, job) {
+        match verdict {
+            Verdict::Fail(reason) => return Err(reason),
+            Verdict::Later => later.push(name),
+            Verdict::Off | Verdict::Pass(_) | Verdict::Warn(_) => {}
+        }
+    }
+    Ok(later)
+}
+
+pub fn print(config: &Config, history: &History, archive: &PostingArchive, remote_applied: &BTreeSet<String>, jobs: &[Job]) {
+    // Caps as they stand at the start of the run
+    let budget = RunBudget::new(config, history);
+    for job in jobs {
+        let verdicts = evaluate(config, history, archive, &budget, remote_applied, job);
+        let kept = !verdicts.iter().any(|(_, verdict)| matches!(verdict, Verdict::Fail(_)));
+        println!("{} ({}): {}", job.job_title, job_id_from_url(&job.url), if kept { "kept" } else { "filtered out" });
+        for (name, verdict) in verdicts {
+            let (result, detail) = match verdict {
+                Verdict::Off => ("off", String::new()),
+                Verdict::Pass(detail) => ("pass", detail),
+                Verdict::Warn(detail) => ("warn", detail),
+                Verdict::Fail(detail) => ("FAIL", detail),
+                Verdict::Later => ("later", "checked against the job page".to_string()),
+            };
+            println!("  {:<16} {:<5} {}", name, result, detail);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use regex::{Regex, RegexBuilder};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::salary;
+use crate::seniority::SeniorityGuard;
+use crate::Job;
+
+// Local filters applied to scraped jobs before anything is opened
+#[derive(Serialize, Deserialize, Default)]
+pub struct JobFilters {
+    // Skip postings that already have more applicants than this
+    #[serde(default)]
+    pub max_applicants: Option<u32>,
+    // Warns about or skips titles far from profile.seniority
+    #[serde(default)]
+    pub seniority: SeniorityGuard,
+    // Drop staffing-agency and other middleman postings
+    #[serde(default)]
+    pub skip_third_party: bool,
+    // Annual pay; hourly and other rates are annualized before comparing.
+    // Jobs that don't post pay are kept.
+    #[serde(default)]
+    pub min_salary: Option<u32>,
+    // Refuse a job whose description matches one applied to within this
+    // many days, whatever its ID
+    #[serde(default)]
+    pub duplicate_description_days: Option<u32>,
+    // Case-insensitive regexes on the job title. With any includes, a title
+    // must match one of them; a title matching any exclude is dropped.
+    #[serde(default)]
+    pub title_include: Patterns,
+    #[serde(default)]
+    pub title_exclude: Patterns,
+}
+
+// Patterns in the config are matched ignoring case
+pub fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+// A list of config patterns, compiled once when the config is loaded so a
+// bad one stops the run at startup
+#[derive(Default)]
+pub struct Patterns(Vec<Regex>);
+
+impl Patterns {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Patterns, regex::Error> {
+        patterns.iter().map(|pattern| compile_pattern(pattern.as_ref())).collect::<Result<_, _>>().map(Patterns)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // The first pattern matching `text`
+    pub fn first_match(&self, text: &str) -> Option<&str> {
+        self.0.iter().find(|regex| regex.is_match(text)).map(Regex::as_str)
+    }
+}
+
+impl Serialize for Patterns {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(Regex::as_str))
+    }
+}
+
+impl<'de> Deserialize<'de> for Patterns {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Vec::<String>::deserialize(deserializer)?;
+        Patterns::new(&raw).map_err(de::Error::custom)
+    }
+}
+
+pub fn title_reason(filters: &JobFilters, title: &str) -> Option<String> {
+    if let Some(pattern) = filters.title_exclude.first_match(title) {
+        return Some(format!("title matches title_exclude /{}/", pattern));
+    }
+    if !filters.title_include.is_empty() && filters.title_include.first_match(title).is_none() {
+        return Some("title matches none of title_include".to_string());
+    }
+    None
+}
+
+// Returns why a job should be skipped, or None if it passes every filter
+pub fn rejection_reason(filters: &JobFilters, job: &Job) -> Option<String> {
+    title_reason(filters, &job.job_title)
+        .or_else(|| applicants_reason(filters, job))
+        .or_else(|| salary::below_minimum(filters, job.salary.as_deref()))
+}
+
+pub fn applicants_reason(filters: &JobFilters, job: &Job) -> Option<String> {
+    let (max, applicants) = (filters.max_applicants?, job.applicants?);
+    (applicants > max).then(|| format!("{} applicants exceeds max_applicants ({})", applicants, max))
+}
+
+pub fn apply_filters(filters: &JobFilters, jobs: Vec<Job>) -> Vec<Job> {
+    let total = jobs.len();
+    let kept: Vec<Job> = jobs
+        .into_iter()
+        .filter(|job| match rejection_reason(filters, job) {
+            Some(reason) => {
+                println!("Skipping {}: {}", job.job_title, reason);
+                false
+            }
+            None => true,
+        })
+        .collect();
+    println!("{} of {} jobs passed filters", kept.len(), total);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_include_and_exclude() {
+        let filters = JobFilters {
+            title_include: Patterns::new(&[r"\brust\b", "golang"]).unwrap(),
+            title_exclude: Patterns::new(&["salesforce", r"\bintern\b"]).unwrap(),
+            ..Default::default()
+        };
+        assert!(title_reason(&filters, "Senior Rust Engineer").is_none());
+        assert!(title_reason(&filters, "Golang Developer").is_none());
+        assert!(title_reason(&filters, "Salesforce Administrator").is_some());
+        assert!(title_reason(&filters, "Rust Intern").is_some());
+        assert!(title_reason(&filters, "Trust Analyst").is_some());
+    }
+
+    #[test]
+    fn bad_patterns_fail_to_load() {
+        let filters: JobFilters = serde_json::from_str(r#"{"title_exclude": ["(?i)intern"]}"#).unwrap();
+        assert_eq!(filters.title_exclude.first_match("INTERN"), Some("(?i)intern"));
+        assert!(serde_json::from_str::<JobFilters>(r#"{"title_include": ["rust("]}"#).is_err());
+    }
+}
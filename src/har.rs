@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use chrono::DateTime;
+use serde_json::{json, Value};
+use thirtyfour::extensions::cdp::ChromeDevTools;
+use thirtyfour::prelude::*;
+use thirtyfour::{CapabilitiesHelper, ExtensionCommand, RequestMethod};
+
+use crate::state_file;
+
+// WebDriver can't subscribe to DevTools events directly, so chromedriver is
+// asked to keep them in its performance log, and the Network domain's
+// events are read back from there when a HAR is wanted.
+pub fn enable_performance_log(caps: &mut ChromeCapabilities) {
+    caps.insert_base_capability("goog:loggingPrefs".to_string(), json!({ "performance": "ALL" }));
+}
+
+// chromedriver's log endpoint; reading the log also empties it
+#[derive(Debug)]
+struct PerformanceLog;
+
+impl ExtensionCommand for PerformanceLog {
+    fn parameters_json(&self) -> Option<Value> {
+        Some(json!({ "type": "performance" }))
+    }
+
+    fn method(&self) -> RequestMethod {
+        RequestMethod::Post
+    }
+
+    fn endpoint(&self) -> Arc<str> {
+        Arc::from("se/log")
+    }
+}
+
+// The DevTools events in the performance log since it was last read
+async fn network_events(driver: &WebDriver) -> WebDriverResult<Vec<Value>> {
+    let log = driver.extension_command(PerformanceLog).await?;
+    let events = log
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| serde_json::from_str::<Value>(entry["message"].as_str()?).ok())
+        .map(|mut message| message["message"].take())
+        .filter(|event| event["method"].as_str().is_some_and(|method| method.starts_with("Network.")))
+        .collect();
+    Ok(events)
+}
+
+// Called when the job page opens: turns on network events and drops
+// whatever earlier pages left in the log
+pub async fn start_recording(driver: &WebDriver) -> WebDriverResult<()> {
+    ChromeDevTools::new(driver.handle.clone()).execute_cdp("Network.enable").await?;
+    network_events(driver).await?;
+    Ok(())
+}
+
+fn headers(headers: &Value) -> Vec<Value> {
+    headers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| json!({ "name": name, "value": value.as_str().unwrap_or_default() }))
+        .collect()
+}
+
+fn ms(value: f64) -> f64 {
+    value.max(0.0)
+}
+
+// A request being followed through its events
+struct Exchange {
+    // Seconds since the epoch, and Chrome's monotonic clock, when it started
+    wall_time: f64,
+    started: f64,
+    request: Value,
+    response: Option<Value>,
+    finished: Option<f64>,
+    size: Option<f64>,
+    error: Option<String>,
+}
+
+impl Exchange {
+    fn into_entry(self) -> Value {
+        let time = self.finished.map_or(0.0, |finished| ms((finished - self.started) * 1000.0));
+        let response = self.response.unwrap_or(Value::Null);
+        let timing = &response["timing"];
+        let send = ms(timing["sendEnd"].as_f64().unwrap_or(0.0) - timing["sendStart"].as_f64().unwrap_or(0.0));
+        let wait = ms(timing["receiveHeadersEnd"].as_f64().unwrap_or(0.0) - timing["sendEnd"].as_f64().unwrap_or(0.0));
+        let started = DateTime::from_timestamp_millis((self.wall_time * 1000.0) as i64).unwrap_or_default();
+        let post_data = self.request["postData"].as_str().map(|text| json!({ "mimeType": "", "text": text }));
+        json!({
+            "startedDateTime": started.to_rfc3339(),
+            "time": time,
+            "request": {
+                "method": self.request["method"],
+                "url": self.request["url"],
+                "httpVersion": response["protocol"].as_str().unwrap_or_default(),
+                "headers": headers(&self.request["headers"]),
+                "queryString": [],
+                "cookies": [],
+                "headersSize": -1,
+                "bodySize": self.request["postData"].as_str().map_or(0, str::len),
+                "postData": post_data,
+            },
+            "response": {
+                "status": response["status"].as_u64().unwrap_or(0),
+                "statusText": self.error.as_deref().unwrap_or(response["statusText"].as_str().unwrap_or_default()),
+                "httpVersion": response["protocol"].as_str().unwrap_or_default(),
+                "headers": headers(&response["headers"]),
+                "cookies": [],
+                "content": { "size": self.size.unwrap_or(-1.0), "mimeType": response["mimeType"].as_str().unwrap_or_default() },
+                "redirectURL": response["headers"]["location"].as_str().unwrap_or_default(),
+                "headersSize": -1,
+                "bodySize": self.size.unwrap_or(-1.0),
+            },
+            "cache": {},
+            "timings": { "send": send, "wait": wait, "receive": ms(time - send - wait) },
+            "pageref": "page_1",
+        })
+    }
+}
+
+// HAR 1.2 from Network.requestWillBeSent, responseReceived, loadingFinished
+// and loadingFailed events. A redirect ends one entry and starts the next
+// under the same request ID.
+fn har_from_events(events: &[Value], title: &str) -> Value {
+    let mut open: HashMap<String, Exchange> = HashMap::new();
+    let mut done = Vec::new();
+    for event in events {
+        let params = &event["params"];
+        let Some(id) = params["requestId"].as_str() else {
+            continue;
+        };
+        match event["method"].as_str().unwrap_or_default() {
+            "Network.requestWillBeSent" => {
+                if let Some(mut redirected) = open.remove(id) {
+                    redirected.response = Some(params["redirectResponse"].clone());
+                    redirected.finished = params["timestamp"].as_f64();
+                    done.push(redirected);
+                }
+                open.insert(
+                    id.to_string(),
+                    Exchange {
+                        wall_time: params["wallTime"].as_f64().unwrap_or(0.0),
+                        started: params["timestamp"].as_f64().unwrap_or(0.0),
+                        request: params["request"].clone(),
+                        response: None,
+                        finished: None,
+                        size: None,
+                        error: None,
+                    },
+                );
+            }
+            "Network.responseReceived" => {
+                if let Some(exchange) = open.get_mut(id) {
+                    exchange.response = Some(params["response"].clone());
+                }
+            }
+            "Network.loadingFinished" | "Network.loadingFailed" => {
+                if let Some(mut exchange) = open.remove(id) {
+                    exchange.finished = params["timestamp"].as_f64();
+                    exchange.size = params["encodedDataLength"].as_f64();
+                    exchange.error = params["errorText"].as_str().map(str::to_string);
+                    done.push(exchange);
+                }
+            }
+            _ => {}
+        }
+    }
+    // Requests still in flight when the HAR is taken
+    done.extend(open.into_values());
+    done.sort_by(|a, b| a.started.total_cmp(&b.started));
+
+    let page_started = done.first().map(|exchange| exchange.wall_time).unwrap_or(0.0);
+    let page_started = DateTime::from_timestamp_millis((page_started * 1000.0) as i64).unwrap_or_default();
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "dice_blast", "version": env!("CARGO_PKG_VERSION") },
+            "pages": [{ "startedDateTime": page_started.to_rfc3339(), "id": "page_1", "title": title, "pageTimings": {} }],
+            "entries": done.into_iter().map(Exchange::into_entry).collect::<Vec<_>>(),
+        }
+    })
+}
+
+pub async fn save_har(driver: &WebDriver, path: &Path) -> WebDriverResult<()> {
+    let events = network_events(driver).await?;
+    let title = driver.title().await.unwrap_or_default();
+    let har = har_from_events(&events, &title);
+    state_file::write_atomic(path, &serde_json::to_vec_pretty(&har)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_requests_through_redirects() {
+        let events = vec![
+            json!({ "method": "Network.requestWillBeSent", "params": { "requestId": "1", "wallTime": 1700000000.0, "timestamp": 10.0,
+                "request": { "method": "GET", "url": "http://dice.com/apply", "headers": {} } } }),
+            json!({ "method": "Network.requestWillBeSent", "params": { "requestId": "1", "wallTime": 1700000000.2, "timestamp": 10.2,
+                "request": { "method": "GET", "url": "https://www.dice.com/apply", "headers": {} },
+                "redirectResponse": { "status": 301, "statusText": "Moved", "headers": { "location": "https://www.dice.com/apply" } } } }),
+            json!({ "method": "Network.responseReceived", "params": { "requestId": "1",
+                "response": { "status": 200, "statusText": "OK", "mimeType": "text/html", "protocol": "h2", "headers": {} } } }),
+            json!({ "method": "Network.loadingFinished", "params": { "requestId": "1", "timestamp": 10.7, "encodedDataLength": 5120.0 } }),
+            json!({ "method": "Network.requestWillBeSent", "params": { "requestId": "2", "wallTime": 1700000000.3, "timestamp": 10.3,
+                "request": { "method": "POST", "url": "https://www.dice.com/api/apply", "headers": { "content-type": "application/json" }, "postData": "{}" } } }),
+            json!({ "method": "Network.loadingFailed", "params": { "requestId": "2", "timestamp": 10.4, "errorText": "net::ERR_FAILED" } }),
+        ];
+        let har = har_from_events(&events, "Apply");
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["response"]["status"], 301);
+        assert_eq!(entries[0]["response"]["redirectURL"], "https://www.dice.com/apply");
+        assert_eq!(entries[1]["response"]["status"], 200);
+        assert_eq!(entries[1]["response"]["bodySize"], 5120.0);
+        assert_eq!(entries[2]["request"]["method"], "POST");
+        assert_eq!(entries[2]["request"]["postData"]["text"], "{}");
+        assert_eq!(entries[2]["response"]["statusText"], "net::ERR_FAILED");
+    }
+}
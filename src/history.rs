@@ -0,0 +1,691 @@
+use std::fs;
+use std::io::{self, Result};
+use std::path::Path;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OpenFlags, Row};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::discovery::JobSource;
+use crate::schema::{self, Migration};
+use crate::{canonical_job_url, job_id_from_url, Job};
+
+// Scraped jobs, apply attempts and applications, in one SQLite database
+pub const HISTORY_FILE: &str = "dice_blast.db";
+// Where applications and seen postings were kept before the database;
+// imported on first open
+pub const LEGACY_HISTORY_FILE: &str = "applications.json";
+pub const LEGACY_ARCHIVE_FILE: &str = "postings.json";
+
+// The API server, the native messaging host and a run can all have the
+// database open at once; a writer waits this long for another to finish
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// A queued job that fails this many times since it was queued is dropped
+// from the queue instead of being retried on every `apply --from-queue`
+pub const MAX_QUEUE_FAILURES: usize = 3;
+
+// Bumped (with an upgrade step in `open`) whenever the tables change.
+// v2: the queue table, which CREATE IF NOT EXISTS adds to a v1 database
+const SCHEMA_VERSION: u32 = 2;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS applications (
+    id INTEGER PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    job_title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    applied_at TEXT NOT NULL,
+    resume_variant TEXT,
+    applicants INTEGER,
+    source TEXT NOT NULL,
+    category TEXT,
+    summary TEXT,
+    status TEXT NOT NULL,
+    description_hash TEXT,
+    company TEXT,
+    snapshot TEXT
+);
+CREATE INDEX IF NOT EXISTS applications_job_id ON applications (job_id);
+CREATE TABLE IF NOT EXISTS jobs (
+    job_id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    company TEXT,
+    location TEXT,
+    salary TEXT,
+    posted_age TEXT,
+    applicants INTEGER,
+    source TEXT NOT NULL,
+    query TEXT,
+    first_seen TEXT NOT NULL,
+    last_seen TEXT NOT NULL,
+    times_seen INTEGER NOT NULL DEFAULT 1
+);
+CREATE TABLE IF NOT EXISTS attempts (
+    id INTEGER PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS attempts_job_id ON attempts (job_id);
+CREATE TABLE IF NOT EXISTS queue (
+    job_id TEXT PRIMARY KEY,
+    job TEXT NOT NULL,
+    queued_at TEXT NOT NULL
+);
+";
+
+const INSERT_APPLICATION: &str = "INSERT INTO applications
+    (job_id, job_title, url, applied_at, resume_variant, applicants, source, category, summary, status, description_hash, company, snapshot)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)";
+
+// A job seen again keeps its first_seen; card details are refreshed where
+// the new card has them
+const UPSERT_JOB: &str = "INSERT INTO jobs
+    (job_id, title, url, company, location, salary, posted_age, applicants, source, query, first_seen, last_seen)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11)
+    ON CONFLICT (job_id) DO UPDATE SET
+        title = excluded.title,
+        url = excluded.url,
+        company = COALESCE(excluded.company, company),
+        location = COALESCE(excluded.location, location),
+        salary = COALESCE(excluded.salary, salary),
+        posted_age = COALESCE(excluded.posted_age, posted_age),
+        applicants = COALESCE(excluded.applicants, applicants),
+        query = COALESCE(excluded.query, query),
+        last_seen = excluded.last_seen,
+        times_seen = times_seen + 1";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationStatus {
+    #[default]
+    Applied,
+    Responded,
+    Interviewing,
+    Rejected,
+}
+
+impl ApplicationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApplicationStatus::Applied => "applied",
+            ApplicationStatus::Responded => "responded",
+            ApplicationStatus::Interviewing => "interviewing",
+            ApplicationStatus::Rejected => "rejected",
+        }
+    }
+
+    // Anything past "applied" means the employer got back to us
+    pub fn is_response(&self) -> bool {
+        *self != ApplicationStatus::Applied
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApplicationRecord {
+    pub job_id: String,
+    pub job_title: String,
+    pub url: String,
+    pub applied_at: DateTime<Utc>,
+    #[serde(default)]
+    pub resume_variant: Option<String>,
+    // Applicant count shown on the search card at apply time
+    #[serde(default)]
+    pub applicants: Option<u32>,
+    #[serde(default)]
+    pub source: JobSource,
+    #[serde(default)]
+    pub category: Option<String>,
+    // What the job was about, in a few sentences
+    #[serde(default)]
+    pub summary: Option<String>,
+    // Updated by hand (or by later tooling) when the employer responds
+    #[serde(default)]
+    pub status: ApplicationStatus,
+    // SHA-256 of the normalized job description, for the duplicate content guard
+    #[serde(default)]
+    pub description_hash: Option<String>,
+    // As shown on the search card, for the per-company cap
+    #[serde(default)]
+    pub company: Option<String>,
+    // PDF (MHTML when headful) of the job page at apply time, when snapshot_pdf is on
+    #[serde(default)]
+    pub snapshot: Option<String>,
+}
+
+// v1: bare array of records
+// v2: versioned envelope; status and source written out explicitly
+// v3: URLs canonicalized
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+fn migrate_v1_to_v2(document: Value) -> Value {
+    let mut document = schema::wrap_legacy_array(document);
+    if let Some(records) = document["records"].as_array_mut() {
+        for record in records {
+            if record.get("status").is_none() {
+                record["status"] = json!("applied");
+            }
+            if record.get("source").is_none() {
+                record["source"] = json!("search");
+            }
+        }
+    }
+    document
+}
+
+fn migrate_v2_to_v3(mut document: Value) -> Value {
+    if let Some(records) = document["records"].as_array_mut() {
+        for record in records {
+            if let Some(url) = record["url"].as_str() {
+                record["url"] = json!(canonical_job_url(url));
+            }
+        }
+    }
+    document
+}
+
+// postings.json, the repost archive from before the database
+#[derive(Deserialize)]
+struct LegacyPosting {
+    job_id: String,
+    title: String,
+    company: Option<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+// A row of the jobs table: a job some search turned up
+pub struct TrackedJob {
+    pub job_id: String,
+    pub title: String,
+    pub url: String,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub salary: Option<String>,
+    pub first_seen: DateTime<Utc>,
+}
+
+fn db_error(e: rusqlite::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+// Enums are stored as their serde names
+fn enum_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).ok().and_then(|value| value.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+fn enum_value<T: DeserializeOwned + Default>(text: String) -> T {
+    serde_json::from_value(Value::String(text)).unwrap_or_default()
+}
+
+fn insert_application(db: &Connection, record: &ApplicationRecord) -> rusqlite::Result<usize> {
+    db.execute(
+        INSERT_APPLICATION,
+        params![
+            record.job_id,
+            record.job_title,
+            record.url,
+            record.applied_at,
+            record.resume_variant,
+            record.applicants,
+            enum_text(&record.source),
+            record.category,
+            record.summary,
+            enum_text(&record.status),
+            record.description_hash,
+            record.company,
+            record.snapshot,
+        ],
+    )
+}
+
+fn application_from_row(row: &Row) -> rusqlite::Result<ApplicationRecord> {
+    Ok(ApplicationRecord {
+        job_id: row.get(0)?,
+        job_title: row.get(1)?,
+        url: row.get(2)?,
+        applied_at: row.get(3)?,
+        resume_variant: row.get(4)?,
+        applicants: row.get(5)?,
+        source: enum_value(row.get(6)?),
+        category: row.get(7)?,
+        summary: row.get(8)?,
+        status: enum_value(row.get(9)?),
+        description_hash: row.get(10)?,
+        company: row.get(11)?,
+        snapshot: row.get(12)?,
+    })
+}
+
+fn open(path: &Path) -> Result<Connection> {
+    let db = Connection::open(path).map_err(db_error)?;
+    db.busy_timeout(BUSY_TIMEOUT).map_err(db_error)?;
+    let version: u32 = db.query_row("PRAGMA user_version", [], |row| row.get(0)).map_err(db_error)?;
+    if version > SCHEMA_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is schema version {}, but this build only understands up to {}. Upgrade dice_blast.", path.display(), version, SCHEMA_VERSION),
+        ));
+    }
+    db.execute_batch(SCHEMA).map_err(db_error)?;
+    db.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION)).map_err(db_error)?;
+    Ok(db)
+}
+
+// A consistent copy of the database at `path`, made by SQLite itself so a
+// run writing to it at the same time can't leave the copy half-written
+pub fn snapshot(path: &Path) -> Result<Vec<u8>> {
+    let db = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(db_error)?;
+    db.busy_timeout(BUSY_TIMEOUT).map_err(db_error)?;
+    let copy = std::env::temp_dir().join(format!("dice_blast-snapshot-{}.db", uuid::Uuid::new_v4()));
+    db.execute("VACUUM INTO ?1", params![copy.to_string_lossy()]).map_err(db_error)?;
+    let contents = fs::read(&copy);
+    fs::remove_file(&copy)?;
+    contents
+}
+
+// Moves applications.json into a fresh database. The file is renamed
+// rather than deleted, so nothing is lost if the import goes wrong.
+fn import_legacy(db: &mut Connection, legacy: &Path) -> Result<Vec<ApplicationRecord>> {
+    let Some(records) = schema::read_versioned::<Vec<ApplicationRecord>>(legacy, MIGRATIONS)? else {
+        return Ok(Vec::new());
+    };
+    let tx = db.transaction().map_err(db_error)?;
+    for record in &records {
+        insert_application(&tx, record).map_err(db_error)?;
+    }
+    tx.commit().map_err(db_error)?;
+    let imported = legacy.with_extension("json.imported");
+    fs::rename(legacy, &imported)?;
+    println!("Imported {} applications from {} (kept as {})", records.len(), legacy.display(), imported.display());
+    Ok(records)
+}
+
+// Moves postings.json into the jobs table; a job already there keeps the
+// earlier of the two first_seen dates
+fn import_legacy_postings(db: &mut Connection, legacy: &Path) -> Result<()> {
+    let Some(postings) = schema::read_versioned::<Vec<LegacyPosting>>(legacy, &[])? else {
+        return Ok(());
+    };
+    let tx = db.transaction().map_err(db_error)?;
+    for posting in &postings {
+        tx.execute(
+            "INSERT INTO jobs (job_id, title, url, company, source, first_seen, last_seen) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ON CONFLICT (job_id) DO UPDATE SET first_seen = MIN(first_seen, excluded.first_seen)",
+            params![
+                posting.job_id,
+                posting.title,
+                canonical_job_url(&posting.job_id),
+                posting.company,
+                enum_text(&JobSource::Search),
+                posting.first_seen,
+                posting.last_seen,
+            ],
+        )
+        .map_err(db_error)?;
+    }
+    tx.commit().map_err(db_error)?;
+    let imported = legacy.with_extension("json.imported");
+    fs::rename(legacy, &imported)?;
+    println!("Imported {} postings from {} (kept as {})", postings.len(), legacy.display(), imported.display());
+    Ok(())
+}
+
+// Every application made, loaded into memory for dedup, quotas and reports,
+// backed by the tracking database. Each change is written as it's made.
+pub struct History {
+    db: Connection,
+    pub records: Vec<ApplicationRecord>,
+    // Jobs recorded during one run share this as their last_seen, so the
+    // latest scrape can be read back as a set
+    run_started: DateTime<Utc>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History> {
+        let mut db = open(path)?;
+        let mut records = db
+            .prepare("SELECT job_id, job_title, url, applied_at, resume_variant, applicants, source, category, summary, status, description_hash, company, snapshot FROM applications ORDER BY id")
+            .and_then(|mut statement| statement.query_map([], application_from_row)?.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(db_error)?;
+        if records.is_empty() {
+            records = import_legacy(&mut db, &path.with_file_name(LEGACY_HISTORY_FILE))?;
+        }
+        import_legacy_postings(&mut db, &path.with_file_name(LEGACY_ARCHIVE_FILE))?;
+        Ok(History { db, records, run_started: Utc::now() })
+    }
+
+    // The daemon keeps one History across runs
+    pub fn start_run(&mut self) {
+        self.run_started = Utc::now();
+    }
+
+    pub fn applied_to(&self, job_id: &str) -> bool {
+        self.records.iter().any(|record| record.job_id == job_id)
+    }
+
+    pub fn record(&mut self, record: ApplicationRecord) -> Result<()> {
+        insert_application(&self.db, &record).map_err(db_error)?;
+        self.records.push(record);
+        Ok(())
+    }
+
+    // Where the latest application to a job stands, as the employer
+    // answers. Returns false when the job was never applied to.
+    pub fn set_status(&mut self, job_id: &str, status: ApplicationStatus) -> Result<bool> {
+        let Some(record) = self.records.iter_mut().rev().find(|record| record.job_id == job_id) else {
+            return Ok(false);
+        };
+        self.db
+            .execute(
+                "UPDATE applications SET status = ?2 WHERE id = (SELECT MAX(id) FROM applications WHERE job_id = ?1)",
+                params![job_id, enum_text(&status)],
+            )
+            .map_err(db_error)?;
+        record.status = status;
+        Ok(true)
+    }
+
+    // Every job a search turned up, whether or not it gets applied to
+    pub fn record_jobs(&mut self, jobs: &[Job]) -> Result<()> {
+        let seen_at = self.run_started;
+        let tx = self.db.transaction().map_err(db_error)?;
+        for job in jobs {
+            tx.execute(
+                UPSERT_JOB,
+                params![
+                    job_id_from_url(&job.url),
+                    job.job_title,
+                    canonical_job_url(&job.url),
+                    job.company,
+                    job.location,
+                    job.salary,
+                    job.posted_age,
+                    job.applicants,
+                    enum_text(&job.source),
+                    job.query,
+                    seen_at,
+                ],
+            )
+            .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)
+    }
+
+    // Oldest first
+    pub fn tracked_jobs(&self) -> Result<Vec<TrackedJob>> {
+        self.db
+            .prepare("SELECT job_id, title, url, company, location, salary, first_seen FROM jobs ORDER BY first_seen, job_id")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok(TrackedJob {
+                            job_id: row.get(0)?,
+                            title: row.get(1)?,
+                            url: row.get(2)?,
+                            company: row.get(3)?,
+                            location: row.get(4)?,
+                            salary: row.get(5)?,
+                            first_seen: row.get(6)?,
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(db_error)
+    }
+
+    // The jobs the most recent run turned up, for audit and status
+    pub fn latest_jobs(&self) -> Result<Vec<Job>> {
+        self.db
+            .prepare(
+                "SELECT title, url, company, location, salary, posted_age, applicants, source, query FROM jobs
+                    WHERE last_seen = (SELECT MAX(last_seen) FROM jobs) ORDER BY first_seen, job_id",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| {
+                        Ok(Job {
+                            page_number: 0,
+                            job_title: row.get(0)?,
+                            url: row.get(1)?,
+                            company: row.get(2)?,
+                            location: row.get(3)?,
+                            salary: row.get(4)?,
+                            posted_age: row.get(5)?,
+                            applicants: row.get(6)?,
+                            source: enum_value(row.get(7)?),
+                            query: row.get(8)?,
+                        })
+                    })?
+                    .collect()
+            })
+            .map_err(db_error)
+    }
+
+    // Adds jobs to the queue `apply --from-queue` works through, skipping
+    // any already waiting; returns how many were new
+    pub fn enqueue(&mut self, jobs: &[Job]) -> Result<usize> {
+        let queued_at = Utc::now();
+        let tx = self.db.transaction().map_err(db_error)?;
+        let mut added = 0;
+        for job in jobs {
+            added += tx
+                .execute(
+                    "INSERT OR IGNORE INTO queue (job_id, job, queued_at) VALUES (?1, ?2, ?3)",
+                    params![job_id_from_url(&job.url), serde_json::to_string(job)?, queued_at],
+                )
+                .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)?;
+        Ok(added)
+    }
+
+    // Oldest first
+    pub fn queued_jobs(&self) -> Result<Vec<Job>> {
+        let jobs: Vec<String> = self
+            .db
+            .prepare("SELECT job FROM queue ORDER BY queued_at, rowid")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .map_err(db_error)?;
+        jobs.iter().map(|job| Ok(serde_json::from_str(job)?)).collect()
+    }
+
+    pub fn queue_len(&self) -> Result<usize> {
+        self.db.query_row("SELECT COUNT(*) FROM queue", [], |row| row.get(0)).map_err(db_error)
+    }
+
+    // Failed attempts at a queued job since it was queued; 0 when it isn't
+    pub fn queued_failures(&self, job_id: &str) -> Result<usize> {
+        self.db
+            .query_row(
+                "SELECT COUNT(*) FROM attempts JOIN queue ON queue.job_id = attempts.job_id
+                 WHERE attempts.job_id = ?1 AND attempts.outcome = 'failed' AND attempts.started_at >= queue.queued_at",
+                params![job_id],
+                |row| row.get(0),
+            )
+            .map_err(db_error)
+    }
+
+    pub fn dequeue(&mut self, job_id: &str) -> Result<()> {
+        self.db.execute("DELETE FROM queue WHERE job_id = ?1", params![job_id]).map_err(db_error)?;
+        Ok(())
+    }
+
+    // Jobs turned down on their job page since `since`, with the reasons
+    pub fn skips_since(&self, since: DateTime<Utc>) -> Result<Vec<(String, String)>> {
+        self.db
+            .prepare("SELECT job_id, COALESCE(error, 'skipped') FROM attempts WHERE outcome = 'skipped' AND started_at >= ?1 ORDER BY id")
+            .and_then(|mut statement| statement.query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))?.collect())
+            .map_err(db_error)
+    }
+
+    // One try at applying to a job: "applied", "skipped" or "failed", with
+    // the error for failures and the reason for skips
+    pub fn record_attempt(&mut self, job: &Job, started_at: DateTime<Utc>, outcome: &str, error: Option<String>) -> Result<()> {
+        self.db
+            .execute(
+                "INSERT INTO attempts (job_id, started_at, finished_at, outcome, error) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![job_id_from_url(&job.url), started_at, Utc::now(), outcome, error],
+            )
+            .map_err(db_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use super::*;
+    use crate::discovery::discovered_job;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dice_blast-history-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn table_names(db: &Connection) -> Vec<String> {
+        db.prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+            .and_then(|mut statement| statement.query_map([], |row| row.get(0))?.collect())
+            .unwrap()
+    }
+
+    #[test]
+    fn creates_the_schema() {
+        let dir = temp_dir();
+        let history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        assert_eq!(table_names(&history.db), vec!["applications", "attempts", "jobs", "queue"]);
+        let version: u32 = history.db.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn imports_legacy_files_once() {
+        let dir = temp_dir();
+        let applications = r#"[{"job_id": "a1", "job_title": "Rust Engineer", "url": "https://dice.com/job-detail/a1?utm=x", "applied_at": "2025-03-01T12:00:00Z"}]"#;
+        let postings = r#"{"version": 1, "records": [{"job_id": "p1", "title": "Go Developer", "company": "Initech", "first_seen": "2025-02-01T12:00:00Z", "last_seen": "2025-02-03T12:00:00Z"}]}"#;
+        fs::write(dir.join(LEGACY_HISTORY_FILE), applications).unwrap();
+        fs::write(dir.join(LEGACY_ARCHIVE_FILE), postings).unwrap();
+
+        let history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].url, canonical_job_url("a1"));
+        assert_eq!(history.records[0].status, ApplicationStatus::Applied);
+        let tracked = history.tracked_jobs().unwrap();
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked[0].company.as_deref(), Some("Initech"));
+        assert!(dir.join("applications.json.imported").exists());
+        assert!(dir.join("postings.json.imported").exists());
+        drop(history);
+
+        let reopened = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        assert_eq!(reopened.records.len(), 1);
+        assert_eq!(reopened.tracked_jobs().unwrap().len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn upgrades_a_v1_database() {
+        let dir = temp_dir();
+        let path = dir.join(HISTORY_FILE);
+        let v1 = Connection::open(&path).unwrap();
+        let v1_schema = &SCHEMA[..SCHEMA.find("CREATE TABLE IF NOT EXISTS queue").unwrap()];
+        v1.execute_batch(v1_schema).unwrap();
+        v1.execute_batch("PRAGMA user_version = 1").unwrap();
+        drop(v1);
+
+        let mut history = History::load(&path).unwrap();
+        let version: u32 = history.db.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        let job = discovered_job("q1", "Rust Engineer".to_string(), JobSource::Search);
+        assert_eq!(history.enqueue(&[job]).unwrap(), 1);
+        drop(history);
+
+        Connection::open(&path).unwrap().execute_batch("PRAGMA user_version = 99").unwrap();
+        let newer = History::load(&path).err().unwrap();
+        assert_eq!(newer.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn latest_jobs_are_the_last_runs() {
+        let dir = temp_dir();
+        let mut history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        let job = |id: &str| discovered_job(id, format!("Job {}", id), JobSource::Search);
+        history.record_jobs(&[job("j1"), job("j2")]).unwrap();
+        history.start_run();
+        history.record_jobs(&[job("j2")]).unwrap();
+        history.record_jobs(&[job("j3")]).unwrap();
+        let latest: Vec<String> = history.latest_jobs().unwrap().into_iter().map(|job| job.job_title).collect();
+        assert_eq!(latest, vec!["Job j2", "Job j3"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn queue_drops_duplicates_and_counts_failures() {
+        let dir = temp_dir();
+        let mut history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        let job = |id: &str| discovered_job(id, format!("Job {}", id), JobSource::Search);
+        assert_eq!(history.enqueue(&[job("q1"), job("q2")]).unwrap(), 2);
+        assert_eq!(history.enqueue(&[job("q2"), job("q3")]).unwrap(), 1);
+        assert_eq!(history.queue_len().unwrap(), 3);
+        let queued: Vec<String> = history.queued_jobs().unwrap().into_iter().map(|job| job.job_title).collect();
+        assert_eq!(queued, vec!["Job q1", "Job q2", "Job q3"]);
+
+        let started_at = Utc::now();
+        history.record_attempt(&job("q1"), started_at, "failed", Some("timeout".to_string())).unwrap();
+        history.record_attempt(&job("q1"), started_at, "failed", Some("timeout".to_string())).unwrap();
+        history.record_attempt(&job("q1"), started_at, "skipped", None).unwrap();
+        history.record_attempt(&job("q4"), started_at, "failed", None).unwrap();
+        assert_eq!(history.queued_failures("q1").unwrap(), 2);
+        assert_eq!(history.queued_failures("q2").unwrap(), 0);
+        assert_eq!(history.queued_failures("q4").unwrap(), 0);
+
+        // Failures before a job was queued again don't count
+        history.dequeue("q1").unwrap();
+        assert_eq!(history.queued_failures("q1").unwrap(), 0);
+        history.enqueue(&[job("q1")]).unwrap();
+        assert_eq!(history.queued_failures("q1").unwrap(), 0);
+        let queued: Vec<String> = history.queued_jobs().unwrap().into_iter().map(|job| job.job_title).collect();
+        assert_eq!(queued, vec!["Job q2", "Job q3", "Job q1"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reads_jobs_queued_before_applicants_and_source() {
+        let dir = temp_dir();
+        let history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+        let old_job = r#"{"page_number": 2, "job_title": "Rust Engineer", "url": "https://www.dice.com/job-detail/old1", "company": "Acme"}"#;
+        history.db.execute("INSERT INTO queue (job_id, job, queued_at) VALUES ('old1', ?1, ?2)", params![old_job, Utc::now()]).unwrap();
+
+        let queued = history.queued_jobs().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].job_title, "Rust Engineer");
+        assert_eq!(queued[0].applicants, None);
+        assert_eq!(queued[0].source, JobSource::Search);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn snapshots_are_complete_databases() {
+        let dir = temp_dir();
+        let path = dir.join(HISTORY_FILE);
+        assert!(snapshot(&path).is_err());
+        let mut history = History::load(&path).unwrap();
+        let job = discovered_job("s1", "Rust Engineer".to_string(), JobSource::Search);
+        history.enqueue(&[job]).unwrap();
+
+        let copy = dir.join("copy.db");
+        fs::write(&copy, snapshot(&path).unwrap()).unwrap();
+        let copied = History::load(&copy).unwrap();
+        assert_eq!(copied.queue_len().unwrap(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
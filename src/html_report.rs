@@ -0,0 +1,138 @@
+use std::fmt::Write;
+use std::io;
+use std::path::PathBuf;
+use chrono::{DateTime, Duration, Utc};
+use plotters::prelude::*;
+
+use crate::analytics;
+use crate::history::History;
+use crate::run_report::RunReport;
+use crate::state_file;
+
+const TREND_DAYS: i64 = 30;
+const TREND_WEEKS: i64 = 12;
+const CHART_SIZE: (u32, u32) = (640, 260);
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 10px; text-align: left; }
+td.n { text-align: right; }
+.charts svg { margin: 0 1em 1em 0; }";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// A line chart over the last `points.len()` days or weeks, as inline SVG.
+// Points without a value (None) leave a gap.
+fn line_chart(title: &str, points: &[Option<f64>], y_max: f64, label: &dyn Fn(&i32) -> String) -> Result<String, String> {
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE).map_err(|e| e.to_string())?;
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("sans-serif", 16))
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(40)
+            .build_cartesian_2d(0..points.len() as i32 - 1, 0f64..y_max)
+            .map_err(|e| e.to_string())?;
+        chart.configure_mesh().x_labels(6).x_label_formatter(label).draw().map_err(|e| e.to_string())?;
+        // One series per unbroken run of values
+        for run in points.iter().enumerate().collect::<Vec<_>>().split(|(_, value)| value.is_none()) {
+            let line = run.iter().filter_map(|(x, value)| value.map(|y| (*x as i32, y)));
+            chart.draw_series(LineSeries::new(line, &BLUE)).map_err(|e| e.to_string())?;
+        }
+        root.present().map_err(|e| e.to_string())?;
+    }
+    Ok(svg)
+}
+
+fn chart_or_note(title: &str, chart: Result<String, String>) -> String {
+    chart.unwrap_or_else(|e| format!("<p>Could not draw {}: {}</p>", escape(title), escape(&e)))
+}
+
+// Applications per day and response rate per week, up to today
+fn trend_charts(history: &History) -> String {
+    let end: DateTime<Utc> = (Utc::now().date_naive() + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+
+    let daily: Vec<Option<f64>> = analytics::daily_applications(&history.records, end, TREND_DAYS).into_iter().map(|count| Some(count as f64)).collect();
+    let most = daily.iter().flatten().fold(0f64, |most, count| most.max(*count));
+    let day = |x: &i32| (end - Duration::days(TREND_DAYS - *x as i64)).format("%m-%d").to_string();
+    let daily_title = format!("Applications per day, last {} days", TREND_DAYS);
+    let daily_chart = chart_or_note(&daily_title, line_chart(&daily_title, &daily, (most + 1.0).ceil(), &day));
+
+    let weekly: Vec<Option<f64>> = analytics::weekly_response_rates(&history.records, end, TREND_WEEKS).into_iter().map(|rate| rate.map(|rate| rate * 100.0)).collect();
+    let week = |x: &i32| (end - Duration::weeks(TREND_WEEKS - *x as i64)).format("%m-%d").to_string();
+    let weekly_title = format!("Response rate % by week applied, last {} weeks", TREND_WEEKS);
+    let weekly_chart = chart_or_note(&weekly_title, line_chart(&weekly_title, &weekly, 100.0, &week));
+
+    format!("<div class=\"charts\">{}{}</div>", daily_chart, weekly_chart)
+}
+
+fn render(report: &RunReport, history: &History) -> String {
+    let applied = report.jobs.values().filter(|job| job.outcome == "applied").count();
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>dice_blast run {}</title><style>{}</style></head><body>\n",
+        report.started_at.format("%Y-%m-%d %H:%M"),
+        STYLE
+    );
+    let _ = write!(
+        html,
+        "<h1>Run of {}</h1>\n<p>Query <b>{}</b>: {} jobs found, {} applied.</p>\n",
+        report.started_at.format("%Y-%m-%d %H:%M UTC"),
+        escape(&report.query),
+        report.jobs.len(),
+        applied
+    );
+
+    html.push_str("<h2>Trends</h2>\n");
+    html.push_str(&trend_charts(history));
+
+    if !report.queries.is_empty() {
+        html.push_str("<h2>Queries</h2>\n<table><tr><th>Query</th><th>Found</th><th>Unique</th><th>Applied</th><th>Skipped</th></tr>\n");
+        for (query, stats) in &report.queries {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td></tr>",
+                escape(query),
+                stats.found,
+                stats.unique,
+                stats.applied,
+                stats.skipped
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !report.stages.is_empty() {
+        html.push_str("<h2>Stages</h2>\n<table><tr><th>Stage</th><th>Seconds</th></tr>\n");
+        for timing in &report.stages {
+            let _ = writeln!(html, "<tr><td>{}</td><td class=\"n\">{:.1}</td></tr>", escape(&timing.stage), timing.secs);
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Jobs</h2>\n<table><tr><th>Job</th><th>Outcome</th><th>Summary</th></tr>\n");
+    for (id, job) in &report.jobs {
+        let _ = writeln!(
+            html,
+            "<tr><td title=\"{}\">{}</td><td>{}</td><td>{}</td></tr>",
+            escape(id),
+            escape(&job.job_title),
+            escape(&job.outcome),
+            escape(job.summary.as_deref().unwrap_or(""))
+        );
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
+
+// Written next to the JSON report, which stays the one diff-runs reads
+pub fn save(report: &RunReport, history: &History) -> io::Result<PathBuf> {
+    let path = report.path("html");
+    state_file::write_atomic(&path, render(report, history).as_bytes())?;
+    Ok(path)
+}
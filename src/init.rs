@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde_json::{json, Map, Value};
+
+use crate::consent::prompt;
+use crate::search_filters::{EmployerType, EmploymentType, FilterValue};
+use crate::{state_file, validate};
+use crate::{paths, Config};
+
+fn ask(question: &str, default: &str) -> String {
+    let answer = if default.is_empty() {
+        prompt(&format!("{}: ", question))
+    } else {
+        prompt(&format!("{} [{}]: ", question, default))
+    };
+    if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer
+    }
+}
+
+fn ask_required(question: &str) -> String {
+    loop {
+        let answer = ask(question, "");
+        if !answer.is_empty() {
+            return answer;
+        }
+        println!("This one is required.");
+    }
+}
+
+fn ask_yes_no(question: &str, default: bool) -> bool {
+    loop {
+        let answer = ask(question, if default { "y" } else { "n" }).to_lowercase();
+        match answer.as_str() {
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => println!("Answer y or n."),
+        }
+    }
+}
+
+fn ask_number(question: &str) -> Option<u64> {
+    loop {
+        let answer = ask(question, "");
+        if answer.is_empty() {
+            return None;
+        }
+        match answer.parse() {
+            Ok(value) => return Some(value),
+            Err(_) => println!("'{}' is not a number.", answer),
+        }
+    }
+}
+
+// `|`-separated values, each of which must be one of `choices`
+fn ask_choices(question: &str, choices: &[&str], default: &str) -> String {
+    loop {
+        let answer = ask(&format!("{} ({})", question, choices.join(", ")), default);
+        let unknown: Vec<&str> = answer.split('|').map(str::trim).filter(|value| !choices.contains(value)).collect();
+        if unknown.is_empty() {
+            return answer;
+        }
+        println!("Not recognised: {}. Separate several with |.", unknown.join(", "));
+    }
+}
+
+fn comma_list(answer: &str) -> Vec<String> {
+    answer.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect()
+}
+
+// The config the answers describe, in the same shape config.json uses
+fn build_document() -> Value {
+    println!("Search");
+    let q = ask_required("Search terms");
+    let location = ask("Location", "");
+    let country_code = ask("Country code", "US").to_uppercase();
+    let employment_type = ask_choices("Employment types", &EmploymentType::names(), EmploymentType::Fulltime.as_str());
+    let employer_type = ask_choices("Employer types", &EmployerType::names(), "Direct Hire|Recruiter");
+    let extra_searches = comma_list(&ask("Other search terms to run with the same filters, comma separated", ""));
+
+    let mut document = Map::new();
+    document.insert("q".to_string(), json!(q));
+    document.insert("location".to_string(), json!(location));
+    document.insert("countryCode".to_string(), json!(country_code));
+    document.insert("filters.employmentType".to_string(), json!(employment_type));
+    document.insert("filters.employerType".to_string(), json!(employer_type));
+    document.insert("filters.easyApply".to_string(), json!(true));
+    document.insert("language".to_string(), json!("en"));
+
+    println!();
+    println!("Limits");
+    if let Some(max) = ask_number("Applications per search per run (blank for no cap)") {
+        document.insert("max_applications".to_string(), json!(max));
+    }
+    // Extra searches copy the main one's filters and cap
+    if !extra_searches.is_empty() {
+        let searches: Vec<Value> = extra_searches
+            .iter()
+            .map(|terms| {
+                let mut search = document.clone();
+                search.insert("q".to_string(), json!(terms));
+                Value::Object(search)
+            })
+            .collect();
+        document.insert("searches".to_string(), Value::Array(searches));
+    }
+    if let Some(max) = ask_number("Applications per run across all searches (blank for the consent limit)") {
+        document.insert("max_applications_per_run".to_string(), json!(max));
+    }
+    let mut job_filters = Map::new();
+    if let Some(max) = ask_number("Skip jobs with more applicants than (blank for no limit)") {
+        job_filters.insert("max_applicants".to_string(), json!(max));
+    }
+    if let Some(min) = ask_number("Skip jobs paying less than this a year (blank for no minimum)") {
+        job_filters.insert("min_salary".to_string(), json!(min));
+    }
+    job_filters.insert("skip_third_party".to_string(), json!(ask_yes_no("Skip staffing agencies and other third-party postings?", false)));
+    document.insert("job_filters".to_string(), Value::Object(job_filters));
+
+    println!();
+    println!("Profile");
+    let skills = comma_list(&ask("Your main skills, comma separated", ""));
+    document.insert("profile".to_string(), json!({ "skills": skills }));
+
+    println!();
+    println!("Credentials");
+    println!("Your Dice login is never stored in the config; run `dice_blast login` afterwards to save a session.");
+    if ask_yes_no("Write employer messages with an OpenAI-compatible LLM?", false) {
+        let endpoint = ask("Chat completions endpoint", "https://api.openai.com/v1/chat/completions");
+        let model = ask_required("Model");
+        let api_key_env = ask("Environment variable holding the API key", "OPENAI_API_KEY");
+        document.insert(
+            "message".to_string(),
+            json!({ "enabled": true, "llm": { "endpoint": endpoint, "model": model, "api_key_env": api_key_env } }),
+        );
+    }
+
+    Value::Object(document)
+}
+
+fn render(path: &Path, document: &Value) -> io::Result<String> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("json").to_lowercase();
+    match extension.as_str() {
+        "toml" => toml::to_string_pretty(document).map_err(|e| invalid(e.to_string())),
+        "yaml" | "yml" => serde_yaml::to_string(document).map_err(|e| invalid(e.to_string())),
+        _ => Ok(serde_json::to_string_pretty(document)? + "\n"),
+    }
+}
+
+// `dice_blast init`: asks for the essentials and writes a config file that
+// has already passed validation
+pub fn run_init(requested: Option<&Path>) -> io::Result<()> {
+    let path = requested.map(PathBuf::from).unwrap_or_else(|| paths::config(paths::CONFIG_FILES[0]));
+    if path.exists() && !ask_yes_no(&format!("{} already exists. Overwrite it?", path.display()), false) {
+        println!("Nothing written.");
+        return Ok(());
+    }
+
+    let document = build_document();
+    validate::check(&document)?;
+    serde_json::from_value::<Config>(document.clone())?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    state_file::write_atomic(&path, render(&path, &document)?.as_bytes())?;
+    println!();
+    println!("Wrote {}. Next: `dice_blast login`, then `dice_blast consent`.", path.display());
+    Ok(())
+}
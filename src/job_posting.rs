@@ -0,0 +1,161 @@
+use regex::Regex;
+use serde_json::Value;
+use thirtyfour::prelude::*;
+
+// Dice job pages embed a schema.org JobPosting as JSON-LD. Its fields don't
+// move when the page is restyled, so they're read first and the CSS
+// selectors elsewhere are only the fallback.
+const LD_JSON_PATTERN: &str = r#"(?s)<script[^>]*type="application/ld\+json"[^>]*>(.*?)</script>"#;
+
+const LD_JSON_SCRIPT: &str = r#"
+    return Array.from(document.querySelectorAll('script[type="application/ld+json"]')).map(el => el.textContent);
+"#;
+
+// What the job page's JobPosting says, where it says it
+#[derive(Default, Debug, PartialEq)]
+pub struct PostingDetails {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    // Written so salary::parse reads it, e.g. "USD 120000 - 150000 per year"
+    pub salary: Option<String>,
+    pub date_posted: Option<String>,
+    // Plain text
+    pub description: Option<String>,
+}
+
+pub fn strip_html(html: &str) -> String {
+    let tags = Regex::new(r"(?s)<[^>]*>").unwrap();
+    let text = tags.replace_all(html, " ");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&#39;", "'")
+        .replace("&quot;", "\"");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_job_posting(value: &Value) -> bool {
+    match &value["@type"] {
+        Value::String(kind) => kind == "JobPosting",
+        Value::Array(kinds) => kinds.iter().any(|kind| kind == "JobPosting"),
+        _ => false,
+    }
+}
+
+// A block can be the posting itself, a list of things, or an @graph
+fn find_in(value: Value) -> Option<Value> {
+    match value {
+        Value::Array(values) => values.into_iter().find_map(find_in),
+        value if is_job_posting(&value) => Some(value),
+        mut value => match value["@graph"].take() {
+            Value::Array(values) => values.into_iter().find_map(find_in),
+            _ => None,
+        },
+    }
+}
+
+fn find_posting<'a>(blocks: impl IntoIterator<Item = &'a str>) -> Option<Value> {
+    blocks.into_iter().filter_map(|block| serde_json::from_str::<Value>(block).ok()).find_map(find_in)
+}
+
+// The JobPosting block of a fetched job page
+pub fn from_html(html: &str) -> Option<Value> {
+    let pattern = Regex::new(LD_JSON_PATTERN).unwrap();
+    find_posting(pattern.captures_iter(html).filter_map(|captures| captures.get(1)).map(|block| block.as_str()))
+}
+
+// The JobPosting block of the page open in the browser
+pub async fn from_page(driver: &WebDriver) -> WebDriverResult<Option<Value>> {
+    let blocks: Vec<String> = driver.execute(LD_JSON_SCRIPT, vec![]).await?.convert()?;
+    Ok(find_posting(blocks.iter().map(String::as_str)))
+}
+
+fn text(value: &Value) -> Option<String> {
+    value.as_str().map(str::trim).filter(|text| !text.is_empty()).map(str::to_string)
+}
+
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str()?.replace(',', "").trim().parse().ok())
+}
+
+// baseSalary is a MonetaryAmount whose value is a number or a
+// QuantitativeValue with a range; unitText can sit at either level
+fn salary_text(base_salary: &Value) -> Option<String> {
+    let value = &base_salary["value"];
+    let (min, max) = match value {
+        Value::Object(_) => {
+            let single = number(&value["value"]);
+            let min = number(&value["minValue"]).or(single)?;
+            (min, number(&value["maxValue"]).or(single).unwrap_or(min))
+        }
+        value => {
+            let single = number(value)?;
+            (single, single)
+        }
+    };
+    let unit = text(&value["unitText"]).or_else(|| text(&base_salary["unitText"])).map(|unit| unit.to_lowercase());
+    let currency = text(&base_salary["currency"]).unwrap_or_default();
+    let mut salary = format!("{} {}", currency, min);
+    if max != min {
+        salary = format!("{} - {}", salary, max);
+    }
+    if let Some(unit) = unit {
+        salary = format!("{} per {}", salary, unit);
+    }
+    Some(salary.trim().to_string())
+}
+
+pub fn details(posting: &Value) -> PostingDetails {
+    PostingDetails {
+        title: text(&posting["title"]),
+        company: text(&posting["hiringOrganization"]["name"]).or_else(|| text(&posting["hiringOrganization"])),
+        salary: salary_text(&posting["baseSalary"]),
+        date_posted: text(&posting["datePosted"]),
+        // Usually HTML, often entity-escaped on top
+        description: posting["description"]
+            .as_str()
+            .map(|html| strip_html(&html.replace("&lt;", "<").replace("&gt;", ">")))
+            .filter(|text| !text.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::salary::{self, PayPeriod};
+
+    const PAGE: &str = r#"<html><head>
+        <script type="application/ld+json">{"@context": "https://schema.org", "@type": "BreadcrumbList"}</script>
+        <script type="application/ld+json">{"@context": "https://schema.org", "@graph": [{"@type": "Organization", "name": "Dice"}, {
+            "@type": "JobPosting",
+            "title": "Senior Rust Engineer",
+            "datePosted": "2026-10-12T14:03:00Z",
+            "hiringOrganization": {"@type": "Organization", "name": "Oxide Computer Company"},
+            "baseSalary": {"@type": "MonetaryAmount", "currency": "USD", "value": {"@type": "QuantitativeValue", "minValue": 180000, "maxValue": "210,000", "unitText": "YEAR"}},
+            "description": "&lt;p&gt;Build &lt;b&gt;firmware&lt;/b&gt; in Rust.&lt;/p&gt;"
+        }]}</script>
+        </head><body></body></html>"#;
+
+    #[test]
+    fn reads_a_posting_inside_a_graph() {
+        let posting = from_html(PAGE).unwrap();
+        let details = details(&posting);
+        assert_eq!(details.title.as_deref(), Some("Senior Rust Engineer"));
+        assert_eq!(details.company.as_deref(), Some("Oxide Computer Company"));
+        assert_eq!(details.date_posted.as_deref(), Some("2026-10-12T14:03:00Z"));
+        assert_eq!(details.salary.as_deref(), Some("USD 180000 - 210000 per year"));
+        let pay = salary::parse(details.salary.as_deref().unwrap()).unwrap();
+        assert_eq!((pay.min, pay.max, pay.period), (180000.0, 210000.0, PayPeriod::Annual));
+        // The description is HTML escaped inside the JSON
+        assert_eq!(details.description.as_deref(), Some("Build firmware in Rust."));
+    }
+
+    #[test]
+    fn hourly_single_figure_and_no_posting() {
+        let posting: Value = serde_json::from_str(r#"{"@type": ["JobPosting"], "baseSalary": {"currency": "USD", "value": 65, "unitText": "HOUR"}}"#).unwrap();
+        assert_eq!(details(&posting).salary.as_deref(), Some("USD 65 per hour"));
+        assert_eq!(from_html("<html><body>No structured data</body></html>"), None);
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use futures::stream::{self, Stream};
+use thirtyfour::prelude::*;
+
+use crate::url_builder::DiceUrlBuilder;
+use crate::{get_job_detail_ids, job_id_from_url, session, Config, Job, SearchQuery};
+
+// Where the stream has got to. Only job IDs are kept between pages, to drop
+// jobs an earlier page or search already turned up.
+struct Cursor<'a> {
+    searches: Vec<&'a SearchQuery>,
+    search: usize,
+    page: usize,
+    seen: HashSet<String>,
+    scraped: usize,
+}
+
+// One page of a search: how many results it showed, and those of them no
+// earlier page or search had
+pub struct ResultPage {
+    pub query: String,
+    pub found: usize,
+    pub jobs: Vec<Job>,
+}
+
+// Search results a page at a time: every page of the main search up to
+// max_pages, then each extra search the same way. Nothing is loaded until
+// the stream is polled, so the caller can use the browser in between.
+// A search moves on early when a page adds no new jobs; the stream ends
+// after max_jobs jobs or the first error.
+pub fn result_pages<'a>(driver: &'a WebDriver, config: &'a Config) -> impl Stream<Item = WebDriverResult<ResultPage>> + 'a {
+    let cursor = Cursor {
+        searches: std::iter::once(&config.search).chain(&config.searches).collect(),
+        search: 0,
+        page: 1,
+        seen: HashSet::new(),
+        scraped: 0,
+    };
+    stream::unfold(Some(cursor), move |cursor| async move {
+        let mut cursor = cursor?;
+        loop {
+            let search = *cursor.searches.get(cursor.search)?;
+            if config.max_jobs.is_some_and(|max| cursor.scraped >= max) {
+                println!("Reached max_jobs; not reading any more pages");
+                return None;
+            }
+            let page = cursor.page;
+            let url = DiceUrlBuilder::new(search).page(page).build();
+            let found = match session::navigate(driver, &config.timeouts, url.as_str()).await {
+                Ok(()) => get_job_detail_ids(driver, &config.timeouts, page).await,
+                Err(e) => Err(e),
+            };
+            let found = match found {
+                Ok(found) => found,
+                Err(e) => return Some((Err(e), None)),
+            };
+
+            let found_count = found.len();
+            let mut jobs: Vec<Job> = found
+                .into_iter()
+                .filter(|job| cursor.seen.insert(job_id_from_url(&job.url).to_string()))
+                .collect();
+            for job in &mut jobs {
+                job.query = Some(search.label().to_string());
+            }
+            if let Some(max) = config.max_jobs {
+                jobs.truncate(max - cursor.scraped);
+            }
+            cursor.scraped += jobs.len();
+
+            if jobs.is_empty() {
+                println!("Page {} of '{}' added no new jobs; moving on", page, search.q);
+            }
+            if jobs.is_empty() || page >= config.max_pages {
+                cursor.search += 1;
+                cursor.page = 1;
+            } else {
+                cursor.page += 1;
+            }
+            // A page of nothing but duplicates still counts towards the query's results
+            if found_count > 0 {
+                let page = ResultPage {
+                    query: search.label().to_string(),
+                    found: found_count,
+                    jobs,
+                };
+                return Some((Ok(page), Some(cursor)));
+            }
+        }
+    })
+}
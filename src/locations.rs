@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::search_filters::{FilterList, RadiusUnit, WorkplaceType};
+use crate::{Config, SearchQuery};
+
+// One place a search covers. "Remote" isn't a place Dice can search around,
+// so it becomes the Remote workplace filter instead.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct SearchLocation {
+    pub location: String,
+    pub radius: Option<u32>,
+    pub radius_unit: Option<RadiusUnit>,
+}
+
+// Written either as a bare name or as {location, radius, radius_unit}
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawLocation {
+    Name(String),
+    Full {
+        location: String,
+        #[serde(default)]
+        radius: Option<u32>,
+        #[serde(default)]
+        radius_unit: Option<RadiusUnit>,
+    },
+}
+
+impl<'de> Deserialize<'de> for SearchLocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match RawLocation::deserialize(deserializer)? {
+            RawLocation::Name(location) => SearchLocation { location, radius: None, radius_unit: None },
+            RawLocation::Full { location, radius, radius_unit } => SearchLocation { location, radius, radius_unit },
+        })
+    }
+}
+
+fn at_location(search: &SearchQuery, place: &SearchLocation) -> SearchQuery {
+    let mut search = search.clone();
+    search.locations = Vec::new();
+    if place.location.trim().eq_ignore_ascii_case("remote") {
+        search.location = String::new();
+        search.radius = None;
+        search.radius_unit = None;
+        search.workplace_types = FilterList::only(WorkplaceType::Remote);
+    } else {
+        search.location = place.location.clone();
+        search.radius = place.radius.or(search.radius);
+        search.radius_unit = place.radius_unit.or(search.radius_unit);
+    }
+    search
+}
+
+// One search per location
+fn expand(search: &SearchQuery) -> Vec<SearchQuery> {
+    if search.locations.is_empty() {
+        return vec![search.clone()];
+    }
+    search.locations.iter().map(|place| at_location(search, place)).collect()
+}
+
+// Fans every search with `locations` out into one search per location. The
+// main search takes the first location; the rest run as extra searches, and
+// the usual merge drops jobs found by more than one of them. Each location
+// is its own search from then on, with its own max_applications.
+pub fn fan_out(config: &mut Config) {
+    let mut main = expand(&config.search).into_iter();
+    let mut extra: Vec<SearchQuery> = Vec::new();
+    if let Some(first) = main.next() {
+        config.search = first;
+    }
+    extra.extend(main);
+    for search in &config.searches {
+        extra.extend(expand(search));
+    }
+    if extra.len() != config.searches.len() {
+        println!("Running {} searches across the configured locations", extra.len() + 1);
+    }
+    config.searches = extra;
+    label_searches(config);
+}
+
+// "q @ location", numbered when two searches would still share a label
+fn label_searches(config: &mut Config) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for search in std::iter::once(&mut config.search).chain(&mut config.searches) {
+        let base = if search.location.is_empty() {
+            search.q.clone()
+        } else {
+            format!("{} @ {}", search.q, search.location)
+        };
+        let count = seen.entry(base.clone()).or_insert(0);
+        *count += 1;
+        search.label = if *count == 1 { base } else { format!("{} #{}", base, count) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+    use crate::{test_config, test_search, workplace};
+
+    #[test]
+    fn every_search_gets_its_own_label() {
+        let mut config = test_config(json!({
+            "locations": ["Austin, TX", "Remote", { "location": "Austin, TX", "radius": 50 }],
+            "searches": [test_search(json!({})), test_search(json!({ "q": "golang", "location": "Austin, TX" }))],
+        }));
+        fan_out(&mut config);
+        let labels: Vec<&str> = std::iter::once(&config.search).chain(&config.searches).map(SearchQuery::label).collect();
+        assert_eq!(labels, ["rust @ Austin, TX", "rust", "rust @ Austin, TX #2", "rust #2", "golang @ Austin, TX"]);
+
+        // The Remote search's workplace filter applies to what it found, not the main search's
+        let mut job = discovered_job("j1", "Rust Engineer".to_string(), JobSource::Search);
+        job.query = Some("rust".to_string());
+        assert_eq!(workplace::filter_for(&config, &job).to_string(), "Remote");
+        job.query = Some("rust #2".to_string());
+        assert!(workplace::filter_for(&config, &job).is_empty());
+    }
+}
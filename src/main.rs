@@ -1,25 +1,143 @@
 use io::Error;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use thirtyfour::error::WebDriverError;
 use thirtyfour::error::WebDriverErrorInfo;
 use thirtyfour::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_reader, json, Value};
-use serde_urlencoded;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
+use std::io::BufReader;
 use std::io::Write;
 use std::io::Result;
 use std::io;
 use std::fmt;
 use std::fmt::Display;
-use regex::Regex;
+use std::panic::AssertUnwindSafe;
+use std::ops::RangeInclusive;
+use std::pin::pin;
+use futures::{FutureExt, StreamExt};
 use tokio::time::{Duration};
 use base64::{encode_config};
 use base64::URL_SAFE;
 use thirtyfour::support::sleep;
 
+mod airtable;
+mod alerts;
+mod analytics;
+mod api;
+mod archive;
+mod assist;
+mod audit;
+mod backup;
+mod blacklist;
+mod bookmark;
+mod budget;
+mod category;
+mod cli;
+mod consent;
+mod content_guard;
+mod control;
+mod cron;
+mod daemon;
+mod debug_bundle;
+mod descriptions;
+mod discovery;
+mod env_overrides;
+mod events;
+mod export;
+mod failures;
+mod filter_trace;
+mod filters;
+mod har;
+mod history;
+mod html_report;
+mod init;
+mod job_posting;
+mod job_stream;
+mod locations;
+mod market;
+mod message;
+mod native_host;
+mod paths;
+mod pipeline;
+mod prelude;
+mod queue;
+mod questions;
+mod query_budget;
+mod recovery;
+mod resume;
+mod rng;
+mod run_report;
+mod salary;
+mod saved_searches;
+mod schema;
+mod scrape_only;
+mod search_filters;
+mod search_result;
+mod seniority;
+mod sheets;
+mod session;
+mod skills;
+mod snapshot;
+mod state_file;
+mod strict;
+mod success;
+mod summary;
+mod sync;
+mod tabs;
+mod template;
+mod third_party;
+mod timeouts;
+mod url_builder;
+mod validate;
+mod verify_apply;
+mod visual;
+mod watch;
+mod whitelist;
+mod workplace;
+mod xpath;
+mod zero_results;
+
+use airtable::AirtableConfig;
+use alerts::AlertConfig;
+use api::ApiConfig;
+use archive::{PostingArchive, RepostPolicy};
+use blacklist::Blacklist;
+use budget::{CompanyCap, RunBudget};
+use category::CategoryConfig;
+use clap::Parser;
+use cli::{Cli, Command};
+use control::PauseControl;
+use chrono::Utc;
+use discovery::{DiscoveryConfig, JobSource};
+use filters::JobFilters;
+use history::{ApplicationRecord, ApplicationStatus, History};
+use job_stream::ResultPage;
+use locations::SearchLocation;
+use message::MessageConfig;
+use pipeline::PipelineConfig;
+use prelude::PreludeConfig;
+use queue::{RedisConfig, RedisQueue};
+use resume::ResumeConfig;
+use rng::RunRng;
+use run_report::{RunReport, StageTimings};
+use scrape_only::ScrapeOnlyConfig;
+use search_filters::{EmployerType, EmploymentType, FilterList, PostedDate, RadiusUnit, SortOrder, WorkplaceType};
+use summary::SummaryConfig;
+use sheets::SheetsConfig;
+use skills::SkillTaxonomy;
+use strict::{FailureRate, StrictMode};
+use success::SuccessCriteria;
+use sync::SyncConfig;
+use watch::WatchConfig;
+use timeouts::TimeoutConfig;
+use url_builder::DiceUrlBuilder;
+use visual::VisualDiffConfig;
+use whitelist::Whitelist;
+use zero_results::ZeroResultLog;
+
 #[derive(Serialize, Deserialize)]
 struct Cookie {
     name: String,
@@ -31,34 +149,234 @@ struct Cookie {
     http_only: Option<bool>, // Make this field optional
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 struct SearchQuery {
     q: String,
+    #[serde(default)]
     location: String,
+    // Distance around `location` to search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    radius: Option<u32>,
+    #[serde(default, rename = "radiusUnit", skip_serializing_if = "Option::is_none")]
+    radius_unit: Option<RadiusUnit>,
+    // Several places to run this search for, each with its own radius;
+    // replaces `location` and `radius`
+    #[serde(default, skip_serializing)]
+    locations: Vec<SearchLocation>,
     #[serde(rename = "countryCode")]
     country_code: String,
     #[serde(rename = "filters.employmentType")]
-    filters_employment_type: String,
+    filters_employment_type: FilterList<EmploymentType>,
     #[serde(rename = "filters.employerType")]
-    filters_employer_type: String,
+    filters_employer_type: FilterList<EmployerType>,
     #[serde(rename = "filters.easyApply")]
     filters_easy_apply: bool, 
-    language: String
+    // Only postings from the last 1, 3 or 7 days
+    #[serde(default, rename = "filters.postedDate", alias = "posted_date", skip_serializing_if = "Option::is_none")]
+    posted_date: Option<PostedDate>,
+    // Remote, Hybrid and/or On-Site; every workplace type when empty
+    #[serde(default, rename = "filters.workplaceTypes", alias = "workplace_type", skip_serializing_if = "FilterList::is_empty")]
+    workplace_types: FilterList<WorkplaceType>,
+    language: String,
+    // Cap on applications from this search per run, so a noisy query can't
+    // use up the whole budget. Not part of the search URL.
+    #[serde(default, skip_serializing)]
+    max_applications: Option<usize>,
+    // Results per page; Dice's default when unset. Up to 100, so fewer
+    // pages have to be loaded.
+    #[serde(default, rename = "pageSize", skip_serializing_if = "Option::is_none")]
+    page_size: Option<u32>,
+    // "date" puts the newest postings first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort: Option<SortOrder>,
+    // Names the search in reports, per-search limits and on the jobs it
+    // finds. Unique within the config; set by locations::fan_out.
+    #[serde(skip)]
+    label: String,
+}
+
+impl SearchQuery {
+    // Searches that never went through fan_out (API requests) go by `q`
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            &self.q
+        } else {
+            &self.label
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Profile {
+    #[serde(default)]
+    skills: Vec<String>,
+    // Used by the seniority guard; left unset, every level is fair game
+    #[serde(default)]
+    seniority: Option<seniority::Seniority>,
+    // Answers for the relocation and travel screening questions; questions
+    // left unset here are left for the user
+    #[serde(default)]
+    willing_to_relocate: Option<bool>,
+    #[serde(default)]
+    max_travel_percent: Option<u32>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct Config {
+    #[serde(flatten)]
+    search: SearchQuery,
+    // Stop a run after this many successful applications. The consent limit
+    // and `apply --limit` still apply; the lowest of them wins.
+    #[serde(default)]
+    max_applications_per_run: Option<usize>,
+    #[serde(default)]
+    repost_policy: RepostPolicy,
+    #[serde(default)]
+    categories: CategoryConfig,
+    #[serde(default)]
+    prelude: PreludeConfig,
+    #[serde(default)]
+    summary: SummaryConfig,
+    #[serde(default)]
+    watch: WatchConfig,
+    // `serve` and `daemon` report a search that keeps coming back empty once
+    // per this many minutes
+    #[serde(default = "default_zero_result_quiet_mins")]
+    zero_result_quiet_mins: u64,
+    // Extra searches scraped in parallel tabs alongside the main one
+    #[serde(default)]
+    searches: Vec<SearchQuery>,
+    #[serde(default = "default_max_search_tabs")]
+    max_search_tabs: usize,
+    // Result pages read per search, when applying and in scrape-only mode
+    #[serde(default = "default_max_pages")]
+    max_pages: usize,
+    // Stop scraping once this many jobs are found, however many pages that is
+    #[serde(default)]
+    max_jobs: Option<usize>,
+    // Apply to each page of results as it's scraped instead of collecting
+    // every page first
+    #[serde(default)]
+    low_memory: bool,
+    // Assist mode: `apply` opens this many matching jobs in tabs and leaves
+    // applying to the user
+    #[serde(default)]
+    assist_tabs: Option<usize>,
+    // Bookmark mode: `apply` adds matching jobs to Dice's Saved Jobs instead
+    #[serde(default)]
+    bookmark: bool,
+    // Scrape in a second browser session while this one applies
+    #[serde(default)]
+    pipeline: Option<PipelineConfig>,
+    #[serde(default)]
+    profile: Profile,
+    // Extra spellings of skills, e.g. {"RoR": "Ruby on Rails"}, on top of
+    // the bundled ones like K8s -> Kubernetes
+    #[serde(default)]
+    skill_synonyms: BTreeMap<String, String>,
+    #[serde(default)]
+    message: MessageConfig,
+    #[serde(default)]
+    resume: ResumeConfig,
+    #[serde(default)]
+    job_filters: JobFilters,
+    // Companies and keywords never to apply to
+    #[serde(default)]
+    blacklist: Blacklist,
+    #[serde(default)]
+    company_cap: Option<CompanyCap>,
+    // When set, the only companies and titles to apply to
+    #[serde(default)]
+    whitelist: Option<Whitelist>,
+    #[serde(default)]
+    timeouts: TimeoutConfig,
+    #[serde(default)]
+    visual_diff: VisualDiffConfig,
+    // Save a HAR of the job page's network activity with each failure
+    #[serde(default)]
+    capture_har: bool,
+    // Keep applying past failures, halting only on a high failure rate
+    #[serde(default)]
+    strict: Option<StrictMode>,
+    // Webhook for alerts such as a strict-mode halt
+    #[serde(default)]
+    alerts: Option<AlertConfig>,
+    // Thresholds a run must meet, or it alerts and exits nonzero
+    #[serde(default)]
+    success: Option<SuccessCriteria>,
+    // Keep a PDF of each job page applied to; MHTML when not headless
+    #[serde(default)]
+    snapshot_pdf: bool,
+    #[serde(default)]
+    discovery: DiscoveryConfig,
+    // Fixed RNG seed for reproducible runs; random when unset
+    #[serde(default)]
+    seed: Option<u64>,
+    // Apply in random order (from the seed above) rather than top to bottom
+    #[serde(default)]
+    shuffle_jobs: bool,
+    // -v count from the command line
+    #[serde(skip)]
+    verbosity: u8,
+    // Optional shared queue for running several workers against one budget
+    #[serde(default)]
+    redis: Option<RedisConfig>,
+    #[serde(default)]
+    scrape_only: ScrapeOnlyConfig,
+    // Shared applied-jobs set for running from more than one machine
+    #[serde(default)]
+    sync: Option<SyncConfig>,
+    // Appends each run's applications to a Google Sheet
+    #[serde(default)]
+    sheets: Option<SheetsConfig>,
+    #[serde(default)]
+    airtable: Option<AirtableConfig>,
+    // Required for `dice_blast serve`
+    #[serde(default)]
+    api: Option<ApiConfig>,
+    #[serde(default = "default_webdriver_url")]
+    webdriver_url: String,
+}
 
-#[derive(Debug)]
-enum ConfigError {
-    FileError(Error),
-    ParseError(serde_json::Error),
-    UrlEncoded(serde_urlencoded::ser::Error),
+fn default_max_search_tabs() -> usize {
+    3
 }
 
-#[derive(Debug)]
+fn default_max_pages() -> usize {
+    1
+}
+
+fn default_zero_result_quiet_mins() -> u64 {
+    60
+}
+
+fn default_webdriver_url() -> String {
+    "http://localhost:9415".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Job {
     page_number: usize,
     job_title: String,
     url: String,
+    #[serde(default)]
+    company: Option<String>,
+    #[serde(default)]
+    location: Option<String>,
+    // Compensation text exactly as shown on the card
+    #[serde(default)]
+    salary: Option<String>,
+    #[serde(default)]
+    posted_age: Option<String>,
+    // Jobs saved before these were added have neither
+    #[serde(default)]
+    applicants: Option<u32>,
+    #[serde(default)]
+    source: JobSource,
+    // The label of the search that found this job, for per-search budgets,
+    // workplace filters and the report
+    #[serde(default)]
+    query: Option<String>,
 }
 impl Display for SearchQuery {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -75,61 +393,113 @@ impl Display for SearchQuery {
     }
 }
 
-impl Display for ConfigError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            ConfigError::FileError(ref err) => write!(f, "File error: {}", err),
-            ConfigError::ParseError(ref err) => write!(f, "Parse error: {}", err),
-            ConfigError::UrlEncoded(ref err) => write!(f, "URL encoding error: {}", err),
-        }
+// A config can list all its queries under `searches` instead of putting the
+// first one at the top level; the first entry then becomes the main search
+fn promote_first_search(document: &mut Value) {
+    let Some(object) = document.as_object_mut() else {
+        return;
+    };
+    if object.contains_key("q") {
+        return;
+    }
+    let Some(first) = object.get_mut("searches").and_then(Value::as_array_mut).filter(|s| !s.is_empty()).map(|s| s.remove(0)) else {
+        return;
+    };
+    if let Value::Object(fields) = first {
+        object.extend(fields);
     }
 }
 
-impl std::error::Error for ConfigError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match *self {
-            ConfigError::FileError(ref err) => Some(err),
-            ConfigError::ParseError(ref err) => Some(err),
-            ConfigError::UrlEncoded(ref err) => Some(err),
-        }
+// When --config isn't given, the config dir is searched for each of
+// paths::CONFIG_FILES in order
+fn config_path(requested: Option<&Path>) -> PathBuf {
+    if let Some(path) = requested {
+        return path.to_path_buf();
     }
+    paths::CONFIG_FILES
+        .iter()
+        .map(|name| paths::config(name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| paths::config(paths::CONFIG_FILES[0]))
 }
 
-impl From<Error> for ConfigError {
-    fn from(err: Error) -> ConfigError {
-        ConfigError::FileError(err)
+// Parses JSON, TOML or YAML by extension into one JSON document, so every
+// format goes through the same serde structs
+fn read_config_document(path: &Path) -> Result<Value> {
+    let invalid = |e: String| Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e));
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("json").to_lowercase();
+    match extension.as_str() {
+        "toml" => toml::from_str(&fs::read_to_string(path)?).map_err(|e| invalid(e.to_string())),
+        "yaml" | "yml" => serde_yaml::from_str(&fs::read_to_string(path)?).map_err(|e| invalid(e.to_string())),
+        _ => {
+            let reader = BufReader::new(File::open(path)?);
+            Ok(from_reader(reader)?)
+        }
     }
 }
 
-impl From<serde_json::Error> for ConfigError {
-    fn from(err: serde_json::Error) -> ConfigError {
-        ConfigError::ParseError(err)
-    }
+fn load_config(requested: Option<&Path>) -> Result<Config> {
+    let path = config_path(requested);
+    // Without a file (e.g. in a container) every field comes from the environment
+    let mut document = if requested.is_none() && !path.exists() {
+        println!("No config file found; reading config from DICE_BLAST_* environment variables");
+        Value::Object(serde_json::Map::new())
+    } else {
+        println!("Loading config from {}", path.display());
+        read_config_document(&path)?
+    };
+    promote_first_search(&mut document);
+    env_overrides::apply(&mut document);
+    validate::check(&document)?;
+    let mut config: Config = serde_json::from_value(document)?;
+    locations::fan_out(&mut config);
+    third_party::restrict_employer_type(&mut config);
+    Ok(config)
 }
 
-impl From<serde_urlencoded::ser::Error> for ConfigError {
-    fn from(err: serde_urlencoded::ser::Error) -> ConfigError {
-        ConfigError::UrlEncoded(err)
-    }
+// A search with just the fields every search needs, plus `extra`
+#[cfg(test)]
+fn test_search(extra: Value) -> Value {
+    let mut search = json!({
+        "q": "rust",
+        "countryCode": "US",
+        "language": "en",
+        "filters.employmentType": "FULLTIME",
+        "filters.employerType": "Direct Hire",
+        "filters.easyApply": true,
+    });
+    search.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+    search
 }
 
+// A config whose main search is test_search(extra); other config fields
+// can go in `extra` too
+#[cfg(test)]
+fn test_config(extra: Value) -> Config {
+    serde_json::from_value(test_search(extra)).unwrap()
+}
 
-fn build_url_from_config() -> Result<String> {
+fn build_url_from_config(config: &Config) -> String {
     println!("Building search url from config file...");
-    let file = File::open("./config.json")?;
-    let reader = BufReader::new(file);
-    let search_query: SearchQuery = from_reader(reader)?;
-    let encoded_query = serde_urlencoded::to_string(&search_query).map_err(ConfigError::UrlEncoded);
-    let url = format!("https://dice.com/jobs?{:?}", encoded_query);
+    build_search_url(&config.search)
+}
 
+fn build_search_url(search: &SearchQuery) -> String {
+    let url = DiceUrlBuilder::new(search).build().to_string();
     println!("Formatted URL: {}", url);
-
-    Ok(url)
+    url
 }
 
+// Cookies can only be set once we're on the Dice domain, so a fresh
+// session has to load a Dice page before the saved ones are restored.
+pub async fn restore_cookies(driver: &WebDriver) -> WebDriverResult<()> {
+    driver.get("https://www.dice.com").await?;
+    sleep(Duration::from_secs(3)).await;
+    load_cookies(driver).await
+}
 
 async fn load_cookies(driver: &WebDriver) -> WebDriverResult<()> {
-    let file = File::open("cookies.json")?;
+    let file = File::open(paths::state(paths::COOKIES_FILE))?;
     let reader = BufReader::new(file);
     let cookies: Vec<Cookie> = from_reader(reader)?;
 
@@ -153,12 +523,45 @@ async fn load_cookies(driver: &WebDriver) -> WebDriverResult<()> {
     Ok(())
 }
 
+// Merges the browser's cookies into cookies.json: cookies in the current
+// session replace stored ones with the same name, domain and path, stored
+// cookies the session doesn't have are kept, and expired ones are dropped.
 async fn save_cookies(driver: &WebDriver) -> WebDriverResult<()> {
-    let cookies = driver.get_all_cookies().await?;
-    let file = File::create("cookies.json")?;
-    let writer = BufWriter::new(file);
-    serde_json::to_writer(writer, &cookies)?;
-    Ok(())
+    let path = &paths::state(paths::COOKIES_FILE);
+    let lock = state_file::lock(path)?;
+    let mut stored: Vec<Cookie> = match File::open(path) {
+        Ok(file) => from_reader(BufReader::new(file)).unwrap_or_else(|e| {
+            println!("Ignoring unreadable {}: {}", path.display(), e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+
+    for fresh in driver.get_all_cookies().await? {
+        let cookie = Cookie {
+            name: fresh.name,
+            value: fresh.value,
+            domain: fresh.domain,
+            path: fresh.path,
+            expiry: fresh.expiry.map(|e| e.max(0) as u64),
+            secure: fresh.secure.unwrap_or(false),
+            http_only: None,
+        };
+        match stored.iter_mut().find(|c| c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path) {
+            Some(existing) => {
+                let http_only = existing.http_only;
+                *existing = Cookie { http_only, ..cookie };
+            }
+            None => stored.push(cookie),
+        }
+    }
+
+    let now = Utc::now().timestamp().max(0) as u64;
+    stored.retain(|cookie| cookie.expiry.is_none_or(|expiry| expiry > now));
+
+    lock.write_json(&stored)?;
+    drop(lock);
+    session::record_fingerprint(driver).await
 }
 
 async fn login(driver: &WebDriver) -> WebDriverResult<()> {
@@ -177,7 +580,7 @@ async fn login(driver: &WebDriver) -> WebDriverResult<()> {
 }
 
 fn cookie_exists() -> Result<bool> {
-    let cookie_file = Path::new("./cookies.json");
+    let cookie_file = &paths::state(paths::COOKIES_FILE);
     match File::open(cookie_file) {
         Ok(_) => {
             println!("There is an existing cookie file. Continuing with program execution.");
@@ -193,57 +596,154 @@ fn cookie_exists() -> Result<bool> {
 //Job Detail Pages look like https://www.dice.com/job-detail/f0767d15-68a2-4c23-95c6-5685dedf2d2d
 // Need to grab the IDs for each job and append them on a future page
 
-async fn wait_for_element(driver: &WebDriver, selector: By, timeout: Duration) -> WebDriverResult<()> {
+// Polling starts at 100ms and backs off by half again each round up to 2s,
+// so fast pages respond quickly without hammering WebDriver on slow ones.
+fn next_poll_interval(interval: Duration) -> Duration {
+    (interval * 3 / 2).min(Duration::from_secs(2))
+}
+
+// Polls a custom readiness predicate until it returns true
+async fn wait_until<F, Fut>(timeout: Duration, what: &str, mut ready: F) -> WebDriverResult<()>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
     let start = tokio::time::Instant::now();
+    let mut interval = Duration::from_millis(100);
     loop {
+        if ready().await {
+            return Ok(());
+        }
         if tokio::time::Instant::now() - start > timeout {
-            return Err(WebDriverError::Timeout("Timeout waiting for element".into()));
+            return Err(WebDriverError::Timeout(format!("Timeout waiting for {}", what)));
         }
-        if driver.find(selector.clone()).await.is_ok() {
-            return Ok(());
+        sleep(interval).await;
+        interval = next_poll_interval(interval);
+    }
+}
+
+// Waits for an element matching `selector` that also satisfies `predicate`
+async fn wait_for_element_matching<P, Fut>(driver: &WebDriver, selector: By, timeout: Duration, predicate: P) -> WebDriverResult<WebElement>
+where
+    P: Fn(WebElement) -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let start = tokio::time::Instant::now();
+    let mut interval = Duration::from_millis(100);
+    loop {
+        if let Ok(element) = driver.find(selector.clone()).await {
+            if predicate(element.clone()).await {
+                return Ok(element);
+            }
+        }
+        if tokio::time::Instant::now() - start > timeout {
+            return Err(WebDriverError::Timeout("Timeout waiting for element".into()));
         }
-        sleep(Duration::from_millis(500)).await;
+        sleep(interval).await;
+        interval = next_poll_interval(interval);
     }
 }
 
-async fn get_job_detail_ids(driver: &WebDriver, page_number: usize) -> WebDriverResult<Vec<Job>> {
+async fn wait_for_element(driver: &WebDriver, selector: By, timeout: Duration) -> WebDriverResult<()> {
+    wait_for_element_matching(driver, selector, timeout, |_| async { true }).await?;
+    Ok(())
+}
+
+async fn wait_for_element_clickable(driver: &WebDriver, selector: By, timeout: Duration) -> WebDriverResult<WebElement> {
+    wait_for_element_matching(driver, selector, timeout, |element| async move {
+        element.is_clickable().await.unwrap_or(false)
+    })
+    .await
+}
+
+// Snapshot of the page location used to detect navigation, including
+// client-side routing that only pushes history state.
+async fn navigation_state(driver: &WebDriver) -> WebDriverResult<(String, u64)> {
+    let state = driver.execute("return [window.location.href, window.history.length];", vec![]).await?;
+    let href = state.json()[0].as_str().unwrap_or_default().to_string();
+    let history_length = state.json()[1].as_u64().unwrap_or(0);
+    Ok((href, history_length))
+}
+
+// Runs `action` (typically a click) and waits until the URL or history state
+// changes. Returns false if nothing changed before the timeout.
+async fn wait_for_url_change<F, Fut>(driver: &WebDriver, timeout: Duration, action: F) -> WebDriverResult<bool>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = WebDriverResult<()>>,
+{
+    let before = navigation_state(driver).await?;
+    action().await?;
+
+    let changed = wait_until(timeout, "navigation", || async {
+        navigation_state(driver).await.map_or(false, |state| state != before)
+    })
+    .await;
+    Ok(changed.is_ok())
+}
+
+async fn get_job_detail_ids(driver: &WebDriver, timeouts: &TimeoutConfig, page_number: usize) -> WebDriverResult<Vec<Job>> {
+    let position = session::BrowserPosition::capture(driver).await?;
     // Wait for the page to load
-    wait_for_element(driver, By::Css("div"), Duration::from_secs(30)).await?;
-    sleep(Duration::from_secs(5)).await; // Additional delay to ensure the page is fully loaded
+    wait_for_element(driver, By::Css("div"), timeouts.element_wait()).await?;
+    sleep(timeouts.page_load()).await; // Additional delay to ensure the page is fully loaded
+    session::recover(driver, timeouts, &position).await?;
 
-    println!("Finding elements...");
-    let div_elements = driver.find_all(By::Css("div")).await?;
-    println!("Found {} <div> elements", div_elements.len());
+    let parsed = search_result::parse_search_results(driver, page_number).await?;
+    let mut results = parsed.results;
+    if results.is_empty() {
+        if parsed.parse_errors > 0 {
+            println!("Every card on page {} failed to parse; scanning the page's links instead", page_number);
+        }
+        results = search_result::scan_job_links(driver).await?;
+    }
 
     let mut jobs = Vec::new();
-    let mut seen_ids = HashSet::new();
-    let hash_pattern = Regex::new(r"^[a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12}$").unwrap();
-
-    for div in div_elements {
-        let a_elements = div.find_all(By::Css("a")).await?;
-        for a in a_elements {
-            if let Ok(id) = a.attr("id").await {
-                if let Some(id_value) = id {
-                    if hash_pattern.is_match(&id_value) && !seen_ids.contains(&id_value) {
-                        if let Ok(title) = a.text().await {
-                            println!("Job Title: {}, Job ID: {}", title, id_value);
-                            let job = Job {
-                                page_number,
-                                job_title: title,
-                                url: format!("https://dice.com/job-detail/{}", id_value),
-                            };
-                            jobs.push(job);
-                            seen_ids.insert(id_value);
-                        }
-                    }
-                }
-            }
+    for result in results {
+        if !result.easy_apply {
+            println!("Skipping job {}: no Easy Apply badge on its search card", result.id);
+            continue;
         }
+        println!("Job Title: {}, Job ID: {}", result.title, result.id);
+        jobs.push(Job {
+            page_number,
+            job_title: result.title,
+            url: canonical_job_url(&result.id),
+            company: result.company,
+            location: result.location,
+            salary: result.salary,
+            posted_age: result.posted_age,
+            applicants: result.applicants,
+            source: JobSource::Search,
+            query: None,
+        });
     }
 
     Ok(jobs)
 }
 
+// Walks result pages until one comes back empty, adds nothing new, the last
+// of `pages` is read or `max_jobs` jobs have been found
+async fn scrape_search_pages(driver: &WebDriver, timeouts: &TimeoutConfig, search: &SearchQuery, pages: RangeInclusive<usize>, max_jobs: Option<usize>) -> WebDriverResult<Vec<Job>> {
+    let mut jobs: Vec<Job> = Vec::new();
+    for page in pages {
+        if max_jobs.is_some_and(|max| jobs.len() >= max) {
+            println!("Reached max_jobs; not reading page {}", page);
+            break;
+        }
+        let url = DiceUrlBuilder::new(search).page(page).build();
+        session::navigate(driver, timeouts, url.as_str()).await?;
+        let found = get_job_detail_ids(driver, timeouts, page).await?;
+        let before = jobs.len();
+        discovery::merge_jobs(&mut jobs, found);
+        if jobs.len() == before {
+            println!("Page {} added no new jobs; stopping pagination", page);
+            break;
+        }
+    }
+    Ok(jobs)
+}
+
 fn generate_encoded_url(job_id: &str, job_title: &str, search_params: &str) -> String {
     let data = json!({
         "djvVersion": "new",
@@ -258,133 +758,926 @@ fn generate_encoded_url(job_id: &str, job_title: &str, search_params: &str) -> S
     format!("https://www.dice.com/apply?{}", encoded_data)
 }
 
-async fn open_job_urls(driver: &WebDriver, jobs: Vec<Job>, search_params: &str) -> WebDriverResult<()> {
+// Click the "Easy Apply" button using JavaScript
+async fn click_easy_apply_button(driver: &WebDriver, timeouts: &TimeoutConfig) -> WebDriverResult<()> {
+    if !xpath::click_button_with_text(driver, "Easy apply").await? {
+        println!("No Easy apply button on this page");
+    }
+    sleep(timeouts.apply_step()).await; // Wait to ensure the application page is fully loaded
+    Ok(())
+}
+
+const JOB_DETAIL_BASE: &str = "https://www.dice.com/job-detail/";
+
+// Ignores query strings, fragments and trailing slashes so tracked links
+// resolve to the same ID as clean ones
+fn job_id_from_url(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url).trim_end_matches('/');
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+// The one form job URLs are stored in: https, www, no tracking parameters
+fn canonical_job_url(url: &str) -> String {
+    format!("{}{}", JOB_DETAIL_BASE, job_id_from_url(url))
+}
+
+// Retries a job after tab crashes, up to recovery::MAX_CRASH_RETRIES times
+// and records how it went in the tracking database
+async fn apply_with_recovery(driver: &WebDriver, config: &Config, history: &mut History, job: &Job, search_params: &str) -> WebDriverResult<ApplyOutcome> {
+    let started_at = Utc::now();
+    let mut attempt = 0;
+    let result = loop {
+        match apply_to_job(driver, config, history, job, search_params).await {
+            Err(e) if recovery::is_tab_crash(&e) && attempt < recovery::MAX_CRASH_RETRIES => {
+                attempt += 1;
+                println!("Tab crashed while applying to {} (attempt {}): {}", job.job_title, attempt, e);
+                if let Err(e) = recovery::recover_crashed_tab(driver).await {
+                    break Err(e);
+                }
+                // A crash after the submit click must not lead to a second application
+                if history.applied_to(job_id_from_url(&job.url)) {
+                    break Ok(ApplyOutcome::Applied);
+                }
+                match recovery::submitted_before_crash(driver, &config.timeouts, &job.url).await {
+                    Ok(true) => {
+                        println!("{} was submitted before the crash; not applying again", job.job_title);
+                        break history.record(crashed_application(job)).map(|_| ApplyOutcome::Applied).map_err(Into::into);
+                    }
+                    Ok(false) => {}
+                    Err(e) => break Err(e),
+                }
+            }
+            result => break result,
+        }
+    };
+    let (outcome, error) = match &result {
+        Ok(ApplyOutcome::Applied) => ("applied", None),
+        Ok(ApplyOutcome::Skipped(reason)) => ("skipped", Some(reason.clone())),
+        Err(e) => ("failed", Some(e.to_string())),
+    };
+    events::application(job, outcome, error.as_deref());
+    if let Err(e) = history.record_attempt(job, started_at, outcome, error) {
+        println!("Could not record the attempt at {}: {}", job.job_title, e);
+    }
+    // Failed jobs stay queued for the next `apply --from-queue`, until
+    // they've failed history::MAX_QUEUE_FAILURES times
+    let id = job_id_from_url(&job.url);
+    let done = match &result {
+        Ok(_) => true,
+        Err(_) => match history.queued_failures(id) {
+            Ok(failures) if failures >= history::MAX_QUEUE_FAILURES => {
+                println!("Giving up on {} after {} failed attempts; removing it from the queue", job.job_title, failures);
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                println!("Could not count failed attempts at {}: {}", job.job_title, e);
+                false
+            }
+        },
+    };
+    if done {
+        if let Err(e) = history.dequeue(id) {
+            println!("Could not remove {} from the queue: {}", job.job_title, e);
+        }
+    }
+    result
+}
+
+// Applications allowed this run: the consent limit, lowered by the config's
+// max_applications_per_run and a command-line limit
+fn run_limit(config: &Config, consent: &consent::Consent, cli_limit: Option<usize>) -> usize {
+    [config.max_applications_per_run, cli_limit]
+        .into_iter()
+        .flatten()
+        .fold(consent.max_applications_per_run as usize, usize::min)
+}
+
+// Application state for one run, carried across batches when jobs arrive
+// a page at a time
+struct ApplyRun<'a> {
+    applied: usize,
+    max_applications: usize,
+    budget: RunBudget<'a>,
+    control: Option<PauseControl>,
+    rng: &'a mut RunRng,
+    // Set in strict mode
+    failure_rate: Option<FailureRate>,
+}
+
+impl<'a> ApplyRun<'a> {
+    fn new(config: &'a Config, history: &History, max_applications: usize, rng: &'a mut RunRng) -> ApplyRun<'a> {
+        ApplyRun {
+            applied: 0,
+            max_applications,
+            budget: RunBudget::new(config, history),
+            control: PauseControl::spawn(),
+            rng,
+            failure_rate: config.strict.as_ref().map(FailureRate::new),
+        }
+    }
+
+    fn limit_reached(&self) -> bool {
+        self.applied >= self.max_applications
+    }
+}
+
+async fn open_job_urls(driver: &WebDriver, config: &Config, history: &mut History, jobs: Vec<Job>, search_params: &str, max_applications: usize, rng: &mut RunRng) -> WebDriverResult<()> {
+    let mut run = ApplyRun::new(config, history, max_applications, rng);
+    apply_batch(driver, config, history, &mut run, jobs, search_params).await
+}
+
+async fn apply_batch(driver: &WebDriver, config: &Config, history: &mut History, run: &mut ApplyRun<'_>, jobs: Vec<Job>, search_params: &str) -> WebDriverResult<()> {
     for job in jobs {
-        println!("Opening job URL: {}", job.url);
-        let encoded_url = generate_encoded_url(&job.url, &job.job_title, search_params);
-        println!("Navigating to encoded URL: {}", encoded_url);
+        if run.limit_reached() {
+            println!("Reached the limit of {} applications for this run", run.max_applications);
+            break;
+        }
+        // Queued and API-submitted jobs never went through filter_jobs
+        if let Some(reason) = applied_reason(history, &job) {
+            println!("Skipping {}: {}", job.job_title, reason);
+            continue;
+        }
+        if let Some(reason) = run.budget.exhausted(&job) {
+            println!("Skipping {}: {}", job.job_title, reason);
+            continue;
+        }
+        if let Some(control) = run.control.as_mut() {
+            control.wait_if_paused().await;
+        }
+
+        match apply_with_recovery(driver, config, history, &job, search_params).await {
+            Ok(ApplyOutcome::Applied) => {}
+            Ok(ApplyOutcome::Skipped(_)) => continue,
+            Err(e) => {
+                failures::capture_failure(driver, &job, &e, config.capture_har).await;
+                strict::on_failure(run.failure_rate.as_mut(), config.alerts.as_ref(), &job, e).await?;
+                continue;
+            }
+        }
+        if let Some(failure_rate) = run.failure_rate.as_mut() {
+            failure_rate.record(false);
+        }
+        run.applied += 1;
+        run.budget.record(&job);
 
-        // Load cookies from the file
-        load_cookies(driver).await?;
+        // Pause before opening the next URL
+        sleep(run.rng.jitter(config.timeouts.navigation(), config.timeouts.jitter())).await;
+    }
+    Ok(())
+}
 
-        driver.get(&encoded_url).await?;
-        sleep(Duration::from_secs(10)).await; // Wait for 10 seconds to ensure the page is fully loaded
+// Same as open_job_urls, but drains the shared Redis queue so several
+// workers split the jobs and the daily budget between them
+async fn apply_from_queue(driver: &WebDriver, config: &Config, history: &mut History, queue: &mut RedisQueue, search_params: &str, max_applications: usize, rng: &mut RunRng) -> WebDriverResult<()> {
+    let mut applied = 0;
+    let mut budget = RunBudget::new(config, history);
+    let mut control = PauseControl::spawn();
+    let mut failure_rate = config.strict.as_ref().map(FailureRate::new);
+    while applied < max_applications {
+        // Pause before claiming so a paused worker doesn't hold budget
+        if let Some(control) = control.as_mut() {
+            control.wait_if_paused().await;
+        }
+        if !queue.claim_budget().await? {
+            println!("Shared daily application budget is used up");
+            break;
+        }
+        let Some(job) = queue.pop().await? else {
+            queue.release_budget().await?;
+            println!("Shared job queue is empty");
+            break;
+        };
+        if let Some(reason) = applied_reason(history, &job) {
+            println!("Skipping {}: {}", job.job_title, reason);
+            queue.release_budget().await?;
+            continue;
+        }
+        if let Some(reason) = budget.exhausted(&job) {
+            println!("Skipping {}: {}", job.job_title, reason);
+            queue.release_budget().await?;
+            continue;
+        }
 
-        // Click the "Easy Apply" button using JavaScript
-        let script_easy_apply = r#"
-            var button = document.querySelector('button.btn.btn-primary');
-            if (button && button.innerText === 'Easy apply') {
-                button.click();
+        match apply_with_recovery(driver, config, history, &job, search_params).await {
+            Ok(ApplyOutcome::Applied) => {}
+            Ok(ApplyOutcome::Skipped(_)) => {
+                queue.release_budget().await?;
+                continue;
             }
-        "#;
-        driver.execute(script_easy_apply, vec![]).await?;
-        sleep(Duration::from_secs(10)).await; // Wait for 10 seconds to ensure the application page is fully loaded
-
-        // Wait for the "Next" button to be present and clickable
-        let script_wait_next_button = r#"
-            return new Promise((resolve) => {
-                const observer = new MutationObserver((mutations, obs) => {
-                    const nextButton = document.querySelector('button.seds-button-primary.btn-next');
-                    if (nextButton && nextButton.innerText === 'Next') {
-                        obs.disconnect();
-                        resolve(true);
-                    }
-                });
-                observer.observe(document, { childList: true, subtree: true });
-            });
-        "#;
-        driver.execute(script_wait_next_button, vec![]).await?;
-        sleep(Duration::from_secs(2)).await; // Wait for 2 seconds to ensure the button is fully interactable
-
-        // Click the "Next" button using JavaScript
-        let script_next_button = r#"
-            var nextButton = document.querySelector('button.seds-button-primary.btn-next');
-            if (nextButton && nextButton.innerText === 'Next') {
-                nextButton.click();
+            Err(e) => {
+                failures::capture_failure(driver, &job, &e, config.capture_har).await;
+                queue.release_budget().await?;
+                strict::on_failure(failure_rate.as_mut(), config.alerts.as_ref(), &job, e).await?;
+                continue;
             }
-        "#;
-        driver.execute(script_next_button, vec![]).await?;
-        sleep(Duration::from_secs(10)).await; // Wait for 10 seconds to ensure the next page is fully loaded
-
-        // Wait for the "Submit" button to be present and clickable
-        let script_wait_submit_button = r#"
-            return new Promise((resolve) => {
-                const observer = new MutationObserver((mutations, obs) => {
-                    const submitButton = document.querySelector('button.seds-button-primary.btn-next');
-                    if (submitButton && submitButton.innerText === 'Submit') {
-                        obs.disconnect();
-                        resolve(true);
-                    }
-                });
-                observer.observe(document, { childList: true, subtree: true });
+        }
+        if let Some(failure_rate) = failure_rate.as_mut() {
+            failure_rate.record(false);
+        }
+        applied += 1;
+        budget.record(&job);
+
+        sleep(rng.jitter(config.timeouts.navigation(), config.timeouts.jitter())).await;
+    }
+    Ok(())
+}
+
+// What's known about an application whose tab crashed after submitting
+fn crashed_application(job: &Job) -> ApplicationRecord {
+    ApplicationRecord {
+        job_id: job_id_from_url(&job.url).to_string(),
+        job_title: job.job_title.clone(),
+        url: canonical_job_url(&job.url),
+        applied_at: Utc::now(),
+        resume_variant: None,
+        applicants: job.applicants,
+        source: job.source,
+        category: None,
+        summary: None,
+        status: ApplicationStatus::Applied,
+        description_hash: None,
+        company: job.company.clone(),
+        snapshot: None,
+    }
+}
+
+// Checks made on the job page can turn a job down before anything is
+// submitted; Skipped carries the reason
+enum ApplyOutcome {
+    Applied,
+    Skipped(String),
+}
+
+async fn apply_to_job(driver: &WebDriver, config: &Config, history: &mut History, job: &Job, search_params: &str) -> WebDriverResult<ApplyOutcome> {
+    println!("Opening job URL: {}", job.url);
+    let encoded_url = generate_encoded_url(&job.url, &job.job_title, search_params);
+    println!("Navigating to encoded URL: {}", encoded_url);
+
+    // Load cookies from the file
+    load_cookies(driver).await?;
+
+    // Recording starts before navigating so the page load itself is captured
+    if config.capture_har {
+        har::start_recording(driver).await?;
+    }
+    session::navigate(driver, &config.timeouts, &encoded_url).await?;
+    sleep(config.timeouts.apply_step()).await; // Wait to ensure the page is fully loaded
+    if recovery::shows_applied(driver).await? {
+        let reason = "Dice shows it as already applied to".to_string();
+        println!("Skipping {}: {}", job.job_title, reason);
+        return Ok(ApplyOutcome::Skipped(reason));
+    }
+
+    // Structured data first; the page's markup only for what it leaves out
+    let posting = job_posting::from_page(driver).await?.map(|posting| job_posting::details(&posting)).unwrap_or_default();
+    if let (Some(title), Some(date_posted)) = (&posting.title, &posting.date_posted) {
+        println!("Job page: {} at {}, posted {}", title, posting.company.as_deref().unwrap_or("unknown company"), date_posted);
+    }
+    let description = match posting.description {
+        Some(description) => Some(description),
+        None => content_guard::description_text(driver).await?,
+    };
+    let description_hash = description.as_deref().map(content_guard::description_hash);
+    let mismatch = match workplace::mismatch(driver, config, job).await? {
+        Some(reason) => Some(reason),
+        None => salary::job_page_below_minimum(driver, &config.job_filters, job, posting.salary.as_deref()).await?,
+    };
+    let mismatch = mismatch
+        .or_else(|| blacklist::description_reason(&config.blacklist, description.as_deref()))
+        .or_else(|| content_guard::duplicate_reason(&config.job_filters, history, job, description_hash.as_deref()));
+    if let Some(reason) = mismatch {
+        println!("Skipping {}: {}", job.job_title, reason);
+        return Ok(ApplyOutcome::Skipped(reason));
+    }
+    // Printed before the apply wizard replaces the description
+    let snapshot = if config.snapshot_pdf { snapshot::save_job_pdf(driver, job).await } else { None };
+
+    click_easy_apply_button(driver, &config.timeouts).await?;
+    visual::check_page(driver, &config.visual_diff, "apply_form").await?;
+
+    // Swap in the assigned resume variant on the first wizard step
+    let category = category::classify(&config.categories, &job.job_title);
+    let variant = resume::assign_variant(&config.resume, history, &job.job_title, category.as_deref());
+    if let Some(variant) = variant {
+        resume::upload_resume(driver, variant).await?;
+    }
+
+    // Wait for the "Next" button to be present and clickable
+    let script_wait_next_button = r#"
+        return new Promise((resolve) => {
+            const observer = new MutationObserver((mutations, obs) => {
+                const nextButton = document.querySelector('button.seds-button-primary.btn-next');
+                if (nextButton && nextButton.innerText === 'Next') {
+                    obs.disconnect();
+                    resolve(true);
+                }
             });
-        "#;
-        driver.execute(script_wait_submit_button, vec![]).await?;
-        sleep(Duration::from_secs(2)).await; // Wait for 2 seconds to ensure the button is fully interactable
-
-        // Click the "Submit" button using JavaScript
-        let script_submit_button = r#"
-            var submitButton = document.querySelector('button.seds-button-primary.btn-next');
-            if (submitButton && submitButton.innerText === 'Submit') {
-                submitButton.click();
+            observer.observe(document, { childList: true, subtree: true });
+        });
+    "#;
+    driver.execute(script_wait_next_button, vec![]).await?;
+    // Wait for the button to become interactable
+    if wait_for_element_clickable(driver, xpath::button_with_text("Next"), config.timeouts.clickable_wait()).await.is_err() {
+        println!("Next button not clickable yet; trying anyway");
+    }
+
+    // Click the "Next" button using JavaScript
+    xpath::click_button_with_text(driver, "Next").await?;
+    sleep(config.timeouts.apply_step()).await; // Wait to ensure the next page is fully loaded
+    questions::answer_screening_questions(driver, &config.profile).await?;
+
+    // Wait for the "Submit" button to be present and clickable
+    let script_wait_submit_button = r#"
+        return new Promise((resolve) => {
+            const observer = new MutationObserver((mutations, obs) => {
+                const submitButton = document.querySelector('button.seds-button-primary.btn-next');
+                if (submitButton && submitButton.innerText === 'Submit') {
+                    obs.disconnect();
+                    resolve(true);
+                }
+            });
+            observer.observe(document, { childList: true, subtree: true });
+        });
+    "#;
+    driver.execute(script_wait_submit_button, vec![]).await?;
+    // Wait for the button to become interactable
+    if wait_for_element_clickable(driver, xpath::button_with_text("Submit"), config.timeouts.clickable_wait()).await.is_err() {
+        println!("Submit button not clickable yet; trying anyway");
+    }
+
+    // Fill the optional "message to employer" field before submitting
+    if config.message.enabled {
+        let taxonomy = SkillTaxonomy::new(&config.skill_synonyms);
+        message::insert_employer_message(driver, &config.message, &config.profile.skills, &taxonomy, job).await?;
+    }
+
+    // Click the "Submit" button using JavaScript
+    let navigated = wait_for_url_change(driver, config.timeouts.apply_step(), || async {
+        xpath::click_button_with_text(driver, "Submit").await?;
+        Ok(())
+    })
+    .await?;
+    if !navigated {
+        println!("No navigation after submitting {}; the application may not have gone through", job.job_title);
+    }
+
+    let summary = summary::summarize_job(&config.summary, &config.profile.skills, job).await;
+    history.record(ApplicationRecord {
+        job_id: job_id_from_url(&job.url).to_string(),
+        job_title: job.job_title.clone(),
+        url: canonical_job_url(&job.url),
+        applied_at: Utc::now(),
+        resume_variant: variant.map(|v| v.name.clone()),
+        applicants: job.applicants,
+        source: job.source,
+        category,
+        summary,
+        status: ApplicationStatus::Applied,
+        description_hash,
+        company: job.company.clone().or(posting.company),
+        snapshot: snapshot.map(|path| path.display().to_string()),
+    })?;
+    Ok(ApplyOutcome::Applied)
+}
+
+
+// What search_and_apply does once the job list is ready
+#[derive(Clone, Copy)]
+enum RunMode {
+    // Record the jobs and save the run report, then stop
+    SearchOnly,
+    // SearchOnly, then open the matching jobs in tabs for the user
+    Assist { tabs: usize },
+    // SearchOnly, then add the matching jobs to Dice's Saved Jobs
+    Bookmark { limit: Option<usize> },
+    // SearchOnly, then add the matching jobs to the persistent queue
+    Enqueue,
+    Apply { limit: Option<usize> },
+}
+
+// `zero_results` is kept across runs by the daemon; one-off runs report every
+// empty search
+async fn search_and_apply(driver: &WebDriver, config: &Config, history: &mut History, url: &str, mode: RunMode, mut stages: StageTimings, zero_results: Option<&mut ZeroResultLog>) -> WebDriverResult<()> {
+    history.start_run();
+    // Never auto-submit without the user's recorded consent
+    let consent = match mode {
+        RunMode::Apply { .. } => Some(consent::require_consent()?),
+        RunMode::SearchOnly | RunMode::Assist { .. } | RunMode::Bookmark { .. } | RunMode::Enqueue => None,
+    };
+    let mut rng = RunRng::new(config.seed);
+    stages.start("prelude");
+    prelude::run_prelude(driver, &config.prelude, &mut rng).await?;
+    stages.start("sync");
+    let remote_applied = match &config.sync {
+        Some(sync_config) => sync::pull(sync_config).await?,
+        None => Default::default(),
+    };
+
+    if config.low_memory || config.pipeline.is_some() {
+        if let (Some(consent), RunMode::Apply { limit }) = (&consent, mode) {
+            if config.redis.is_none() {
+                let mut paged = PagedApply {
+                    remote_applied: &remote_applied,
+                    report: RunReport::new(&config.search.q, &[], &[], &config.job_filters, history),
+                    run: ApplyRun::new(config, history, run_limit(config, consent, limit), &mut rng),
+                };
+                let result = match &config.pipeline {
+                    Some(pipeline_config) => pipeline::run(driver, config, history, &mut paged, &mut stages, pipeline_config).await,
+                    None => apply_pages(driver, config, history, &mut paged, &mut stages, job_stream::result_pages(driver, config)).await,
+                };
+                return finish_run(config, history, &remote_applied, paged.report, stages, result).await;
             }
-        "#;
-        driver.execute(script_submit_button, vec![]).await?;
-        sleep(Duration::from_secs(10)).await; // Wait for 10 seconds to ensure the application is submitted
+            println!("low_memory and pipeline are ignored when applying from a shared Redis queue");
+        }
+    }
 
-        // Wait for 2 seconds before opening the next URL
-        sleep(Duration::from_secs(2)).await;
+    stages.start("search");
+    let mut report = RunReport::new(&config.search.q, &[], &[], &config.job_filters, history);
+    session::navigate(driver, &config.timeouts, url).await?;
+    let mut jobs = get_job_detail_ids(driver, &config.timeouts, 1).await?;
+    for job in &mut jobs {
+        job.query = Some(config.search.label().to_string());
     }
-    Ok(())
+    report.count_jobs_found(&jobs);
+    if jobs.is_empty() {
+        // An empty results page would make a useless visual reference
+        if zero_results.is_none_or(|log| log.should_report(url)) {
+            println!("Search '{}' returned no results", config.search.q);
+        }
+    } else {
+        if let Some(log) = zero_results {
+            log.clear(url);
+        }
+        visual::check_page(driver, &config.visual_diff, "search_results").await?;
+    }
+    if !config.searches.is_empty() {
+        let extra = tabs::scrape_in_tabs(driver, &config.timeouts, &config.searches, config.max_search_tabs).await?;
+        report.count_jobs_found(&extra);
+        discovery::merge_jobs(&mut jobs, extra);
+    }
+    // The first page of every search is in; read further ones one search at a time
+    if config.max_pages > 1 {
+        for search in std::iter::once(&config.search).chain(&config.searches) {
+            let remaining = config.max_jobs.map(|max| max.saturating_sub(jobs.len()));
+            if remaining == Some(0) {
+                break;
+            }
+            let mut more = scrape_search_pages(driver, &config.timeouts, search, 2..=config.max_pages, remaining).await?;
+            for job in &mut more {
+                job.query = Some(search.label().to_string());
+            }
+            report.count_jobs_found(&more);
+            discovery::merge_jobs(&mut jobs, more);
+        }
+    }
+    stages.start("enrich");
+    let discovered = discovery::discover_jobs(&config.discovery).await;
+    report.count_jobs_found(&discovered);
+    discovery::merge_jobs(&mut jobs, discovered);
+    // After the merge, so discovered jobs count towards max_jobs too
+    if let Some(max) = config.max_jobs {
+        if jobs.len() > max {
+            println!("Keeping the first {} of {} scraped jobs (max_jobs)", max, jobs.len());
+            jobs.truncate(max);
+        }
+    }
+    if jobs.is_empty() {
+        println!("No jobs found by any search; nothing to apply to");
+        return Ok(());
+    }
+    history.record_jobs(&jobs)?;
+    events::jobs_scraped(&jobs);
+    let found = jobs.clone();
+
+    stages.start("filter");
+    let archive = PostingArchive::load(history)?;
+    let jobs = filter_jobs(config, history, &archive, &remote_applied, &mut rng, jobs).await;
+    report.jobs = RunReport::new(&config.search.q, &found, &jobs, &config.job_filters, history).jobs;
+
+    let (Some(consent), RunMode::Apply { limit }) = (consent, mode) else {
+        println!("{} of {} jobs matched; recorded all {} in {}", jobs.len(), found.len(), found.len(), paths::state(history::HISTORY_FILE).display());
+        match mode {
+            RunMode::Assist { tabs } => assist::open_in_tabs(driver, &jobs, tabs).await?,
+            RunMode::Bookmark { limit } => bookmark::save_jobs(driver, &config.timeouts, &jobs, limit, &mut rng).await?,
+            RunMode::Enqueue => {
+                let added = history.enqueue(&jobs)?;
+                println!("Queued {} new jobs ({} waiting); apply with `dice_blast apply --from-queue`", added, history.queue_len()?);
+            }
+            _ => {}
+        }
+        report.summarize_queries();
+        report.print_queries();
+        report.stages = stages.finish();
+        run_report::print_stages(&report.stages);
+        match report.save() {
+            Ok(path) => println!("Run report saved to {}", path.display()),
+            Err(e) => println!("Could not save run report: {}", e),
+        }
+        match html_report::save(&report, history) {
+            Ok(path) => println!("HTML report saved to {}", path.display()),
+            Err(e) => println!("Could not save HTML report: {}", e),
+        }
+        return Ok(());
+    };
+
+    if config.resume.gap_report {
+        stages.start("enrich");
+        let taxonomy = SkillTaxonomy::new(&config.skill_synonyms);
+        resume::keyword_gap_report(driver, &config.resume, &taxonomy, &config.timeouts, &jobs).await?;
+    }
+
+    stages.start("apply");
+    let mut jobs = jobs;
+    if config.shuffle_jobs {
+        rng.shuffle(&mut jobs);
+    }
+    let max_applications = run_limit(config, &consent, limit);
+    let result = match &config.redis {
+        Some(redis_config) => {
+            let mut queue = RedisQueue::connect(redis_config).await?;
+            let added = queue.enqueue(&jobs).await?;
+            println!("Added {} of {} jobs to the shared queue", added, jobs.len());
+            apply_from_queue(driver, config, history, &mut queue, "", max_applications, &mut rng).await
+        }
+        None => open_job_urls(driver, config, history, jobs, "", max_applications, &mut rng).await,
+    };
+    finish_run(config, history, &remote_applied, report, stages, result).await
+}
+
+// The repost, already-applied, blacklist, whitelist, filter, seniority,
+// third-party, description and synced-applied checks, in that order
+async fn filter_jobs(config: &Config, history: &History, archive: &PostingArchive, remote_applied: &BTreeSet<String>, rng: &mut RunRng, jobs: Vec<Job>) -> Vec<Job> {
+    if config.verbosity >= 2 {
+        filter_trace::print(config, history, archive, remote_applied, &jobs);
+    }
+    let mut jobs = archive::handle_reposts(archive, config.repost_policy, jobs);
+    let total = jobs.len();
+    jobs.retain(|job| match applied_reason(history, job) {
+        Some(reason) => {
+            println!("Skipping {}: {}", job.job_title, reason);
+            false
+        }
+        None => true,
+    });
+    if jobs.len() < total {
+        println!("Skipped {} jobs already applied to", total - jobs.len());
+    }
+    let jobs = blacklist::apply_blacklist(&config.blacklist, jobs);
+    let jobs = whitelist::apply_whitelist(config.whitelist.as_ref(), jobs);
+    let jobs = filters::apply_filters(&config.job_filters, jobs);
+    let jobs = seniority::apply_seniority_guard(&config.job_filters.seniority, config.profile.seniority, jobs);
+    let jobs = third_party::drop_third_party(config, rng, jobs).await;
+    let mut jobs = descriptions::drop_by_description(config, rng, jobs).await;
+    jobs.retain(|job| match synced_reason(remote_applied, job) {
+        Some(reason) => {
+            println!("Skipping {}: {}", job.job_title, reason);
+            false
+        }
+        None => true,
+    });
+    jobs
+}
+
+fn applied_reason(history: &History, job: &Job) -> Option<String> {
+    history.applied_to(job_id_from_url(&job.url)).then(|| "already applied in an earlier run".to_string())
+}
+
+fn synced_reason(remote_applied: &BTreeSet<String>, job: &Job) -> Option<String> {
+    remote_applied.contains(job_id_from_url(&job.url)).then(|| "already applied from another machine (synced applied set)".to_string())
+}
+
+// State for applying to jobs a page at a time (low_memory and pipeline)
+struct PagedApply<'a> {
+    remote_applied: &'a BTreeSet<String>,
+    report: RunReport,
+    run: ApplyRun<'a>,
+}
+
+impl PagedApply<'_> {
+    async fn filter(&mut self, config: &Config, history: &History, found: Vec<Job>) -> WebDriverResult<Vec<Job>> {
+        let archive = PostingArchive::load(history)?;
+        let jobs = filter_jobs(config, history, &archive, self.remote_applied, self.run.rng, found.clone()).await;
+        self.report.jobs.extend(RunReport::new(&config.search.q, &found, &jobs, &config.job_filters, history).jobs);
+        println!("{} of {} jobs in this batch matched", jobs.len(), found.len());
+        Ok(jobs)
+    }
+}
+
+// Each page of results is filtered and applied to before the next one is
+// taken, so only a page of jobs is held at a time and applying starts
+// without waiting for the whole search. Jobs from discovery sources come
+// last, as one batch. There's no gap report in this mode.
+async fn apply_pages<S>(driver: &WebDriver, config: &Config, history: &mut History, paged: &mut PagedApply<'_>, stages: &mut StageTimings, pages: S) -> WebDriverResult<()>
+where
+    S: futures::Stream<Item = WebDriverResult<ResultPage>>,
+{
+    let mut pages = pin!(pages);
+    loop {
+        stages.start("search");
+        let Some(page) = pages.next().await else {
+            break;
+        };
+        let page = page?;
+        paged.report.count_found(Some(&page.query), page.found);
+        if page.jobs.is_empty() {
+            continue;
+        }
+        stages.start("filter");
+        history.record_jobs(&page.jobs)?;
+        events::jobs_scraped(&page.jobs);
+        let mut jobs = paged.filter(config, history, page.jobs).await?;
+        if config.shuffle_jobs {
+            paged.run.rng.shuffle(&mut jobs);
+        }
+        stages.start("apply");
+        apply_batch(driver, config, history, &mut paged.run, jobs, "").await?;
+        if paged.run.limit_reached() {
+            return Ok(());
+        }
+    }
+
+    stages.start("enrich");
+    let discovered = discovery::discover_jobs(&config.discovery).await;
+    if discovered.is_empty() {
+        return Ok(());
+    }
+    stages.start("filter");
+    paged.report.count_jobs_found(&discovered);
+    history.record_jobs(&discovered)?;
+    events::jobs_scraped(&discovered);
+    let mut jobs = paged.filter(config, history, discovered).await?;
+    if config.shuffle_jobs {
+        paged.run.rng.shuffle(&mut jobs);
+    }
+    stages.start("apply");
+    apply_batch(driver, config, history, &mut paged.run, jobs, "").await
+}
+
+// The queued jobs, oldest first, dropping any applied to since they were
+// queued
+fn queued_to_apply(history: &mut History, remote_applied: &BTreeSet<String>) -> WebDriverResult<Vec<Job>> {
+    let mut jobs = Vec::new();
+    for job in history.queued_jobs()? {
+        let id = job_id_from_url(&job.url);
+        if history.applied_to(id) || remote_applied.contains(id) {
+            println!("Dropping {} from the queue: already applied", job.job_title);
+            history.dequeue(id)?;
+        } else {
+            jobs.push(job);
+        }
+    }
+    Ok(jobs)
+}
+
+// `apply --from-queue`: applies to the jobs earlier `scrape` runs queued,
+// oldest first, instead of searching. They were filtered when queued; only
+// jobs applied to since then are dropped here.
+async fn apply_queued(driver: &WebDriver, config: &Config, history: &mut History, limit: Option<usize>, mut stages: StageTimings) -> WebDriverResult<()> {
+    let consent = consent::require_consent()?;
+    let mut rng = RunRng::new(config.seed);
+    stages.start("prelude");
+    prelude::run_prelude(driver, &config.prelude, &mut rng).await?;
+    stages.start("sync");
+    let remote_applied = match &config.sync {
+        Some(sync_config) => sync::pull(sync_config).await?,
+        None => Default::default(),
+    };
+
+    stages.start("filter");
+    let mut jobs = queued_to_apply(history, &remote_applied)?;
+    if jobs.is_empty() {
+        println!("The queue is empty; add jobs with `dice_blast scrape`");
+        return Ok(());
+    }
+    println!("{} jobs in the queue", jobs.len());
+    let report = RunReport::new("(queue)", &jobs, &jobs, &config.job_filters, history);
+
+    stages.start("apply");
+    if config.shuffle_jobs {
+        rng.shuffle(&mut jobs);
+    }
+    let max_applications = run_limit(config, &consent, limit);
+    let result = open_job_urls(driver, config, history, jobs, "", max_applications, &mut rng).await;
+    finish_run(config, history, &remote_applied, report, stages, result).await
 }
 
+// Sync, export and the run report, which happen however applying ended
+async fn finish_run(config: &Config, history: &mut History, remote_applied: &BTreeSet<String>, mut report: RunReport, mut stages: StageTimings, result: WebDriverResult<()>) -> WebDriverResult<()> {
+    // Publish what we applied to even if the run stopped early
+    if let Some(sync_config) = &config.sync {
+        stages.start("sync");
+        sync::push(sync_config, remote_applied, history).await?;
+    }
+    if config.sheets.is_some() || config.airtable.is_some() {
+        stages.start("export");
+    }
+    if let Some(sheets_config) = &config.sheets {
+        if let Err(e) = sheets::export_applications(sheets_config, history, report.started_at).await {
+            println!("Google Sheets export failed: {}", e);
+        }
+    }
+    if let Some(airtable_config) = &config.airtable {
+        if let Err(e) = airtable::export_applications(airtable_config, history, report.started_at).await {
+            println!("Airtable export failed: {}", e);
+        }
+    }
+    report.attach_outcomes(history)?;
+    report.summarize_queries();
+    report.print_queries();
+    report.stages = stages.finish();
+    run_report::print_stages(&report.stages);
+    match report.save() {
+        Ok(path) => println!("Run report saved to {}", path.display()),
+        Err(e) => println!("Could not save run report: {}", e),
+    }
+    match html_report::save(&report, history) {
+        Ok(path) => println!("HTML report saved to {}", path.display()),
+        Err(e) => println!("Could not save HTML report: {}", e),
+    }
+    result?;
+    analytics::print_variant_report(history);
+    analytics::print_category_report(history);
+    let applications = history.records.iter().filter(|record| record.applied_at >= report.started_at).count();
+    success::check(config.success.as_ref(), config.alerts.as_ref(), applications, report.jobs.len()).await
+}
 
 
+// run_session, but a panic doesn't strand the browser: history is flushed,
+// the WebDriver session is closed, and the panic becomes an error
+async fn run_session_guarded(driver: &WebDriver, config: &Config, history: &mut History, url: &str, command: &Command, stages: StageTimings) -> WebDriverResult<()> {
+    let payload = match AssertUnwindSafe(run_session(driver, config, history, url, command, stages)).catch_unwind().await {
+        Ok(result) => return result,
+        Err(payload) => payload,
+    };
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    // Applications are already in the database; each is written as it's recorded
+    println!("Run panicked: {}. Closing the browser.", message);
+    if let Err(e) = driver.clone().quit().await {
+        println!("Could not close the browser: {}", e);
+    }
+    Err(WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("Run panicked: {}", message))))
+}
+
+// Runs the browser subcommand once the browser has a session
+async fn run_session(driver: &WebDriver, config: &Config, history: &mut History, url: &str, command: &Command, stages: StageTimings) -> WebDriverResult<()> {
+    match command {
+        Command::ImportSavedSearches => saved_searches::import_saved_searches(driver, &config.timeouts, &config.search).await,
+        Command::ScrapeOnly { .. } => scrape_only::run(driver, config).await,
+        Command::Watch => watch::run(driver, config).await,
+        Command::Daemon { schedule } => daemon::run(driver, config, history, url, schedule).await,
+        Command::VerifyApplyUrl { job } => verify_apply::verify_apply_url(driver, &config.timeouts, job).await,
+        Command::Serve => match &config.api {
+            Some(api) => api::serve(driver, config, history, api).await,
+            None => Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("`serve` needs an api section in the config".to_string()))),
+        },
+        Command::Search => search_and_apply(driver, config, history, url, RunMode::SearchOnly, stages, None).await,
+        Command::Scrape => search_and_apply(driver, config, history, url, RunMode::Enqueue, stages, None).await,
+        Command::Apply { limit, from_queue: true } => apply_queued(driver, config, history, *limit, stages).await,
+        Command::Apply { limit, .. } => {
+            let mode = match config.assist_tabs {
+                Some(tabs) => RunMode::Assist { tabs },
+                None if config.bookmark => RunMode::Bookmark { limit: *limit },
+                None => RunMode::Apply { limit: *limit },
+            };
+            search_and_apply(driver, config, history, url, mode, stages, None).await
+        }
+        // Handled in main before the browser starts
+        _ => Ok(()),
+    }
+}
+
 
 #[tokio::main]
 async fn main() -> WebDriverResult<()> {
-    let caps = DesiredCapabilities::chrome();
-    let driver = WebDriver::new("http://localhost:9415", caps).await?;
-    let url = build_url_from_config()?; // Unwrap the URL here
-    let login_result = login(&driver).await;
+    failures::install_panic_hook();
+    let cli = Cli::parse();
+    paths::init(cli.config_dir.as_deref(), cli.state_dir.as_deref())?;
+    if let Some(path) = &cli.jsonl {
+        events::open(path)?;
+    }
+    let command = cli.command.unwrap_or(Command::Apply { limit: None, from_queue: false });
 
-    match cookie_exists() {
-        Ok(true) => {
-            // Continue program execution
-            load_cookies(&driver).await?;
-            driver.get(&url).await?;
-            let jobs = get_job_detail_ids(&driver, 1).await?;
-            open_job_urls(&driver, jobs, "").await?;
-
-            println!("Press Enter to exit...");
-            let _ = io::stdout().flush();
-            let _ = io::stdin().read_line(&mut String::new());
-
-            Ok(())
-        }
-        Ok(false) => {
-            match login_result {
-                Ok(()) => {
-                    save_cookies(&driver).await?;
-                    driver.get(&url).await?;
-                    let jobs = get_job_detail_ids(&driver, 1).await?;
-                    open_job_urls(&driver, jobs, "").await?;
-
-                    println!("Press Enter to exit...");
-                    let _ = io::stdout().flush();
-                    let _ = io::stdin().read_line(&mut String::new());
-
-                    Ok(())
-                }
-                Err(e) => {
-                    println!("Something went wrong! Please try again... Error: {:?}", e);
-                    Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("Login failed".to_string())))
+    // Commands that don't need a browser
+    match &command {
+        Command::Init => return Ok(init::run_init(cli.config.as_deref())?),
+        Command::Consent => return Ok(consent::record_consent()?),
+        Command::DebugBundle => return Ok(debug_bundle::create_debug_bundle()?),
+        Command::Backup { path } => return Ok(backup::backup(path.as_deref())?),
+        Command::Restore { source } => return Ok(backup::restore(source)?),
+        Command::DiffRuns { run_a, run_b } => return Ok(run_report::diff_runs(run_a, run_b)?),
+        Command::Export { format, output } => {
+            let history = History::load(&paths::state(history::HISTORY_FILE))?;
+            return Ok(export::export(&history, *format, output.as_deref())?);
+        }
+        Command::NativeHost { .. } => return Ok(native_host::run(cli.config.as_deref()).await?),
+        Command::NativeHostManifest { extension_id } => return Ok(native_host::print_manifest(extension_id)?),
+        Command::Audit => {
+            let config = load_config(cli.config.as_deref())?;
+            let history = History::load(&paths::state(history::HISTORY_FILE))?;
+            return audit::run_audit(&config, &history).await;
+        }
+        Command::SkillsReport { top } => {
+            let config = load_config(cli.config.as_deref())?;
+            let postings = market::load_dataset(Path::new(&config.scrape_only.output))?;
+            market::print_skills_report(&postings, &SkillTaxonomy::new(&config.skill_synonyms), *top);
+            return Ok(());
+        }
+        Command::SalaryReport { min_postings } => {
+            let config = load_config(cli.config.as_deref())?;
+            let postings = market::load_dataset(Path::new(&config.scrape_only.output))?;
+            market::print_salary_benchmark(&postings, *min_postings);
+            return Ok(());
+        }
+        Command::Status { job: Some(job), state } => {
+            let mut history = History::load(&paths::state(history::HISTORY_FILE))?;
+            let id = job_id_from_url(job);
+            let Some(state) = state else {
+                let record = history.records.iter().rev().find(|record| record.job_id == id);
+                match record {
+                    Some(record) => println!("{} ({}): {}", record.job_title, id, record.status.as_str()),
+                    None => println!("No application to {} in the history", id),
                 }
+                return Ok(());
+            };
+            if history.set_status(id, *state)? {
+                println!("Marked {} as {}", id, state.as_str());
+            } else {
+                println!("No application to {} in the history", id);
             }
+            return Ok(());
+        }
+        Command::Status { job: None, .. } => {
+            let history = History::load(&paths::state(history::HISTORY_FILE))?;
+            let scraped = history.latest_jobs()?;
+            analytics::print_status(&history, &scraped);
+            return Ok(());
         }
+        Command::Report => {
+            let history = History::load(&paths::state(history::HISTORY_FILE))?;
+            println!("{} applications recorded", history.records.len());
+            analytics::print_variant_report(&history);
+            analytics::print_category_report(&history);
+            analytics::print_follow_up_reminders(&history);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let mut config = load_config(cli.config.as_deref())?;
+    if cli.seed.is_some() {
+        config.seed = cli.seed;
+    }
+    config.verbosity = cli.verbose;
+    if let Command::ScrapeOnly { max_pages: Some(max_pages) } = command {
+        config.max_pages = max_pages;
+    }
+
+    let mut caps = DesiredCapabilities::chrome();
+    if cli.headless {
+        caps.set_headless()?;
+    }
+    session::apply_fingerprint(&mut caps)?;
+    if config.capture_har {
+        har::enable_performance_log(&mut caps);
+    }
+    let driver = WebDriver::new(&config.webdriver_url, caps).await?;
+    let url = build_url_from_config(&config);
+    let mut history = History::load(&paths::state(history::HISTORY_FILE))?;
+    let mut stages = StageTimings::default();
+    stages.start("login");
+
+    if let Command::Login = command {
+        login(&driver).await?;
+        save_cookies(&driver).await?;
+        println!("Saved session cookies to {}", paths::state(paths::COOKIES_FILE).display());
+        return Ok(());
+    }
+
+    match cookie_exists() {
+        Ok(true) => restore_cookies(&driver).await?,
+        Ok(false) => match login(&driver).await {
+            Ok(()) => save_cookies(&driver).await?,
+            Err(e) => {
+                println!("Something went wrong! Please try again... Error: {:?}", e);
+                return Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("Login failed".to_string())));
+            }
+        },
         Err(e) => {
             println!("Error checking cookie file: {:?}", e);
-            Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("Error checking cookie file".to_string())))
+            return Err(WebDriverError::UnknownError(WebDriverErrorInfo::new("Error checking cookie file".to_string())));
         }
     }
+
+    run_session_guarded(&driver, &config, &mut history, &url, &command, stages).await?;
+
+    println!("Press Enter to exit...");
+    let _ = io::stdout().flush();
+    let _ = io::stdin().read_line(&mut String::new());
+
+    Ok(())
 }
@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use serde_json::Value;
+
+use crate::salary;
+use crate::skills::SkillTaxonomy;
+
+// Seniority and level markers dropped from titles before grouping
+const TITLE_NOISE: [&str; 10] = ["sr", "senior", "jr", "junior", "i", "ii", "iii", "iv", "mid", "level"];
+
+// A posting as written to the scrape-only dataset
+pub struct ScrapedPosting {
+    pub title: String,
+    pub location: Option<String>,
+    pub salary: Option<String>,
+    pub skills: Vec<String>,
+}
+
+// JobPosting metadata gives skills as one comma-separated string or a list
+fn skills_from_details(details: &Value) -> Vec<String> {
+    let skills: Vec<String> = match &details["skills"] {
+        Value::String(skills) => skills.split(',').map(str::to_string).collect(),
+        Value::Array(skills) => skills.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+    skills.into_iter().map(|skill| skill.trim().to_string()).filter(|skill| !skill.is_empty()).collect()
+}
+
+pub fn load_dataset(path: &Path) -> io::Result<Vec<ScrapedPosting>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut postings = Vec::new();
+    let mut unreadable = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(row) = serde_json::from_str::<Value>(&line) else {
+            unreadable += 1;
+            continue;
+        };
+        postings.push(ScrapedPosting {
+            title: row["job_title"].as_str().unwrap_or_default().to_string(),
+            location: row["location"].as_str().map(str::to_string),
+            salary: row["salary"].as_str().map(str::to_string),
+            skills: skills_from_details(&row["details"]),
+        });
+    }
+    if unreadable > 0 {
+        println!("Skipped {} unreadable lines in {}", unreadable, path.display());
+    }
+    Ok(postings)
+}
+
+#[derive(Default)]
+struct SkillDemand {
+    postings: usize,
+    salary_total: f64,
+    salaried: usize,
+}
+
+// How many scraped postings ask for each skill, and what the ones that post
+// pay offer on average (annualized midpoint of the range)
+pub fn print_skills_report(postings: &[ScrapedPosting], taxonomy: &SkillTaxonomy, top: usize) {
+    let mut demand: HashMap<String, SkillDemand> = HashMap::new();
+    let mut with_skills = 0;
+    for posting in postings {
+        let mut skills: Vec<String> = posting.skills.iter().map(|skill| taxonomy.canonical(skill)).collect();
+        skills.sort();
+        skills.dedup();
+        if skills.is_empty() {
+            continue;
+        }
+        with_skills += 1;
+        let pay = posting.salary.as_deref().and_then(salary::parse).map(|salary| salary.annual_midpoint());
+        for skill in skills {
+            let entry = demand.entry(skill).or_default();
+            entry.postings += 1;
+            if let Some(pay) = pay {
+                entry.salary_total += pay;
+                entry.salaried += 1;
+            }
+        }
+    }
+    if with_skills == 0 {
+        println!("No postings with skills in the dataset; run scrape-only with scrape_only.enrich set to true");
+        return;
+    }
+
+    let mut ranked: Vec<(String, SkillDemand)> = demand.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.postings.cmp(&a.1.postings).then_with(|| a.0.cmp(&b.0)));
+    println!("Top skills across {} postings:", with_skills);
+    for (skill, stats) in ranked.iter().take(top) {
+        let share = stats.postings as f64 / with_skills as f64 * 100.0;
+        let pay = if stats.salaried > 0 {
+            format!("avg ${:.0} ({} with pay)", stats.salary_total / stats.salaried as f64, stats.salaried)
+        } else {
+            "no pay posted".to_string()
+        };
+        println!("  {:<25} {:>5} postings ({:>4.1}%)  {}", skill, stats.postings, share, pay);
+    }
+}
+
+// "Sr. Software Engineer II (Remote) - Contract" -> "software engineer"
+fn normalize_title(title: &str) -> String {
+    let title = title.split(['(', '|']).next().unwrap_or(title);
+    let title = title.split(" - ").next().unwrap_or(title).to_lowercase();
+    title
+        .split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+        .filter(|word| !word.is_empty() && !TITLE_NOISE.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_location(location: Option<&str>) -> String {
+    let location = location.map(str::trim).unwrap_or_default();
+    if location.is_empty() {
+        "Unknown".to_string()
+    } else if location.to_lowercase().contains("remote") {
+        "Remote".to_string()
+    } else {
+        location.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+// Linear interpolation between the two nearest ranks; `sorted` is non-empty
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+// p25/p50/p75 of annualized pay per normalized title and location. Groups
+// with fewer than `min_postings` priced postings are left out as noise.
+pub fn print_salary_benchmark(postings: &[ScrapedPosting], min_postings: usize) {
+    let mut groups: BTreeMap<(String, String), Vec<f64>> = BTreeMap::new();
+    for posting in postings {
+        let Some(pay) = posting.salary.as_deref().and_then(salary::parse) else {
+            continue;
+        };
+        let title = normalize_title(&posting.title);
+        if title.is_empty() {
+            continue;
+        }
+        let location = normalize_location(posting.location.as_deref());
+        groups.entry((title, location)).or_default().push(pay.annual_midpoint());
+    }
+    let priced: usize = groups.values().map(Vec::len).sum();
+    groups.retain(|_, pays| pays.len() >= min_postings.max(1));
+    if groups.is_empty() {
+        println!("Not enough priced postings to benchmark ({} with pay; need {} per title and location)", priced, min_postings);
+        return;
+    }
+
+    println!("Salary benchmark from {} priced postings (annualized):", priced);
+    println!("  {:<35} {:<25} {:>5} {:>10} {:>10} {:>10}", "Title", "Location", "n", "p25", "p50", "p75");
+    for ((title, location), mut pays) in groups {
+        pays.sort_by(|a, b| a.total_cmp(b));
+        println!(
+            "  {:<35} {:<25} {:>5} {:>10.0} {:>10.0} {:>10.0}",
+            title,
+            location,
+            pays.len(),
+            percentile(&pays, 0.25),
+            percentile(&pays, 0.5),
+            percentile(&pays, 0.75)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_lose_level_and_extras() {
+        assert_eq!(normalize_title("Sr. Software Engineer II (Remote) - Contract"), "software engineer");
+        assert_eq!(normalize_title("Senior DevOps Engineer | AWS"), "devops engineer");
+        assert_eq!(normalize_title("C++ Developer"), "c++ developer");
+    }
+
+    #[test]
+    fn remote_locations_are_grouped() {
+        assert_eq!(normalize_location(Some("Remote or Austin, TX")), "Remote");
+        assert_eq!(normalize_location(Some("  Austin,  TX ")), "Austin, TX");
+        assert_eq!(normalize_location(None), "Unknown");
+    }
+
+    #[test]
+    fn percentiles_interpolate() {
+        let pays = [100.0, 200.0, 300.0, 400.0, 500.0];
+        assert_eq!(percentile(&pays, 0.25), 200.0);
+        assert_eq!(percentile(&pays, 0.5), 300.0);
+        assert_eq!(percentile(&[100.0, 200.0], 0.5), 150.0);
+        assert_eq!(percentile(&[120.0], 0.75), 120.0);
+    }
+}
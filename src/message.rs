@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thirtyfour::prelude::*;
+
+use crate::skills::SkillTaxonomy;
+use crate::template::TemplateContext;
+use crate::Job;
+
+// Placeholders are listed in template.rs
+const DEFAULT_TEMPLATE: &str = "Hello, I'm excited to apply for the {job_title} position at {company|your company}. \
+My hands-on experience with {skill} lines up well with what you're looking for, and I'd welcome the chance to talk further.";
+
+#[derive(Serialize, Deserialize)]
+pub struct MessageConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_template")]
+    pub template: String,
+    // When set, the message is generated by an OpenAI-compatible chat endpoint
+    // and the template is only used as a fallback
+    #[serde(default)]
+    pub llm: Option<LlmConfig>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LlmConfig {
+    pub endpoint: String,
+    pub model: String,
+    #[serde(default = "default_api_key_env")]
+    pub api_key_env: String,
+}
+
+impl Default for MessageConfig {
+    fn default() -> Self {
+        MessageConfig {
+            enabled: false,
+            template: default_template(),
+            llm: None,
+        }
+    }
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+fn default_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+// Profile skills mentioned in the job title or page text under any of their
+// names, in profile order
+pub fn matching_skills<'a>(skills: &'a [String], taxonomy: &SkillTaxonomy, job_title: &str, page_text: &str) -> Vec<&'a str> {
+    let haystack = format!("{} {}", job_title, page_text).to_lowercase();
+    skills
+        .iter()
+        .filter(|skill| taxonomy.mentioned_in(skill, &haystack))
+        .map(|skill| skill.as_str())
+        .collect()
+}
+
+async fn generate_llm_message(llm: &LlmConfig, context: &TemplateContext) -> Option<String> {
+    let company = context.get("company").map(|company| format!(" at {}", company)).unwrap_or_default();
+    let prompt = format!(
+        "Write a short (2-3 sentence) message to a hiring manager for the \"{}\" role{}. \
+         Mention my experience with {}. Plain text only, no greeting placeholders.",
+        context.get("job_title").unwrap_or_default(),
+        company,
+        context.get("skills").unwrap_or("this stack")
+    );
+    chat_completion(llm, &prompt).await
+}
+
+// Sends one user prompt to the chat endpoint and returns the reply text
+pub async fn chat_completion(llm: &LlmConfig, prompt: &str) -> Option<String> {
+    let api_key = std::env::var(&llm.api_key_env).unwrap_or_default();
+    let body = json!({
+        "model": llm.model,
+        "messages": [{ "role": "user", "content": prompt }],
+    });
+
+    let response = reqwest::Client::new()
+        .post(&llm.endpoint)
+        .bearer_auth(api_key)
+        .json(&body)
+        .send()
+        .await;
+
+    let response: Value = match response {
+        Ok(resp) => match resp.json().await {
+            Ok(value) => value,
+            Err(e) => {
+                println!("Could not parse LLM response: {}", e);
+                return None;
+            }
+        },
+        Err(e) => {
+            println!("LLM request failed: {}", e);
+            return None;
+        }
+    };
+
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+pub async fn compose_message(config: &MessageConfig, skills: &[String], taxonomy: &SkillTaxonomy, job: &Job, page_text: &str) -> String {
+    let mut mentioned = matching_skills(skills, taxonomy, &job.job_title, page_text);
+    if mentioned.is_empty() {
+        mentioned.extend(skills.first().map(|s| s.as_str()));
+    }
+    let context = TemplateContext::for_job(job, &mentioned);
+
+    if let Some(llm) = &config.llm {
+        if let Some(message) = generate_llm_message(llm, &context).await {
+            return message;
+        }
+        println!("Falling back to the message template");
+    }
+
+    context.render(&config.template)
+}
+
+// Fills the first visible, empty textarea in the Easy Apply wizard.
+// Returns false when the step has no message field.
+pub async fn insert_employer_message(driver: &WebDriver, config: &MessageConfig, skills: &[String], taxonomy: &SkillTaxonomy, job: &Job) -> WebDriverResult<bool> {
+    let textareas = driver.find_all(By::Css("textarea")).await?;
+    for textarea in textareas {
+        if !textarea.is_displayed().await.unwrap_or(false) {
+            continue;
+        }
+        let current = textarea.prop("value").await?.unwrap_or_default();
+        if !current.trim().is_empty() {
+            continue;
+        }
+
+        let page_text = driver.execute("return document.body.innerText;", vec![]).await?;
+        let page_text = page_text.json().as_str().unwrap_or_default().to_string();
+        let message = compose_message(config, skills, taxonomy, job, &page_text).await;
+
+        println!("Inserting message to employer for {}", job.job_title);
+        textarea.send_keys(&message).await?;
+        return Ok(true);
+    }
+
+    println!("No message field found for {}", job.job_title);
+    Ok(false)
+}
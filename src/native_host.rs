@@ -0,0 +1,197 @@
+use std::env;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::api::{self, ApiConfig};
+use crate::history::{self, ApplicationStatus, History};
+use crate::{config_path, job_id_from_url, paths, read_config_document, Job};
+
+// Native messaging host for the companion browser extension. The browser
+// starts `dice_blast native-host` and exchanges JSON messages over
+// stdin/stdout, each preceded by its length as a 32-bit native-endian
+// integer. Stdout belongs to the protocol, so diagnostics go to stderr.
+//
+// Requests:
+//   {"type": "ping"}
+//   {"type": "status", "url": "<job page URL>"}
+//   {"type": "queue", "url": "<job page URL>", "title": "<optional>"}
+// Replies:
+//   {"type": "pong", "version": "..."}
+//   {"type": "status", "job_id": "...", "applied": true, "applied_at": "...", "application_status": "applied", "queued": false}
+//   {"type": "queued", "job_id": "...", "queued": true, "queue_length": 3}
+//   {"type": "error", "message": "..."}
+// Queueing goes through the running `dice_blast serve` daemon; status reads
+// the application history directly and asks the daemon about its queue if
+// it's up.
+const HOST_NAME: &str = "com.dice_blast.native_host";
+
+// Chrome's cap on messages sent to the host
+const MAX_MESSAGE_BYTES: u32 = 64 * 1024 * 1024;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Ping,
+    Status { url: String },
+    Queue {
+        url: String,
+        #[serde(default)]
+        title: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Reply {
+    Pong { version: &'static str },
+    Status {
+        job_id: String,
+        applied: bool,
+        applied_at: Option<DateTime<Utc>>,
+        application_status: Option<ApplicationStatus>,
+        // None when the daemon isn't running
+        queued: Option<bool>,
+    },
+    Queued { job_id: String, queued: bool, queue_length: usize },
+    Error { message: String },
+}
+
+#[derive(Deserialize)]
+struct Submitted {
+    queued: bool,
+    queue_length: usize,
+}
+
+fn read_message(input: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut length = [0u8; 4];
+    match input.read_exact(&mut length) {
+        Ok(()) => {}
+        // The browser closes stdin when the extension disconnects
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let length = u32::from_ne_bytes(length);
+    if length > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("message of {} bytes is too large", length)));
+    }
+    let mut message = vec![0u8; length as usize];
+    input.read_exact(&mut message)?;
+    Ok(Some(message))
+}
+
+fn write_message(output: &mut impl Write, reply: &Reply) -> io::Result<()> {
+    let message = serde_json::to_vec(reply)?;
+    output.write_all(&(message.len() as u32).to_ne_bytes())?;
+    output.write_all(&message)?;
+    output.flush()
+}
+
+// Only the `api` section is needed, and load_config would log to stdout
+fn api_config(requested: Option<&Path>) -> Option<ApiConfig> {
+    let document = read_config_document(&config_path(requested)).ok()?;
+    serde_json::from_value(document.get("api")?.clone()).ok()
+}
+
+fn daemon_url(api: &ApiConfig, route: &str) -> String {
+    format!("http://{}{}", api.bind, route)
+}
+
+async fn queued_ids(client: &reqwest::Client, api: &ApiConfig) -> Option<Vec<String>> {
+    let response = client.get(daemon_url(api, "/queue")).bearer_auth(&api.token).send().await.ok()?;
+    let jobs: Vec<Job> = response.error_for_status().ok()?.json().await.ok()?;
+    Some(jobs.iter().map(|job| job_id_from_url(&job.url).to_string()).collect())
+}
+
+async fn status(client: &reqwest::Client, api: Option<&ApiConfig>, url: &str) -> Reply {
+    let Some(job_id) = api::submitted_job_id(url) else {
+        return Reply::Error { message: format!("{} is not a Dice job page", url) };
+    };
+    let history = match History::load(&paths::state(history::HISTORY_FILE)) {
+        Ok(history) => history,
+        Err(e) => return Reply::Error { message: format!("Could not read the application history: {}", e) },
+    };
+    let record = history.records.iter().rev().find(|record| record.job_id == job_id);
+    let queued = match api {
+        Some(api) => queued_ids(client, api).await.map(|ids| ids.contains(&job_id)),
+        None => None,
+    };
+    Reply::Status {
+        applied: record.is_some(),
+        applied_at: record.map(|r| r.applied_at),
+        application_status: record.map(|r| r.status),
+        queued,
+        job_id,
+    }
+}
+
+async fn queue(client: &reqwest::Client, api: Option<&ApiConfig>, url: &str, title: Option<String>) -> Reply {
+    let Some(api) = api else {
+        return Reply::Error { message: "Add an api section to the config and run dice_blast serve to queue jobs".to_string() };
+    };
+    let response = match client
+        .post(daemon_url(api, "/jobs"))
+        .bearer_auth(&api.token)
+        .json(&json!({ "job": url, "title": title }))
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(_) => return Reply::Error { message: format!("dice_blast serve isn't reachable on {}", api.bind) },
+    };
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Reply::Error { message: format!("The daemon refused the job ({}): {}", status, body) };
+    }
+    match response.json::<Submitted>().await {
+        Ok(submitted) => Reply::Queued {
+            job_id: api::submitted_job_id(url).unwrap_or_default(),
+            queued: submitted.queued,
+            queue_length: submitted.queue_length,
+        },
+        Err(e) => Reply::Error { message: format!("Unexpected reply from the daemon: {}", e) },
+    }
+}
+
+// Answers messages until the browser disconnects
+pub async fn run(requested: Option<&Path>) -> io::Result<()> {
+    let api = api_config(requested);
+    if api.is_none() {
+        eprintln!("No api section in the config; queueing is unavailable");
+    }
+    let client = reqwest::Client::new();
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    while let Some(message) = read_message(&mut input)? {
+        let reply = match serde_json::from_slice::<Request>(&message) {
+            Ok(Request::Ping) => Reply::Pong { version: env!("CARGO_PKG_VERSION") },
+            Ok(Request::Status { url }) => status(&client, api.as_ref(), &url).await,
+            Ok(Request::Queue { url, title }) => queue(&client, api.as_ref(), &url, title).await,
+            Err(e) => Reply::Error { message: format!("Unrecognised message: {}", e) },
+        };
+        write_message(&mut output, &reply)?;
+    }
+    Ok(())
+}
+
+// The host manifest to register with the browser. Chrome identifies
+// extensions by ID, Firefox by an ID that looks like an email address.
+pub fn print_manifest(extension_id: &str) -> io::Result<()> {
+    let path = env::current_exe()?;
+    let mut manifest = json!({
+        "name": HOST_NAME,
+        "description": "dice_blast companion",
+        "path": path,
+        "type": "stdio",
+    });
+    if extension_id.contains('@') {
+        manifest["allowed_extensions"] = json!([extension_id]);
+    } else {
+        manifest["allowed_origins"] = json!([format!("chrome-extension://{}/", extension_id)]);
+    }
+    println!("{}", serde_json::to_string_pretty(&manifest)?);
+    Ok(())
+}
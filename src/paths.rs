@@ -0,0 +1,130 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const APP_DIR: &str = "dice_blast";
+
+// Config files live in the config dir
+pub const CONFIG_FILES: [&str; 4] = ["config.json", "config.toml", "config.yaml", "config.yml"];
+pub const COOKIES_FILE: &str = "cookies.json";
+
+// Everything the tool writes, moved out of the working directory the first
+// time it runs with the standard directories
+const STATE_ENTRIES: [&str; 11] = [
+    COOKIES_FILE,
+    crate::session::FINGERPRINT_FILE,
+    crate::history::HISTORY_FILE,
+    crate::history::LEGACY_HISTORY_FILE,
+    crate::history::LEGACY_ARCHIVE_FILE,
+    crate::consent::CONSENT_FILE,
+    crate::saved_searches::IMPORT_FILE,
+    crate::watch::SEEN_FILE,
+    crate::visual::REFERENCE_DIR,
+    crate::run_report::RUNS_DIR,
+    crate::failures::FAILURES_DIR,
+];
+
+struct Dirs {
+    config: PathBuf,
+    state: PathBuf,
+}
+
+static DIRS: OnceLock<Dirs> = OnceLock::new();
+
+// ~/.config/dice_blast and ~/.local/state/dice_blast on Linux, the platform
+// equivalents elsewhere; the working directory if neither can be found
+fn default_dirs() -> Dirs {
+    let config = dirs::config_dir().map(|dir| dir.join(APP_DIR));
+    let state = dirs::state_dir().or_else(dirs::data_local_dir).map(|dir| dir.join(APP_DIR));
+    Dirs {
+        config: config.unwrap_or_else(|| PathBuf::from(".")),
+        state: state.unwrap_or_else(|| PathBuf::from(".")),
+    }
+}
+
+fn dirs() -> &'static Dirs {
+    DIRS.get_or_init(default_dirs)
+}
+
+pub fn config_dir() -> &'static Path {
+    &dirs().config
+}
+
+pub fn state_dir() -> &'static Path {
+    &dirs().state
+}
+
+pub fn config(name: &str) -> PathBuf {
+    config_dir().join(name)
+}
+
+pub fn state(name: &str) -> PathBuf {
+    state_dir().join(name)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// rename, or copy and delete when the directories are on different filesystems
+fn move_entry(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            move_entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        fs::remove_dir(from)
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+}
+
+// Moves files left in the working directory by older versions into `dir`.
+// Anything already present in `dir` wins and the old copy is left alone.
+fn migrate_legacy(names: &[&str], dir: &Path) -> io::Result<()> {
+    let legacy = Path::new(".");
+    if same_dir(legacy, dir) {
+        return Ok(());
+    }
+    for name in names {
+        let from = legacy.join(name);
+        let to = dir.join(name);
+        if !from.exists() {
+            continue;
+        }
+        if to.exists() {
+            println!("Leaving ./{} in place: {} already exists", name, to.display());
+            continue;
+        }
+        move_entry(&from, &to)?;
+        println!("Moved ./{} to {}", name, to.display());
+    }
+    Ok(())
+}
+
+// Resolves the config and state directories (overridden by --config-dir and
+// --state-dir), creates them, and migrates files from the working directory
+pub fn init(config_override: Option<&Path>, state_override: Option<&Path>) -> io::Result<()> {
+    let defaults = default_dirs();
+    let resolved = Dirs {
+        config: config_override.map(Path::to_path_buf).unwrap_or(defaults.config),
+        state: state_override.map(Path::to_path_buf).unwrap_or(defaults.state),
+    };
+    fs::create_dir_all(&resolved.config)?;
+    fs::create_dir_all(&resolved.state)?;
+    migrate_legacy(&CONFIG_FILES, &resolved.config)?;
+    migrate_legacy(&STATE_ENTRIES, &resolved.state)?;
+    if DIRS.set(resolved).is_err() {
+        println!("Config and state directories were already resolved; ignoring overrides");
+    }
+    Ok(())
+}
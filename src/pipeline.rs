@@ -0,0 +1,59 @@
+use std::pin::pin;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use thirtyfour::prelude::*;
+use tokio::sync::mpsc;
+
+use crate::history::History;
+use crate::run_report::StageTimings;
+use crate::{apply_pages, job_stream, restore_cookies, session, Config, PagedApply};
+
+// One WebDriver session can only show one page at a time, so scraping runs
+// in a second, headless session and hands pages of results over a bounded
+// channel. The applying session never waits on a results page unless it
+// has caught up with the scraper; the scraper stops reading ahead once
+// `buffer_pages` pages are waiting.
+#[derive(Serialize, Deserialize)]
+pub struct PipelineConfig {
+    #[serde(default = "default_buffer_pages")]
+    pub buffer_pages: usize,
+}
+
+fn default_buffer_pages() -> usize {
+    2
+}
+
+async fn start_scraper(config: &Config) -> WebDriverResult<WebDriver> {
+    let mut caps = DesiredCapabilities::chrome();
+    caps.set_headless()?;
+    session::apply_fingerprint(&mut caps)?;
+    let scraper = WebDriver::new(&config.webdriver_url, caps).await?;
+    restore_cookies(&scraper).await?;
+    Ok(scraper)
+}
+
+pub async fn run(driver: &WebDriver, config: &Config, history: &mut History, paged: &mut PagedApply<'_>, stages: &mut StageTimings, pipeline: &PipelineConfig) -> WebDriverResult<()> {
+    let scraper = start_scraper(config).await?;
+    let (sender, receiver) = mpsc::channel(pipeline.buffer_pages.max(1));
+
+    let scrape = async {
+        let sender = sender;
+        let mut pages = pin!(job_stream::result_pages(&scraper, config));
+        while let Some(page) = pages.next().await {
+            let failed = page.is_err();
+            // A closed channel means applying has stopped
+            if sender.send(page).await.is_err() || failed {
+                break;
+            }
+        }
+    };
+    // Dropping the receiver when applying ends stops the scraper at its next page
+    let pages = stream::unfold(receiver, |mut receiver| async move { receiver.recv().await.map(|page| (page, receiver)) });
+    let apply = apply_pages(driver, config, history, paged, stages, pages);
+
+    let ((), result) = tokio::join!(scrape, apply);
+    if let Err(e) = scraper.quit().await {
+        println!("Could not close the scraping session: {}", e);
+    }
+    result
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+use tokio::time::Duration;
+
+use crate::rng::RunRng;
+
+// Warm-up pages visited at a human pace before the scraping/applying burst,
+// so a session doesn't open straight onto a deep link at full speed
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreludeConfig {
+    pub enabled: bool,
+    pub pages: Vec<String>,
+    // Time spent on each page is min_pause_secs plus up to extra_pause_secs
+    pub min_pause_secs: u64,
+    pub extra_pause_secs: u64,
+}
+
+impl Default for PreludeConfig {
+    fn default() -> Self {
+        PreludeConfig {
+            enabled: false,
+            pages: vec![
+                "https://www.dice.com/".to_string(),
+                "https://www.dice.com/dashboard".to_string(),
+            ],
+            min_pause_secs: 3,
+            extra_pause_secs: 7,
+        }
+    }
+}
+
+pub async fn run_prelude(driver: &WebDriver, config: &PreludeConfig, rng: &mut RunRng) -> WebDriverResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+    let min_pause = Duration::from_secs(config.min_pause_secs);
+    let extra_pause = Duration::from_secs(config.extra_pause_secs);
+    for page in &config.pages {
+        println!("Warm-up: visiting {}", page);
+        driver.get(page).await?;
+        // Split the pause around a partial scroll, like someone skimming the page
+        sleep(rng.jitter(min_pause, extra_pause) / 2).await;
+        driver.execute("window.scrollBy({ top: window.innerHeight * 0.6, behavior: 'smooth' });", vec![]).await?;
+        sleep(rng.jitter(min_pause, extra_pause) / 2).await;
+    }
+    Ok(())
+}
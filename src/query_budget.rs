@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::{Config, Job};
+
+// Per-search application caps for one run, keyed by the search's label
+pub struct QueryBudget {
+    limits: HashMap<String, usize>,
+    applied: HashMap<String, usize>,
+}
+
+impl QueryBudget {
+    pub fn new(config: &Config) -> QueryBudget {
+        let limits = std::iter::once(&config.search)
+            .chain(&config.searches)
+            .filter_map(|search| search.max_applications.map(|max| (search.label().to_string(), max)))
+            .collect();
+        QueryBudget {
+            limits,
+            applied: HashMap::new(),
+        }
+    }
+
+    // Why the job's search can't take any more applications, if it can't
+    pub fn exhausted(&self, job: &Job) -> Option<String> {
+        let query = job.query.as_ref()?;
+        let limit = *self.limits.get(query)?;
+        let applied = self.applied.get(query).copied().unwrap_or(0);
+        if applied >= limit {
+            return Some(format!("search '{}' reached its max_applications ({})", query, limit));
+        }
+        None
+    }
+
+    pub fn record(&mut self, job: &Job) {
+        if let Some(query) = &job.query {
+            *self.applied.entry(query.clone()).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+    use crate::{locations, test_config, test_search};
+
+    #[test]
+    fn searches_sharing_a_query_have_separate_caps() {
+        let mut config = test_config(json!({
+            "location": "Austin, TX",
+            "max_applications": 1,
+            "searches": [test_search(json!({ "location": "Denver, CO", "max_applications": 2 }))],
+        }));
+        locations::fan_out(&mut config);
+        let mut budget = QueryBudget::new(&config);
+        let job = |id: &str, query: &str| {
+            let mut job = discovered_job(id, "Rust Engineer".to_string(), JobSource::Search);
+            job.query = Some(query.to_string());
+            job
+        };
+        budget.record(&job("j1", "rust @ Austin, TX"));
+        assert!(budget.exhausted(&job("j2", "rust @ Austin, TX")).is_some());
+        assert!(budget.exhausted(&job("j2", "rust @ Denver, CO")).is_none());
+        budget.record(&job("j2", "rust @ Denver, CO"));
+        assert!(budget.exhausted(&job("j3", "rust @ Denver, CO")).is_none());
+        budget.record(&job("j3", "rust @ Denver, CO"));
+        assert!(budget.exhausted(&job("j4", "rust @ Denver, CO")).is_some());
+    }
+}
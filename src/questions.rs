@@ -0,0 +1,163 @@
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use thirtyfour::prelude::*;
+
+use crate::Profile;
+
+const QUESTION_ATTR: &str = "data-dice-blast-question";
+
+// A screening question on the current wizard step
+#[derive(Deserialize)]
+struct Question {
+    id: usize,
+    text: String,
+    // "radio", "select" or "text"
+    kind: String,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+enum Answer {
+    // Text of the radio/select option to pick
+    Choice(String),
+    Text(String),
+}
+
+type Handler = fn(&Profile, &Question) -> Option<Answer>;
+
+// Tried in order; the first handler with an answer wins
+const HANDLERS: &[Handler] = &[relocation_answer, travel_answer];
+
+fn yes_no_option(question: &Question, yes: bool) -> Option<Answer> {
+    let wanted = if yes { "yes" } else { "no" };
+    question
+        .options
+        .iter()
+        .find(|option| option.trim().to_lowercase().starts_with(wanted))
+        .map(|option| Answer::Choice(option.clone()))
+}
+
+fn relocation_answer(profile: &Profile, question: &Question) -> Option<Answer> {
+    let willing = profile.willing_to_relocate?;
+    if !question.text.to_lowercase().contains("relocat") {
+        return None;
+    }
+    match question.kind.as_str() {
+        "text" => Some(Answer::Text(if willing { "Yes" } else { "No" }.to_string())),
+        _ => yes_no_option(question, willing),
+    }
+}
+
+fn percentages(text: &str) -> Vec<u32> {
+    let pattern = Regex::new(r"(\d{1,3})\s*%").unwrap();
+    pattern.captures_iter(text).filter_map(|cap| cap[1].parse().ok()).collect()
+}
+
+// Handles "Are you willing to travel up to 50%?" (yes/no), range options
+// like "25-50%", and free-text percentage fields
+fn travel_answer(profile: &Profile, question: &Question) -> Option<Answer> {
+    let max = profile.max_travel_percent?;
+    if !question.text.to_lowercase().contains("travel") {
+        return None;
+    }
+    if question.kind == "text" {
+        return Some(Answer::Text(max.to_string()));
+    }
+
+    // Pick the option with the highest travel that still fits the profile
+    let ranged = question
+        .options
+        .iter()
+        .filter_map(|option| percentages(option).into_iter().max().map(|upper| (upper, option)))
+        .filter(|(upper, _)| *upper <= max)
+        .max_by_key(|(upper, _)| *upper);
+    if let Some((_, option)) = ranged {
+        return Some(Answer::Choice(option.clone()));
+    }
+
+    let asked = percentages(&question.text).into_iter().max().unwrap_or(0);
+    yes_no_option(question, asked <= max)
+}
+
+// Answers the screening questions on the current wizard step that the
+// profile covers. Questions nobody handles are left for the user.
+pub async fn answer_screening_questions(driver: &WebDriver, profile: &Profile) -> WebDriverResult<usize> {
+    let script_collect = r#"
+        const attr = arguments[0];
+        const questions = [];
+        const labelText = el => {
+            const label = (el.id && document.querySelector(`label[for="${el.id}"]`)) || el.closest('label');
+            return label ? label.innerText.trim() : '';
+        };
+        document.querySelectorAll('fieldset').forEach(fieldset => {
+            const radios = Array.from(fieldset.querySelectorAll('input[type="radio"]'));
+            if (radios.length === 0) return;
+            const legend = fieldset.querySelector('legend');
+            fieldset.setAttribute(attr, questions.length);
+            questions.push({
+                id: questions.length,
+                text: legend ? legend.innerText.trim() : fieldset.innerText.trim(),
+                kind: 'radio',
+                options: radios.map(labelText)
+            });
+        });
+        document.querySelectorAll('select, input[type="text"], input[type="number"]').forEach(el => {
+            const text = labelText(el);
+            if (!text || el.offsetParent === null) return;
+            el.setAttribute(attr, questions.length);
+            questions.push({
+                id: questions.length,
+                text,
+                kind: el.tagName === 'SELECT' ? 'select' : 'text',
+                options: el.tagName === 'SELECT' ? Array.from(el.options).map(o => o.text.trim()) : []
+            });
+        });
+        return questions;
+    "#;
+    let questions: Vec<Question> = driver.execute(script_collect, vec![json!(QUESTION_ATTR)]).await?.convert()?;
+
+    let script_answer = r#"
+        const [attr, id, choice, text] = arguments;
+        const el = document.querySelector(`[${attr}="${id}"]`);
+        if (!el) return false;
+        if (el.tagName === 'FIELDSET') {
+            const radio = Array.from(el.querySelectorAll('input[type="radio"]')).find(r => {
+                const label = (r.id && document.querySelector(`label[for="${r.id}"]`)) || r.closest('label');
+                return label && label.innerText.trim() === choice;
+            });
+            if (!radio) return false;
+            radio.click();
+            return true;
+        }
+        if (el.tagName === 'SELECT') {
+            const option = Array.from(el.options).find(o => o.text.trim() === choice);
+            if (!option) return false;
+            el.value = option.value;
+        } else {
+            el.value = text;
+            el.dispatchEvent(new Event('input', { bubbles: true }));
+        }
+        el.dispatchEvent(new Event('change', { bubbles: true }));
+        return true;
+    "#;
+
+    let mut answered = 0;
+    for question in &questions {
+        let Some(answer) = HANDLERS.iter().find_map(|handler| handler(profile, question)) else {
+            continue;
+        };
+        let (choice, text) = match &answer {
+            Answer::Choice(choice) => (choice.as_str(), ""),
+            Answer::Text(text) => ("", text.as_str()),
+        };
+        let args = vec![json!(QUESTION_ATTR), json!(question.id), json!(choice), json!(text)];
+        if driver.execute(script_answer, args).await?.json().as_bool().unwrap_or(false) {
+            println!("Answered '{}' with '{}{}'", question.text, choice, text);
+            answered += 1;
+        } else {
+            println!("Could not answer '{}'", question.text);
+        }
+    }
+    Ok(answered)
+}
@@ -0,0 +1,102 @@
+use chrono::Utc;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+
+use crate::{job_id_from_url, Job};
+
+// Shared queue for several machines (each with its own browser) working one
+// application budget. Every worker enqueues what it scrapes; the seen-set
+// keeps a job from being queued twice across workers, and the budget counter
+// is claimed atomically before each application.
+#[derive(Serialize, Deserialize)]
+pub struct RedisConfig {
+    pub url: String,
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+    // Applications allowed per day across all workers
+    pub daily_budget: u32,
+}
+
+fn default_key_prefix() -> String {
+    "dice_blast".to_string()
+}
+
+pub struct RedisQueue {
+    connection: MultiplexedConnection,
+    prefix: String,
+    daily_budget: u32,
+}
+
+fn redis_error(err: redis::RedisError) -> WebDriverError {
+    WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("Redis error: {}", err)))
+}
+
+impl RedisQueue {
+    pub async fn connect(config: &RedisConfig) -> WebDriverResult<RedisQueue> {
+        let client = redis::Client::open(config.url.as_str()).map_err(redis_error)?;
+        let connection = client.get_multiplexed_async_connection().await.map_err(redis_error)?;
+        println!("Connected to shared job queue at {}", config.url);
+        Ok(RedisQueue {
+            connection,
+            prefix: config.key_prefix.clone(),
+            daily_budget: config.daily_budget,
+        })
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("{}:{}", self.prefix, name)
+    }
+
+    fn budget_key(&self) -> String {
+        self.key(&format!("budget:{}", Utc::now().format("%Y-%m-%d")))
+    }
+
+    // Returns how many of the jobs were new to the shared queue
+    pub async fn enqueue(&mut self, jobs: &[Job]) -> WebDriverResult<usize> {
+        let seen_key = self.key("seen");
+        let queue_key = self.key("queue");
+        let mut added = 0;
+        for job in jobs {
+            let is_new: bool = self
+                .connection
+                .sadd(&seen_key, job_id_from_url(&job.url))
+                .await
+                .map_err(redis_error)?;
+            if is_new {
+                let payload = serde_json::to_string(job)?;
+                let _: () = self.connection.rpush(&queue_key, payload).await.map_err(redis_error)?;
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    pub async fn pop(&mut self) -> WebDriverResult<Option<Job>> {
+        let payload: Option<String> = self.connection.lpop(self.key("queue"), None).await.map_err(redis_error)?;
+        match payload {
+            Some(payload) => Ok(Some(serde_json::from_str(&payload)?)),
+            None => Ok(None),
+        }
+    }
+
+    // Reserves one application from today's shared budget
+    pub async fn claim_budget(&mut self) -> WebDriverResult<bool> {
+        let key = self.budget_key();
+        let used: u32 = self.connection.incr(&key, 1).await.map_err(redis_error)?;
+        let _: () = self.connection.expire(&key, 2 * 24 * 60 * 60).await.map_err(redis_error)?;
+        if used > self.daily_budget {
+            let _: () = self.connection.decr(&key, 1).await.map_err(redis_error)?;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    // Gives a claimed slot back after a failed application
+    pub async fn release_budget(&mut self) -> WebDriverResult<()> {
+        let _: () = self.connection.decr(self.budget_key(), 1).await.map_err(redis_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+
+use crate::timeouts::TimeoutConfig;
+use crate::{restore_cookies, session};
+
+pub const MAX_CRASH_RETRIES: usize = 2;
+
+// Chrome reports a crashed renderer ("Aw, Snap!") through a handful of
+// different chromedriver messages depending on version.
+const CRASH_MARKERS: [&str; 3] = ["tab crashed", "page crash", "target crashed"];
+
+pub fn is_tab_crash(err: &WebDriverError) -> bool {
+    let message = format!("{}", err).to_lowercase();
+    CRASH_MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+// Once an application is in, Dice replaces the Easy apply button with an
+// "Applied" badge, inside the apply button's shadow root on newer pages
+const APPLIED_SCRIPT: &str = r#"
+    const els = Array.from(document.querySelectorAll('apply-button-wc, [data-testid*="apply"], [data-cy*="apply"]'));
+    const text = els.map(el => (el.shadowRoot ? el.shadowRoot.textContent : '') + ' ' + el.textContent).join(' ');
+    return /application submitted|\bapplied\b/i.test(text);
+"#;
+
+// Whether the open job page says it was already applied to
+pub async fn shows_applied(driver: &WebDriver) -> WebDriverResult<bool> {
+    driver.execute(APPLIED_SCRIPT, vec![]).await?.convert()
+}
+
+// Reopens the job after a crash to see whether the submit click got
+// through before the tab died
+pub async fn submitted_before_crash(driver: &WebDriver, timeouts: &TimeoutConfig, url: &str) -> WebDriverResult<bool> {
+    session::navigate(driver, timeouts, url).await?;
+    sleep(timeouts.apply_step()).await;
+    shows_applied(driver).await
+}
+
+// Opens a fresh tab, abandons the crashed one and restores the session cookies
+// so the current job can be retried from scratch.
+pub async fn recover_crashed_tab(driver: &WebDriver) -> WebDriverResult<()> {
+    println!("Recovering from crashed tab...");
+    let crashed = driver.window().await.ok();
+    let fresh = driver.new_tab().await?;
+
+    if let Some(crashed) = crashed {
+        // The crashed tab may refuse to close; that's fine as long as we move on
+        if driver.switch_to_window(crashed).await.is_ok() {
+            let _ = driver.close_window().await;
+        }
+    }
+    driver.switch_to_window(fresh).await?;
+
+    restore_cookies(driver).await?;
+
+    println!("Opened a new tab, retrying current job");
+    Ok(())
+}
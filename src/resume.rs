@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+use tokio::time::Duration;
+
+use crate::history::History;
+use crate::session;
+use crate::skills::SkillTaxonomy;
+use crate::timeouts::TimeoutConfig;
+use crate::Job;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ResumeConfig {
+    #[serde(default)]
+    pub path: Option<String>,
+    // Visit each job's detail page and report skills the resume doesn't mention
+    #[serde(default)]
+    pub gap_report: bool,
+    // Alternative resumes uploaded during Easy Apply, assigned per application
+    #[serde(default)]
+    pub variants: Vec<ResumeVariant>,
+    // Title keyword rules checked before falling back to round-robin
+    #[serde(default)]
+    pub rules: Vec<VariantRule>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ResumeVariant {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VariantRule {
+    pub variant: String,
+    #[serde(default)]
+    pub title_keywords: Vec<String>,
+    // Job categories (see `categories` in config.json) this variant is for
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+// Rule matches win; otherwise rotate through the variants based on how many
+// applications have already been assigned one, so the rotation survives restarts.
+pub fn assign_variant<'a>(config: &'a ResumeConfig, history: &History, job_title: &str, category: Option<&str>) -> Option<&'a ResumeVariant> {
+    if config.variants.is_empty() {
+        return None;
+    }
+
+    let title = job_title.to_lowercase();
+    for rule in &config.rules {
+        let matches = rule.title_keywords.iter().any(|k| title.contains(&k.to_lowercase()))
+            || category.is_some_and(|c| rule.categories.iter().any(|rc| rc.eq_ignore_ascii_case(c)));
+        if !matches {
+            continue;
+        }
+        match config.variants.iter().find(|v| v.name == rule.variant) {
+            Some(variant) => return Some(variant),
+            None => println!("Resume rule refers to unknown variant '{}'", rule.variant),
+        }
+    }
+
+    let assigned = history.records.iter().filter(|r| r.resume_variant.is_some()).count();
+    config.variants.get(assigned % config.variants.len())
+}
+
+// Attaches the resume through the file input on the first Easy Apply step
+pub async fn upload_resume(driver: &WebDriver, variant: &ResumeVariant) -> WebDriverResult<()> {
+    let path = std::fs::canonicalize(&variant.path)?;
+    println!("Uploading resume variant '{}' ({})", variant.name, path.display());
+
+    let file_input = driver.find(By::Css("input[type='file']")).await?;
+    file_input.send_keys(path.to_string_lossy()).await?;
+    sleep(Duration::from_secs(3)).await; // Wait for the upload to finish
+    Ok(())
+}
+
+pub fn extract_resume_text(path: &Path) -> WebDriverResult<String> {
+    println!("Extracting text from resume {}...", path.display());
+    pdf_extract::extract_text(path).map_err(|e| {
+        WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("Could not read resume {}: {}", path.display(), e)))
+    })
+}
+
+// Skills listed on a job detail page. Dice has shipped a few different
+// markups for the skills section, so try each of them in turn.
+pub async fn fetch_job_skills(driver: &WebDriver, timeouts: &TimeoutConfig, job: &Job) -> WebDriverResult<Vec<String>> {
+    session::navigate(driver, timeouts, &job.url).await?;
+    sleep(timeouts.page_load()).await; // Wait for the skills section to render
+
+    let script_skills = r#"
+        const selectors = [
+            '[data-cy="skillsList"] span',
+            '[data-testid="skillsList"] span',
+            '#skillsBody span',
+            'div.skills span'
+        ];
+        for (const selector of selectors) {
+            const nodes = document.querySelectorAll(selector);
+            if (nodes.length > 0) {
+                return Array.from(nodes).map(n => n.innerText.trim()).filter(t => t.length > 0);
+            }
+        }
+        return [];
+    "#;
+    let result = driver.execute(script_skills, vec![]).await?;
+    result.convert::<Vec<String>>()
+}
+
+// Listed skills the resume doesn't mention under any name, in their
+// canonical spelling
+fn missing_skills(resume_text: &str, skills: &[String], taxonomy: &SkillTaxonomy) -> Vec<String> {
+    skills
+        .iter()
+        .filter(|skill| !taxonomy.mentioned_in(skill, resume_text))
+        .map(|skill| taxonomy.canonical(skill))
+        .collect()
+}
+
+pub async fn keyword_gap_report(driver: &WebDriver, config: &ResumeConfig, taxonomy: &SkillTaxonomy, timeouts: &TimeoutConfig, jobs: &[Job]) -> WebDriverResult<()> {
+    let path = match &config.path {
+        Some(path) => path,
+        None => {
+            println!("Keyword gap report is enabled but no resume path is configured. Skipping.");
+            return Ok(());
+        }
+    };
+    let resume_text = extract_resume_text(Path::new(path))?.to_lowercase();
+
+    // Keyed by lowercase skill, keeping the first spelling seen for display
+    let mut missing_counts: HashMap<String, (String, usize)> = HashMap::new();
+
+    for job in jobs {
+        let skills = fetch_job_skills(driver, timeouts, job).await?;
+        let missing = missing_skills(&resume_text, &skills, taxonomy);
+        if missing.is_empty() {
+            println!("{}: resume covers all {} listed skills", job.job_title, skills.len());
+            continue;
+        }
+
+        println!("{}: missing {} of {} skills: {}", job.job_title, missing.len(), skills.len(), missing.join(", "));
+        for skill in missing {
+            let entry = missing_counts.entry(skill.to_lowercase()).or_insert((skill, 0));
+            entry.1 += 1;
+        }
+    }
+
+    let mut top_missing: Vec<(String, usize)> = missing_counts.into_values().collect();
+    top_missing.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    println!("Top missing keywords across {} jobs:", jobs.len());
+    for (skill, count) in top_missing.iter().take(15) {
+        println!("  {:<30} {} job(s)", skill, count);
+    }
+
+    Ok(())
+}
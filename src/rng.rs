@@ -0,0 +1,34 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use tokio::time::Duration;
+
+// Single source of randomness for a run. Every random decision (jitter,
+// sampling, ordering) draws from this so a run can be replayed exactly by
+// passing the seed it printed at startup.
+pub struct RunRng {
+    rng: StdRng,
+}
+
+impl RunRng {
+    pub fn new(seed: Option<u64>) -> RunRng {
+        let seed = seed.unwrap_or_else(rand::random);
+        println!("Using RNG seed {} (rerun with --seed {} to reproduce)", seed, seed);
+        RunRng {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // `base` plus up to `max_jitter` extra
+    pub fn jitter(&mut self, base: Duration, max_jitter: Duration) -> Duration {
+        let max_ms = max_jitter.as_millis() as u64;
+        if max_ms == 0 {
+            return base;
+        }
+        base + Duration::from_millis(self.rng.random_range(0..=max_ms))
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        items.shuffle(&mut self.rng);
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::filters::{self, JobFilters};
+use crate::history::History;
+use crate::paths;
+use crate::state_file;
+use crate::{job_id_from_url, Job};
+
+pub const RUNS_DIR: &str = "runs";
+
+// What happened to one scraped job during a run
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobOutcome {
+    pub job_title: String,
+    // "queued", or why the job was dropped
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    // The search that found the job; None for discovery sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+// How one search query did. `found` counts every result, duplicates of
+// jobs another page or query already turned up included; `unique` only
+// the jobs this query found first. Skipped is everything unique that
+// wasn't applied to, whether filtered out or never reached.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+pub struct QueryStats {
+    pub found: usize,
+    pub unique: usize,
+    pub applied: usize,
+    pub skipped: usize,
+}
+
+const OTHER_SOURCES: &str = "(other sources)";
+
+fn query_key(query: Option<&str>) -> &str {
+    query.unwrap_or(OTHER_SOURCES)
+}
+
+// Summary of one search_and_apply run, written to ./runs so runs can be
+// compared with `dice_blast diff-runs`
+#[derive(Serialize, Deserialize)]
+pub struct RunReport {
+    pub started_at: DateTime<Utc>,
+    pub query: String,
+    // Keyed by job ID
+    pub jobs: BTreeMap<String, JobOutcome>,
+    // Wall-clock time per pipeline stage, in the order stages first ran
+    #[serde(default)]
+    pub stages: Vec<StageTiming>,
+    // Keyed by the search's label; filled in by count_found and summarize_queries
+    #[serde(default)]
+    pub queries: BTreeMap<String, QueryStats>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StageTiming {
+    pub stage: String,
+    pub secs: f64,
+}
+
+// Times the pipeline one stage at a time. Starting a stage ends the previous
+// one; a stage that runs more than once accumulates.
+#[derive(Default)]
+pub struct StageTimings {
+    stages: Vec<StageTiming>,
+    current: Option<(String, Instant)>,
+}
+
+impl StageTimings {
+    pub fn start(&mut self, stage: &str) {
+        self.stop();
+        self.current = Some((stage.to_string(), Instant::now()));
+    }
+
+    fn stop(&mut self) {
+        let Some((stage, started)) = self.current.take() else {
+            return;
+        };
+        let secs = started.elapsed().as_secs_f64();
+        match self.stages.iter_mut().find(|timing| timing.stage == stage) {
+            Some(timing) => timing.secs += secs,
+            None => self.stages.push(StageTiming { stage, secs }),
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<StageTiming> {
+        self.stop();
+        self.stages
+    }
+}
+
+pub fn print_stages(stages: &[StageTiming]) {
+    let total: f64 = stages.iter().map(|timing| timing.secs).sum();
+    if total <= 0.0 {
+        return;
+    }
+    println!("Time per stage:");
+    for timing in stages {
+        println!("  {:<10} {:>8.1}s ({:.0}%)", timing.stage, timing.secs, timing.secs / total * 100.0);
+    }
+}
+
+impl RunReport {
+    pub fn new(query: &str, found: &[Job], queued: &[Job], job_filters: &JobFilters, history: &History) -> RunReport {
+        let queued: HashSet<&str> = queued.iter().map(|job| job_id_from_url(&job.url)).collect();
+        let jobs = found
+            .iter()
+            .map(|job| {
+                let id = job_id_from_url(&job.url);
+                let outcome = if queued.contains(id) {
+                    "queued".to_string()
+                } else if history.applied_to(id) {
+                    "already applied".to_string()
+                } else {
+                    // The repost, seniority and sync checks don't leave a reason behind
+                    filters::rejection_reason(job_filters, job).unwrap_or_else(|| "skipped".to_string())
+                };
+                let outcome = JobOutcome {
+                    job_title: job.job_title.clone(),
+                    outcome,
+                    summary: None,
+                    query: job.query.clone(),
+                };
+                (id.to_string(), outcome)
+            })
+            .collect();
+        RunReport {
+            started_at: Utc::now(),
+            query: query.to_string(),
+            jobs,
+            stages: Vec::new(),
+            queries: BTreeMap::new(),
+        }
+    }
+
+    // Counts search results as they're scraped, before duplicates are dropped
+    pub fn count_found(&mut self, query: Option<&str>, found: usize) {
+        self.queries.entry(query_key(query).to_string()).or_default().found += found;
+    }
+
+    pub fn count_jobs_found(&mut self, jobs: &[Job]) {
+        for job in jobs {
+            self.count_found(job.query.as_deref(), 1);
+        }
+    }
+
+    // Marks the jobs turned down on their job page during this run with the
+    // reason, and the ones applied to with their summaries
+    pub fn attach_outcomes(&mut self, history: &History) -> io::Result<()> {
+        for (job_id, reason) in history.skips_since(self.started_at)? {
+            if let Some(job) = self.jobs.get_mut(&job_id) {
+                job.outcome = reason;
+            }
+        }
+        for record in history.records.iter().filter(|r| r.applied_at >= self.started_at) {
+            if let Some(job) = self.jobs.get_mut(&record.job_id) {
+                job.outcome = "applied".to_string();
+                job.summary = record.summary.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn summarize_queries(&mut self) {
+        for stats in self.queries.values_mut() {
+            stats.unique = 0;
+            stats.applied = 0;
+        }
+        for job in self.jobs.values() {
+            let stats = self.queries.entry(query_key(job.query.as_deref()).to_string()).or_default();
+            stats.unique += 1;
+            if job.outcome == "applied" {
+                stats.applied += 1;
+            }
+        }
+        for stats in self.queries.values_mut() {
+            stats.skipped = stats.unique - stats.applied;
+        }
+    }
+
+    pub fn print_queries(&self) {
+        if self.queries.is_empty() {
+            return;
+        }
+        println!("Per query:");
+        println!("  {:<30} {:>6} {:>6} {:>7} {:>7}", "query", "found", "unique", "applied", "skipped");
+        for (query, stats) in &self.queries {
+            println!("  {:<30} {:>6} {:>6} {:>7} {:>7}", query, stats.found, stats.unique, stats.applied, stats.skipped);
+        }
+    }
+
+    // Where the report is saved, as .json or (see html_report) .html
+    pub fn path(&self, extension: &str) -> PathBuf {
+        paths::state(RUNS_DIR).join(format!("run-{}.{}", self.started_at.format("%Y%m%d-%H%M%S"), extension))
+    }
+
+    pub fn save(&self) -> io::Result<PathBuf> {
+        fs::create_dir_all(paths::state(RUNS_DIR))?;
+        let path = self.path("json");
+        state_file::write_json_atomic(&path, self)?;
+        Ok(path)
+    }
+
+    // Accepts a path, or a report name inside the runs directory with or without ".json"
+    pub fn load(name: &str) -> io::Result<RunReport> {
+        let candidates = [
+            PathBuf::from(name),
+            paths::state(RUNS_DIR).join(name),
+            paths::state(RUNS_DIR).join(format!("{}.json", name)),
+        ];
+        let path = candidates
+            .iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("No run report named {}", name)))?;
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+}
+
+// `dice_blast diff-runs <run_a> <run_b>`
+pub fn diff_runs(a_name: &str, b_name: &str) -> io::Result<()> {
+    let a = RunReport::load(a_name)?;
+    let b = RunReport::load(b_name)?;
+    println!("A: {} ({}), {} jobs", a_name, a.query, a.jobs.len());
+    println!("B: {} ({}), {} jobs", b_name, b.query, b.jobs.len());
+
+    let new: Vec<_> = b.jobs.iter().filter(|(id, _)| !a.jobs.contains_key(*id)).collect();
+    let gone: Vec<_> = a.jobs.iter().filter(|(id, _)| !b.jobs.contains_key(*id)).collect();
+    let overlap = b.jobs.len() - new.len();
+
+    println!("\nOverlap: {} jobs found by both runs", overlap);
+    println!("\nNew in B ({}):", new.len());
+    for (id, job) in &new {
+        println!("  {} {} [{}]", id, job.job_title, job.outcome);
+    }
+    println!("\nOnly in A ({}):", gone.len());
+    for (id, job) in &gone {
+        println!("  {} {} [{}]", id, job.job_title, job.outcome);
+    }
+
+    println!("\nOutcome changes:");
+    let mut changes = 0;
+    for (id, before) in &a.jobs {
+        if let Some(after) = b.jobs.get(id) {
+            if before.outcome != after.outcome {
+                println!("  {} {}: {} -> {}", id, after.job_title, before.outcome, after.outcome);
+                changes += 1;
+            }
+        }
+    }
+    if changes == 0 {
+        println!("  none");
+    }
+
+    println!("\nStage times (A -> B):");
+    for timing in &b.stages {
+        let before = a.stages.iter().find(|t| t.stage == timing.stage).map_or(0.0, |t| t.secs);
+        println!("  {:<10} {:>8.1}s -> {:>8.1}s", timing.stage, before, timing.secs);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(outcome: &str, query: Option<&str>) -> JobOutcome {
+        JobOutcome {
+            job_title: "Rust Engineer".to_string(),
+            outcome: outcome.to_string(),
+            summary: None,
+            query: query.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn queries_count_duplicates_only_as_found() {
+        let mut report = RunReport {
+            started_at: Utc::now(),
+            query: "rust".to_string(),
+            jobs: BTreeMap::new(),
+            stages: Vec::new(),
+            queries: BTreeMap::new(),
+        };
+        report.count_found(Some("rust"), 3);
+        report.count_found(Some("golang"), 2);
+        report.jobs.insert("a".to_string(), outcome("applied", Some("rust")));
+        report.jobs.insert("b".to_string(), outcome("queued", Some("rust")));
+        report.jobs.insert("c".to_string(), outcome("skipped", Some("rust")));
+        report.jobs.insert("d".to_string(), outcome("applied", None));
+        report.summarize_queries();
+
+        let stats = |found, unique, applied, skipped| QueryStats { found, unique, applied, skipped };
+        // Both golang results had already turned up under rust
+        assert_eq!(report.queries["golang"], stats(2, 0, 0, 0));
+        assert_eq!(report.queries["rust"], stats(3, 3, 1, 2));
+        assert_eq!(report.queries[OTHER_SOURCES], stats(0, 1, 1, 0));
+    }
+
+    #[test]
+    fn job_page_skips_keep_their_reason() {
+        let dir = std::env::temp_dir().join(format!("dice_blast-report-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut history = History::load(&dir.join(crate::history::HISTORY_FILE)).unwrap();
+        let job = |id: &str| crate::discovery::discovered_job(id, "Rust Engineer".to_string(), crate::discovery::JobSource::Search);
+        let jobs = [job("a"), job("b")];
+        let mut report = RunReport::new("rust", &jobs, &jobs, &JobFilters::default(), &history);
+        history.record_attempt(&jobs[0], Utc::now(), "skipped", Some("workplace is On-Site, searching for Remote".to_string())).unwrap();
+        report.attach_outcomes(&history).unwrap();
+        report.summarize_queries();
+        assert_eq!(report.jobs["a"].outcome, "workplace is On-Site, searching for Remote");
+        assert_eq!(report.jobs["b"].outcome, "queued");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use regex::Regex;
+use thirtyfour::prelude::*;
+
+use crate::filters::JobFilters;
+use crate::Job;
+
+// Working hours/days/weeks in a year, for comparing pay quoted per period
+const HOURS_PER_YEAR: f64 = 2080.0;
+const DAYS_PER_YEAR: f64 = 260.0;
+const WEEKS_PER_YEAR: f64 = 52.0;
+const MONTHS_PER_YEAR: f64 = 12.0;
+
+// Below this, an amount with no period given is assumed to be hourly
+const HOURLY_GUESS_CEILING: f64 = 500.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PayPeriod {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Annual,
+}
+
+impl PayPeriod {
+    fn per_year(self) -> f64 {
+        match self {
+            PayPeriod::Hourly => HOURS_PER_YEAR,
+            PayPeriod::Daily => DAYS_PER_YEAR,
+            PayPeriod::Weekly => WEEKS_PER_YEAR,
+            PayPeriod::Monthly => MONTHS_PER_YEAR,
+            PayPeriod::Annual => 1.0,
+        }
+    }
+}
+
+// Pay as posted; a single figure has min == max
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Salary {
+    pub min: f64,
+    pub max: f64,
+    pub period: PayPeriod,
+}
+
+impl Salary {
+    pub fn annual_max(&self) -> f64 {
+        self.max * self.period.per_year()
+    }
+
+    pub fn annual_midpoint(&self) -> f64 {
+        (self.min + self.max) / 2.0 * self.period.per_year()
+    }
+}
+
+fn period_from_text(text: &str) -> Option<PayPeriod> {
+    let words: [(&[&str], PayPeriod); 5] = [
+        (&["hour", "/hr", " hr", "hrly"], PayPeriod::Hourly),
+        (&["per day", "/day", "daily"], PayPeriod::Daily),
+        (&["week", "/wk"], PayPeriod::Weekly),
+        (&["month", "/mo"], PayPeriod::Monthly),
+        (&["year", "annual", "/yr", " yr", "per annum"], PayPeriod::Annual),
+    ];
+    words
+        .iter()
+        .find(|(markers, _)| markers.iter().any(|marker| text.contains(marker)))
+        .map(|(_, period)| *period)
+}
+
+// Reads the compensation text Dice shows on cards and job pages, e.g.
+// "$120,000 - $150,000", "USD 60.00 - 70.00 per hour", "$55/hr", "120-140k".
+// None for text without figures, like "Depends on experience".
+pub fn parse(text: &str) -> Option<Salary> {
+    let text = text.to_lowercase();
+    let amount = Regex::new(r"(\d[\d,]*(?:\.\d+)?)\s*(k\b)?").unwrap();
+    let amounts: Vec<(f64, bool)> = amount
+        .captures_iter(&text)
+        .filter_map(|captures| {
+            let value: f64 = captures[1].replace(',', "").parse().ok()?;
+            Some((value, captures.get(2).is_some()))
+        })
+        .take(2)
+        .collect();
+    let (first, first_k) = *amounts.first()?;
+    let (second, second_k) = amounts.get(1).copied().unwrap_or((first, first_k));
+    // "120-140k" puts the k on the second figure only
+    let thousands = |value: f64, k: bool| if k || ((first_k || second_k) && value < 1000.0) { value * 1000.0 } else { value };
+    let (low, high) = (thousands(first, first_k), thousands(second, second_k));
+    if low <= 0.0 {
+        return None;
+    }
+
+    let period = period_from_text(&text).unwrap_or(if high < HOURLY_GUESS_CEILING { PayPeriod::Hourly } else { PayPeriod::Annual });
+    Some(Salary { min: low.min(high), max: low.max(high), period })
+}
+
+// Jobs whose pay isn't posted, or can't be read, are kept
+pub fn below_minimum(filters: &JobFilters, salary_text: Option<&str>) -> Option<String> {
+    let minimum = filters.min_salary?;
+    let salary = parse(salary_text?)?;
+    if salary.annual_max() < minimum as f64 {
+        Some(format!("pays about {:.0} a year, under min_salary ({})", salary.annual_max(), minimum))
+    } else {
+        None
+    }
+}
+
+const COMPENSATION_SCRIPT: &str = r#"
+    const el = document.querySelector('[data-cy="compensationText"], [data-testid="compensationText"], [data-testid*="compensation"]');
+    return el ? el.innerText : null;
+"#;
+
+// Cards often leave pay out, so jobs that got through the card check are
+// checked again against the job page: its JobPosting pay if that reads,
+// otherwise the compensation text shown on the page
+pub async fn job_page_below_minimum(driver: &WebDriver, filters: &JobFilters, job: &Job, posted: Option<&str>) -> WebDriverResult<Option<String>> {
+    if filters.min_salary.is_none() || job.salary.as_deref().and_then(parse).is_some() {
+        return Ok(None);
+    }
+    if let Some(posted) = posted.filter(|text| parse(text).is_some()) {
+        return Ok(below_minimum(filters, Some(posted)));
+    }
+    let text: Option<String> = driver.execute(COMPENSATION_SCRIPT, vec![]).await?.convert()?;
+    Ok(below_minimum(filters, text.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn salary(min: f64, max: f64, period: PayPeriod) -> Option<Salary> {
+        Some(Salary { min, max, period })
+    }
+
+    #[test]
+    fn annual_range() {
+        assert_eq!(parse("$120,000 - $150,000"), salary(120_000.0, 150_000.0, PayPeriod::Annual));
+    }
+
+    #[test]
+    fn hourly_range_with_period() {
+        assert_eq!(parse("USD 60.00 - 70.00 per hour"), salary(60.0, 70.0, PayPeriod::Hourly));
+        assert_eq!(parse("$55/hr"), salary(55.0, 55.0, PayPeriod::Hourly));
+    }
+
+    #[test]
+    fn thousands_suffix() {
+        assert_eq!(parse("120-140k"), salary(120_000.0, 140_000.0, PayPeriod::Annual));
+        assert_eq!(parse("$90K - $110K per year"), salary(90_000.0, 110_000.0, PayPeriod::Annual));
+    }
+
+    #[test]
+    fn small_amount_without_period_is_hourly() {
+        assert_eq!(parse("$65"), salary(65.0, 65.0, PayPeriod::Hourly));
+    }
+
+    #[test]
+    fn no_figures() {
+        assert_eq!(parse("Depends on Experience"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn minimum_compares_annualized_top_of_range() {
+        let filters = JobFilters { min_salary: Some(100_000), ..Default::default() };
+        assert!(below_minimum(&filters, Some("$40 - $45/hr")).is_some());
+        assert!(below_minimum(&filters, Some("$50 - $60/hr")).is_none());
+        assert!(below_minimum(&filters, Some("$80,000 - $105,000")).is_none());
+        assert!(below_minimum(&filters, Some("DOE")).is_none());
+        assert!(below_minimum(&filters, None).is_none());
+    }
+}
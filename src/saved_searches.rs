@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use serde::Deserialize;
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+use url::Url;
+
+use crate::paths;
+use crate::state_file;
+use crate::timeouts::TimeoutConfig;
+use crate::search_filters::{FilterValue, PostedDate, RadiusUnit, SortOrder};
+use crate::SearchQuery;
+
+const SAVED_SEARCHES_URL: &str = "https://www.dice.com/dashboard/saved-searches";
+pub const IMPORT_FILE: &str = "imported_searches.json";
+
+#[derive(Deserialize)]
+struct SavedSearchLink {
+    name: String,
+    href: String,
+}
+
+// Builds a SearchQuery from a saved search's results link, using the current
+// config for anything the link doesn't specify.
+fn search_query_from_link(href: &str, defaults: &SearchQuery) -> Option<SearchQuery> {
+    let url = Url::parse(href).or_else(|_| Url::parse("https://www.dice.com").and_then(|base| base.join(href))).ok()?;
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let q = params.get("q")?.clone();
+
+    let param = |key: &str, fallback: &str| params.get(key).cloned().unwrap_or_else(|| fallback.to_string());
+    Some(SearchQuery {
+        q,
+        location: param("location", ""),
+        radius: params.get("radius").and_then(|v| v.parse().ok()),
+        radius_unit: params.get("radiusUnit").and_then(|v| RadiusUnit::parse(v)),
+        locations: Vec::new(),
+        country_code: param("countryCode", &defaults.country_code),
+        filters_employment_type: params
+            .get("filters.employmentType")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| defaults.filters_employment_type.clone()),
+        filters_employer_type: params
+            .get("filters.employerType")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| defaults.filters_employer_type.clone()),
+        filters_easy_apply: params
+            .get("filters.easyApply")
+            .map_or(defaults.filters_easy_apply, |v| v == "true"),
+        posted_date: params
+            .get("filters.postedDate")
+            .and_then(|v| PostedDate::parse(v))
+            .or(defaults.posted_date),
+        workplace_types: params
+            .get("filters.workplaceTypes")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| defaults.workplace_types.clone()),
+        language: param("language", &defaults.language),
+        max_applications: None,
+        page_size: params.get("pageSize").and_then(|v| v.parse().ok()).or(defaults.page_size),
+        sort: params.get("sort").and_then(|v| SortOrder::parse(v)).or(defaults.sort),
+        label: String::new(),
+    })
+}
+
+fn confirm(prompt: &str) -> bool {
+    print!("{} [y/N] ", prompt);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    let _ = io::stdin().read_line(&mut answer);
+    answer.trim().eq_ignore_ascii_case("y")
+}
+
+// Scrapes the saved searches from the user's Dice dashboard and writes the
+// ones they confirm to imported_searches.json.
+pub async fn import_saved_searches(driver: &WebDriver, timeouts: &TimeoutConfig, defaults: &SearchQuery) -> WebDriverResult<()> {
+    println!("Loading saved searches from {}...", SAVED_SEARCHES_URL);
+    driver.get(SAVED_SEARCHES_URL).await?;
+    sleep(timeouts.page_load()).await;
+
+    let script_saved_searches = r#"
+        return Array.from(document.querySelectorAll('a[href*="/jobs?"]')).map(a => ({
+            name: a.innerText.trim(),
+            href: a.getAttribute('href')
+        }));
+    "#;
+    let links: Vec<SavedSearchLink> = driver.execute(script_saved_searches, vec![]).await?.convert()?;
+    println!("Found {} saved searches", links.len());
+
+    let mut imported = Vec::new();
+    for link in links {
+        let Some(query) = search_query_from_link(&link.href, defaults) else {
+            println!("Skipping saved search '{}': no search terms in {}", link.name, link.href);
+            continue;
+        };
+        if confirm(&format!("Import saved search '{}' ({})?", link.name, query)) {
+            imported.push(query);
+        }
+    }
+
+    if imported.is_empty() {
+        println!("No saved searches imported.");
+        return Ok(());
+    }
+
+    let path = paths::state(IMPORT_FILE);
+    state_file::write_json_atomic(&path, &imported)?;
+    println!("Wrote {} searches to {}", imported.len(), path.display());
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, Result};
+use std::path::Path;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{from_reader, json, Value};
+
+use crate::state_file::{self, StateLock};
+
+// Upgrades a document from version N to N + 1. Migrations are listed in
+// order, so `migrations[0]` takes version 1 to version 2.
+pub type Migration = fn(Value) -> Value;
+
+// State files are stored as `{"version": N, "records": [...]}`. Files written
+// before versioning existed are bare arrays and count as version 1. None
+// for a version that isn't a whole number from 1 up.
+fn document_version(document: &Value) -> Option<u32> {
+    if document.is_array() {
+        return Some(1);
+    }
+    match &document["version"] {
+        Value::Null => Some(1),
+        version => version.as_u64().and_then(|v| u32::try_from(v).ok()).filter(|&v| v >= 1),
+    }
+}
+
+pub fn current_version(migrations: &[Migration]) -> u32 {
+    migrations.len() as u32 + 1
+}
+
+// Wraps a legacy bare array in the versioned envelope
+pub fn wrap_legacy_array(document: Value) -> Value {
+    if document.is_array() {
+        json!({ "version": 2, "records": document })
+    } else {
+        document
+    }
+}
+
+// Reads a versioned state file, running any pending migrations. The original
+// file is copied to `<file>.v<N>.bak` before the upgraded version replaces it.
+// Returns None when the file doesn't exist.
+pub fn read_versioned<T: DeserializeOwned>(path: &Path, migrations: &[Migration]) -> Result<Option<T>> {
+    read(path, migrations, None)
+}
+
+// read_versioned for a caller that will write the file back under `lock`
+pub fn read_versioned_locked<T: DeserializeOwned>(lock: &StateLock, migrations: &[Migration]) -> Result<Option<T>> {
+    read(lock.path(), migrations, Some(lock))
+}
+
+fn read<T: DeserializeOwned>(path: &Path, migrations: &[Migration], lock: Option<&StateLock>) -> Result<Option<T>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut document: Value = from_reader(BufReader::new(file))?;
+
+    let Some(found) = document_version(&document) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has an invalid schema version {}", path.display(), document["version"]),
+        ));
+    };
+    let current = current_version(migrations);
+    if found > current {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is schema version {}, but this build only understands up to {}. Upgrade dice_blast.", path.display(), found, current),
+        ));
+    }
+
+    if found < current {
+        // Migrating writes the file, so it's read again under the lock in
+        // case another process migrated it first
+        let Some(lock) = lock else {
+            return read(path, migrations, Some(&state_file::lock(path)?));
+        };
+        let backup = path.with_extension(format!("json.v{}.bak", found));
+        fs::copy(path, &backup)?;
+        for migration in &migrations[(found - 1) as usize..] {
+            document = migration(document);
+        }
+        document["version"] = json!(current);
+        println!("Migrated {} from schema version {} to {} (backup at {})", path.display(), found, current, backup.display());
+        lock.write_json(&document)?;
+    }
+
+    let records = serde_json::from_value(document["records"].take())?;
+    Ok(Some(records))
+}
+
+pub fn write_versioned_locked<T: Serialize>(lock: &StateLock, migrations: &[Migration], records: &T) -> Result<()> {
+    let document = json!({
+        "version": current_version(migrations),
+        "records": records,
+    });
+    lock.write_json(&document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upgrade(document: Value) -> Value {
+        let document = wrap_legacy_array(document);
+        json!({ "version": document["version"], "records": [document["records"][0], "added"] })
+    }
+
+    fn read_file(contents: &str) -> Result<Option<Vec<String>>> {
+        let dir = std::env::temp_dir().join(format!("dice_blast-schema-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("state.json");
+        fs::write(&path, contents)?;
+        read_versioned(&path, &[upgrade])
+    }
+
+    #[test]
+    fn migrates_older_versions() {
+        assert_eq!(read_file(r#"{"version": 1, "records": ["a"]}"#).unwrap().unwrap(), ["a", "added"]);
+        assert_eq!(read_file(r#"["a"]"#).unwrap().unwrap(), ["a", "added"]);
+        assert_eq!(read_file(r#"{"version": 2, "records": ["a"]}"#).unwrap().unwrap(), ["a"]);
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        for contents in [
+            r#"{"version": 0, "records": []}"#,
+            r#"{"version": -1, "records": []}"#,
+            r#"{"version": "2", "records": []}"#,
+            r#"{"version": 4294967297, "records": []}"#,
+            r#"{"version": 3, "records": []}"#,
+        ] {
+            let err = read_file(contents).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", contents);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thirtyfour::prelude::*;
+
+use crate::descriptions::PageFetcher;
+use crate::rng::RunRng;
+use crate::{discovery, events, job_posting, prelude, scrape_search_pages, Config, Job};
+
+// Research mode: walk max_pages result pages per search and write what the
+// cards say to a dataset. Never opens a job page in the browser and never applies.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrapeOnlyConfig {
+    // Fetch each job page over plain HTTP and attach its JobPosting metadata
+    // and the employer's own careers link
+    pub enrich: bool,
+    pub output: String,
+}
+
+impl Default for ScrapeOnlyConfig {
+    fn default() -> Self {
+        ScrapeOnlyConfig {
+            enrich: false,
+            output: "./dataset.jsonl".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DatasetRow<'a> {
+    #[serde(flatten)]
+    job: &'a Job,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    careers_url: Option<String>,
+}
+
+// Link text or URL fragments that mark an employer careers page
+const CAREERS_HINTS: [&str; 4] = ["career", "company website", "company site", "jobs at"];
+
+fn is_dice_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|host| host == "dice.com" || host.ends_with(".dice.com")))
+        .unwrap_or(true)
+}
+
+// The employer's own careers URL: the posting's hiringOrganization link if
+// it leaves Dice, otherwise the first off-site link that looks like one
+fn careers_url(html: &str, posting: Option<&Value>) -> Option<String> {
+    let organization = posting.map(|p| &p["hiringOrganization"]);
+    let declared = organization.and_then(|org| org["sameAs"].as_str().or_else(|| org["url"].as_str()));
+    if let Some(url) = declared.filter(|url| !is_dice_url(url)) {
+        return Some(url.to_string());
+    }
+
+    let link = Regex::new(r#"(?is)<a[^>]*href="(https?://[^"]+)"[^>]*>(.*?)</a>"#).unwrap();
+    let found = link
+        .captures_iter(html)
+        .find(|cap| {
+            let text = format!("{} {}", &cap[1], &cap[2]).to_lowercase();
+            !is_dice_url(&cap[1]) && CAREERS_HINTS.iter().any(|hint| text.contains(hint))
+        })
+        .map(|cap| cap[1].to_string());
+    found
+}
+
+pub async fn run(driver: &WebDriver, config: &Config) -> WebDriverResult<()> {
+    let settings = &config.scrape_only;
+    let mut searches = vec![&config.search];
+    searches.extend(config.searches.iter());
+    let mut rng = RunRng::new(config.seed);
+    prelude::run_prelude(driver, &config.prelude, &mut rng).await?;
+
+    let mut jobs = Vec::new();
+    for search in searches {
+        let remaining = config.max_jobs.map(|max| max.saturating_sub(jobs.len()));
+        if remaining == Some(0) {
+            break;
+        }
+        let found = scrape_search_pages(driver, &config.timeouts, search, 1..=config.max_pages, remaining).await?;
+        println!("Search '{}' returned {} jobs", search.q, found.len());
+        discovery::merge_jobs(&mut jobs, found);
+    }
+    if let Some(max) = config.max_jobs {
+        jobs.truncate(max);
+    }
+    events::jobs_scraped(&jobs);
+
+    let mut fetcher = PageFetcher::new(&config.timeouts, &mut rng);
+    let mut writer = BufWriter::new(File::create(&settings.output)?);
+    for job in &jobs {
+        let html = if settings.enrich {
+            fetcher.html(job).await
+        } else {
+            None
+        };
+        let details = html.as_deref().and_then(job_posting::from_html);
+        let careers_url = html.as_deref().and_then(|html| careers_url(html, details.as_ref()));
+        serde_json::to_writer(&mut writer, &DatasetRow { job, details, careers_url })?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+
+    println!("Wrote {} jobs to {}", jobs.len(), settings.output);
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+// A value Dice accepts for one of its search filters, with the exact string
+// it expects in the URL
+pub trait FilterValue: Copy + PartialEq + 'static {
+    const ALL: &'static [Self];
+    const NAME: &'static str;
+
+    fn as_str(self) -> &'static str;
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|v| v.as_str() == value)
+    }
+
+    fn names() -> Vec<&'static str> {
+        Self::ALL.iter().map(|v| v.as_str()).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmploymentType {
+    Fulltime,
+    Parttime,
+    Contracts,
+    ThirdParty,
+}
+
+impl FilterValue for EmploymentType {
+    const ALL: &'static [Self] = &[EmploymentType::Fulltime, EmploymentType::Parttime, EmploymentType::Contracts, EmploymentType::ThirdParty];
+    const NAME: &'static str = "employment type";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            EmploymentType::Fulltime => "FULLTIME",
+            EmploymentType::Parttime => "PARTTIME",
+            EmploymentType::Contracts => "CONTRACTS",
+            EmploymentType::ThirdParty => "THIRD_PARTY",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmployerType {
+    DirectHire,
+    Recruiter,
+    Other,
+}
+
+impl FilterValue for EmployerType {
+    const ALL: &'static [Self] = &[EmployerType::DirectHire, EmployerType::Recruiter, EmployerType::Other];
+    const NAME: &'static str = "employer type";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            EmployerType::DirectHire => "Direct Hire",
+            EmployerType::Recruiter => "Recruiter",
+            EmployerType::Other => "Other",
+        }
+    }
+}
+
+// Dice's "posted within" filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostedDate {
+    #[serde(rename = "ONE", alias = "1")]
+    OneDay,
+    #[serde(rename = "THREE", alias = "3")]
+    ThreeDays,
+    #[serde(rename = "SEVEN", alias = "7")]
+    SevenDays,
+}
+
+impl FilterValue for PostedDate {
+    const ALL: &'static [Self] = &[PostedDate::OneDay, PostedDate::ThreeDays, PostedDate::SevenDays];
+    const NAME: &'static str = "posted date";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            PostedDate::OneDay => "ONE",
+            PostedDate::ThreeDays => "THREE",
+            PostedDate::SevenDays => "SEVEN",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkplaceType {
+    Remote,
+    Hybrid,
+    OnSite,
+}
+
+impl FilterValue for WorkplaceType {
+    const ALL: &'static [Self] = &[WorkplaceType::Remote, WorkplaceType::Hybrid, WorkplaceType::OnSite];
+    const NAME: &'static str = "workplace type";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            WorkplaceType::Remote => "Remote",
+            WorkplaceType::Hybrid => "Hybrid",
+            WorkplaceType::OnSite => "On-Site",
+        }
+    }
+}
+
+// Result order; Dice sorts by relevance unless told otherwise
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Relevance,
+    Date,
+}
+
+impl FilterValue for SortOrder {
+    const ALL: &'static [Self] = &[SortOrder::Relevance, SortOrder::Date];
+    const NAME: &'static str = "sort order";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "relevance",
+            SortOrder::Date => "date",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RadiusUnit {
+    Mi,
+    Km,
+}
+
+impl FilterValue for RadiusUnit {
+    const ALL: &'static [Self] = &[RadiusUnit::Mi, RadiusUnit::Km];
+    const NAME: &'static str = "radius unit";
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RadiusUnit::Mi => "mi",
+            RadiusUnit::Km => "km",
+        }
+    }
+}
+
+// One or more filter values, written the way Dice takes them: joined with `|`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterList<T>(pub Vec<T>);
+
+impl<T: FilterValue> FilterList<T> {
+    pub fn only(value: T) -> FilterList<T> {
+        FilterList(vec![value])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.0.contains(&value)
+    }
+}
+
+// Derived Default would require T: Default
+impl<T> Default for FilterList<T> {
+    fn default() -> Self {
+        FilterList(Vec::new())
+    }
+}
+
+impl<T: FilterValue> fmt::Display for FilterList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<&str> = self.0.iter().map(|v| v.as_str()).collect();
+        write!(f, "{}", values.join("|"))
+    }
+}
+
+impl<T: FilterValue> FromStr for FilterList<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('|')
+            .map(str::trim)
+            .map(|value| {
+                T::parse(value).ok_or_else(|| format!("unknown {} \"{}\", expected one of {}", T::NAME, value, T::names().join(", ")))
+            })
+            .collect::<Result<Vec<T>, String>>()
+            .map(FilterList)
+    }
+}
+
+impl<T: FilterValue> Serialize for FilterList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, T: FilterValue> Deserialize<'de> for FilterList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(de::Error::custom)
+    }
+}
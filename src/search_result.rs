@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use thirtyfour::prelude::*;
+
+use crate::failures;
+
+// Cards have been rendered as a custom element and as plain divs over time
+const CARD_SELECTOR: &str = "dhi-search-card, [data-cy='search-card'], div.search-card";
+
+// Everything we can read off a search result card without opening the job
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub salary: Option<String>,
+    pub easy_apply: bool,
+    pub posted_age: Option<String>,
+    pub applicants: Option<u32>,
+}
+
+fn job_id_pattern() -> Regex {
+    Regex::new(r"^[a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12}$").unwrap()
+}
+
+fn job_href_pattern() -> Regex {
+    Regex::new(r"(?i)/job-detail/([a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12})(?:[/?#]|$)").unwrap()
+}
+
+// Dice has renamed the anchor `id` before, but the link target has stayed
+// /job-detail/<uuid>, so the href is tried first and the id is the fallback
+struct IdPatterns {
+    href: Regex,
+    id: Regex,
+}
+
+impl IdPatterns {
+    fn new() -> IdPatterns {
+        IdPatterns { href: job_href_pattern(), id: job_id_pattern() }
+    }
+
+    fn job_id(&self, href: Option<&str>, id: Option<&str>) -> Option<String> {
+        let from_href = href
+            .and_then(|href| self.href.captures(href))
+            .map(|captures| captures[1].to_lowercase());
+        from_href.or_else(|| id.filter(|id| self.id.is_match(id)).map(str::to_string))
+    }
+}
+
+// Search cards show an "Easy Apply" badge for jobs that support it. Jobs
+// without it would be skipped on the apply page anyway, so don't enqueue them.
+// If the card itself can't be located we keep the job rather than guess.
+fn card_has_easy_apply(card_text: Option<&str>) -> bool {
+    card_text.map_or(true, |text| text.to_lowercase().contains("easy apply"))
+}
+
+// Parses the "123 applicants" indicator some cards display
+fn parse_applicant_count(card_text: &str) -> Option<u32> {
+    let pattern = Regex::new(r"(?i)(\d[\d,]*)\s+applicants?").unwrap();
+    let captures = pattern.captures(card_text)?;
+    captures[1].replace(',', "").parse().ok()
+}
+
+// "Posted 3 days ago", "Today", "Updated 2 hours ago" and the like
+fn parse_posted_age(card_text: &str) -> Option<String> {
+    let pattern = Regex::new(r"(?i)\b(today|yesterday|\d+\s+(?:minute|hour|day|week|month)s?\s+ago)\b").unwrap();
+    pattern.captures(card_text).map(|c| c[1].to_string())
+}
+
+fn from_card_text(id: String, title: String, card_text: Option<&str>) -> SearchResult {
+    SearchResult {
+        id,
+        title,
+        company: None,
+        location: None,
+        salary: None,
+        easy_apply: card_has_easy_apply(card_text),
+        posted_age: card_text.and_then(parse_posted_age),
+        applicants: card_text.and_then(parse_applicant_count),
+    }
+}
+
+// A link as read by the page scripts below
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawLink {
+    href: Option<String>,
+    id: Option<String>,
+    text: String,
+    // Only set by LINK_SCRIPT: text of the card around the link, if any
+    card_text: Option<String>,
+}
+
+// One card as read by CARD_SCRIPT. Cards the script couldn't read come back
+// with `error` and the card's HTML instead.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawCard {
+    links: Vec<RawLink>,
+    text: Option<String>,
+    company: Option<String>,
+    location: Option<String>,
+    salary: Option<String>,
+    posted: Option<String>,
+    error: Option<String>,
+    html: Option<String>,
+}
+
+// Reads every card on the page in one round trip instead of several
+// WebDriver calls per card
+const CARD_SCRIPT: &str = r#"
+    const child = (card, selector) => {
+        const el = card.querySelector(selector);
+        const text = el ? el.innerText.trim() : '';
+        return text.length > 0 ? text : null;
+    };
+    return Array.from(document.querySelectorAll(arguments[0])).map((card) => {
+        try {
+            return {
+                links: Array.from(card.querySelectorAll('a')).map((a) => ({
+                    href: a.getAttribute('href'),
+                    id: a.getAttribute('id'),
+                    text: a.innerText
+                })),
+                text: card.innerText,
+                company: child(card, "[data-cy='search-result-company-name']"),
+                location: child(card, "[data-cy='search-result-location']"),
+                salary: child(card, "[data-cy='compensationText']"),
+                posted: child(card, "[data-cy='card-posted-date']")
+            };
+        } catch (e) {
+            return { error: String(e), html: card.outerHTML };
+        }
+    });
+"#;
+
+// Every link on the page, with the text of the search card around it when
+// there is one
+const LINK_SCRIPT: &str = r#"
+    return Array.from(document.querySelectorAll('a')).map((a) => {
+        const card = a.closest("dhi-search-card, [data-cy='search-card'], [class*='search-card']");
+        return {
+            href: a.getAttribute('href'),
+            id: a.getAttribute('id'),
+            text: a.innerText,
+            card_text: card ? card.innerText : null
+        };
+    });
+"#;
+
+// Ok(None) for cards without a job link (ads, promos)
+fn card_from_raw(card: RawCard, patterns: &IdPatterns) -> Result<Option<SearchResult>, String> {
+    if let Some(error) = card.error {
+        return Err(error);
+    }
+    let link = card
+        .links
+        .into_iter()
+        .find_map(|link| patterns.job_id(link.href.as_deref(), link.id.as_deref()).map(|id| (id, link.text.trim().to_string())));
+    let Some((id, title)) = link else {
+        return Ok(None);
+    };
+
+    let mut result = from_card_text(id, title, card.text.as_deref());
+    result.company = card.company;
+    result.location = card.location;
+    result.salary = card.salary;
+    if card.posted.is_some() {
+        result.posted_age = card.posted;
+    }
+    Ok(Some(result))
+}
+
+// Cards that parsed, and how many threw while parsing
+pub struct ParsedPage {
+    pub results: Vec<SearchResult>,
+    pub parse_errors: usize,
+}
+
+// Parses every result card on the current search page in one pass. A card
+// that fails is logged, its HTML saved under failures/, and skipped; the
+// rest of the page still counts.
+pub async fn parse_search_results(driver: &WebDriver, page_number: usize) -> WebDriverResult<ParsedPage> {
+    let patterns = IdPatterns::new();
+    let cards: Vec<RawCard> = driver.execute(CARD_SCRIPT, vec![json!(CARD_SELECTOR)]).await?.convert()?;
+    let total = cards.len();
+
+    let mut results = Vec::new();
+    let mut seen_ids = HashSet::new();
+    let mut parse_errors = 0;
+    for (index, card) in cards.into_iter().enumerate() {
+        let html = card.html.clone().unwrap_or_default();
+        match card_from_raw(card, &patterns) {
+            Ok(Some(result)) => {
+                if seen_ids.insert(result.id.clone()) {
+                    results.push(result);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                parse_errors += 1;
+                println!("Could not parse search card {} on page {}: {}", index + 1, page_number, e);
+                failures::capture_card_html(page_number, index + 1, &html, &e);
+            }
+        }
+    }
+    if total > 0 {
+        println!("Page {}: parsed {} of {} cards ({} parse errors)", page_number, results.len(), total, parse_errors);
+    }
+    Ok(ParsedPage { results, parse_errors })
+}
+
+// Fallback for when no card markup is recognised: scan every link on the
+// page for job IDs, the way the scraper originally worked.
+pub async fn scan_job_links(driver: &WebDriver) -> WebDriverResult<Vec<SearchResult>> {
+    let links: Vec<RawLink> = driver.execute(LINK_SCRIPT, vec![]).await?.convert()?;
+    println!("Scanning {} links for job IDs", links.len());
+
+    let patterns = IdPatterns::new();
+    let mut results = Vec::new();
+    let mut seen_ids = HashSet::new();
+    for link in links {
+        let Some(id) = patterns.job_id(link.href.as_deref(), link.id.as_deref()) else {
+            continue;
+        };
+        if seen_ids.insert(id.clone()) {
+            results.push(from_card_text(id, link.text.trim().to_string(), link.card_text.as_deref()));
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Attributes of the first anchor in a fixture card
+    fn anchor_attributes(html: &str) -> (Option<String>, Option<String>) {
+        let anchor = Regex::new(r"<a\b[^>]*>").unwrap().find(html).unwrap().as_str();
+        let attribute = |name: &str| {
+            Regex::new(&format!(r#"\s{}="([^"]*)""#, name))
+                .unwrap()
+                .captures(anchor)
+                .map(|c| c[1].to_string())
+        };
+        (attribute("href"), attribute("id"))
+    }
+
+    fn fixture_job_id(html: &str) -> Option<String> {
+        let (href, id) = anchor_attributes(html);
+        IdPatterns::new().job_id(href.as_deref(), id.as_deref())
+    }
+
+    #[test]
+    fn reads_id_from_href() {
+        let html = include_str!("../tests/fixtures/search_card_href.html");
+        assert_eq!(fixture_job_id(html).as_deref(), Some("f0767d15-68a2-4c23-95c6-5685dedf2d2d"));
+    }
+
+    #[test]
+    fn prefers_href_over_a_renamed_id_attribute() {
+        let html = include_str!("../tests/fixtures/search_card_renamed_id.html");
+        assert_eq!(fixture_job_id(html).as_deref(), Some("3b1e7c2a-9d4f-4e8b-a6c5-0f2d9e8b7a61"));
+    }
+
+    #[test]
+    fn falls_back_to_id_attribute() {
+        let html = include_str!("../tests/fixtures/search_card_id_only.html");
+        assert_eq!(fixture_job_id(html).as_deref(), Some("8c4d2e1f-5a6b-4c7d-9e8f-1a2b3c4d5e6f"));
+    }
+
+    #[test]
+    fn ignores_links_that_are_not_job_details() {
+        let patterns = IdPatterns::new();
+        assert_eq!(patterns.job_id(Some("/company-profile/f0767d15-68a2-4c23-95c6-5685dedf2d2d"), Some("companyLink")), None);
+        assert_eq!(patterns.job_id(Some("/job-detail/not-a-uuid"), None), None);
+        assert_eq!(patterns.job_id(None, None), None);
+    }
+
+    #[test]
+    fn builds_result_from_script_output() {
+        let card: RawCard = serde_json::from_value(json!({
+            "links": [
+                { "href": "/company-profile/acme", "id": null, "text": "Acme Corp" },
+                { "href": "/job-detail/f0767d15-68a2-4c23-95c6-5685dedf2d2d", "id": "title-0", "text": "Senior DevOps Engineer" }
+            ],
+            "text": "Senior DevOps Engineer\nAcme Corp\nEasy Apply\n42 applicants",
+            "company": "Acme Corp",
+            "location": "Austin, TX",
+            "salary": null,
+            "posted": "Posted 3 days ago"
+        }))
+        .unwrap();
+        let result = card_from_raw(card, &IdPatterns::new()).unwrap().unwrap();
+        assert_eq!(result.id, "f0767d15-68a2-4c23-95c6-5685dedf2d2d");
+        assert_eq!(result.title, "Senior DevOps Engineer");
+        assert_eq!(result.company.as_deref(), Some("Acme Corp"));
+        assert_eq!(result.posted_age.as_deref(), Some("Posted 3 days ago"));
+        assert_eq!(result.applicants, Some(42));
+        assert!(result.easy_apply);
+    }
+
+    #[test]
+    fn reports_cards_the_script_could_not_read() {
+        let card: RawCard = serde_json::from_value(json!({ "error": "TypeError: x is null", "html": "<div></div>" })).unwrap();
+        assert_eq!(card_from_raw(card, &IdPatterns::new()).err().as_deref(), Some("TypeError: x is null"));
+    }
+
+    #[test]
+    fn normalizes_absolute_and_uppercase_hrefs() {
+        let patterns = IdPatterns::new();
+        let href = "https://www.dice.com/job-detail/F0767D15-68A2-4C23-95C6-5685DEDF2D2D?searchlink=search%2F&searchId=1";
+        assert_eq!(patterns.job_id(Some(href), None).as_deref(), Some("f0767d15-68a2-4c23-95c6-5685dedf2d2d"));
+    }
+}
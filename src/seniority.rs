@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Job;
+
+// Ordered from least to most senior so the distance between two levels is
+// just the difference of their positions
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Seniority {
+    Intern,
+    Junior,
+    Mid,
+    Senior,
+    Lead,
+    Principal,
+}
+
+// Checked most senior first, so "Senior Staff Engineer" lands on Lead
+// rather than Senior
+const TITLE_KEYWORDS: &[(Seniority, &[&str])] = &[
+    (Seniority::Principal, &["principal", "distinguished", "director", "vp", "head of"]),
+    (Seniority::Lead, &["lead", "staff", "manager", "architect"]),
+    (Seniority::Senior, &["senior", "sr", "iii", "iv"]),
+    (Seniority::Mid, &["mid", "ii", "intermediate"]),
+    (Seniority::Junior, &["junior", "jr", "entry", "associate", "graduate"]),
+    (Seniority::Intern, &["intern", "internship", "co-op"]),
+];
+
+// Titles with no recognisable level are left alone
+pub fn from_title(title: &str) -> Option<Seniority> {
+    let lower = title.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let padded = format!(" {} ", words.join(" "));
+    TITLE_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| padded.contains(&format!(" {} ", k))))
+        .map(|(level, _)| *level)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardAction {
+    #[default]
+    Warn,
+    Skip,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeniorityGuard {
+    // How many levels apart a title may be from the profile before the guard fires
+    pub max_gap: u8,
+    pub action: GuardAction,
+}
+
+impl Default for SeniorityGuard {
+    fn default() -> Self {
+        SeniorityGuard {
+            max_gap: 1,
+            action: GuardAction::Warn,
+        }
+    }
+}
+
+// The title's level and how many levels it is from the profile, when the
+// title has a recognisable level
+pub fn level_gap(profile: Seniority, title: &str) -> Option<(Seniority, u32)> {
+    let level = from_title(title)?;
+    Some((level, (level as i32 - profile as i32).unsigned_abs()))
+}
+
+// What the guard does with a title and why, when the title is too far from
+// the profile
+pub fn guard_reason(guard: &SeniorityGuard, profile: Seniority, title: &str) -> Option<(GuardAction, String)> {
+    let (level, gap) = level_gap(profile, title)?;
+    (gap > guard.max_gap as u32).then(|| (guard.action, format!("{:?} role is {} levels from your {:?} profile", level, gap, profile)))
+}
+
+// Does nothing unless the profile declares a seniority
+pub fn apply_seniority_guard(guard: &SeniorityGuard, profile: Option<Seniority>, jobs: Vec<Job>) -> Vec<Job> {
+    let Some(profile) = profile else {
+        return jobs;
+    };
+    jobs.into_iter()
+        .filter(|job| match guard_reason(guard, profile, &job.job_title) {
+            None => true,
+            Some((GuardAction::Warn, reason)) => {
+                println!("Warning: {}: {}", job.job_title, reason);
+                true
+            }
+            Some((GuardAction::Skip, reason)) => {
+                println!("Skipping {}: {}", job.job_title, reason);
+                false
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::BufReader;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+use url::Url;
+
+use crate::paths;
+use crate::state_file;
+use crate::timeouts::TimeoutConfig;
+use crate::{login, save_cookies, wait_for_element};
+
+pub const FINGERPRINT_FILE: &str = "session_fingerprint.json";
+
+// Browser identity at the time cookies.json was captured. Dice invalidates
+// sessions whose cookies show up with a different user agent or locale, so
+// later sessions present the same ones.
+#[derive(Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub user_agent: String,
+    pub locale: String,
+    pub captured_at: DateTime<Utc>,
+}
+
+pub async fn record_fingerprint(driver: &WebDriver) -> WebDriverResult<()> {
+    let navigator = driver
+        .execute("return { user_agent: navigator.userAgent, locale: navigator.language };", vec![])
+        .await?;
+    let navigator = navigator.json();
+    let fingerprint = Fingerprint {
+        user_agent: navigator["user_agent"].as_str().unwrap_or_default().to_string(),
+        locale: navigator["locale"].as_str().unwrap_or_default().to_string(),
+        captured_at: Utc::now(),
+    };
+    state_file::write_json_atomic(&paths::state(FINGERPRINT_FILE), &fingerprint)?;
+    Ok(())
+}
+
+pub fn load_fingerprint() -> Option<Fingerprint> {
+    let file = File::open(paths::state(FINGERPRINT_FILE)).ok()?;
+    serde_json::from_reader(BufReader::new(file)).ok()
+}
+
+// Starts Chrome with the user agent and locale the cookies were captured with
+pub fn apply_fingerprint(caps: &mut ChromeCapabilities) -> WebDriverResult<()> {
+    let Some(fingerprint) = load_fingerprint() else {
+        return Ok(());
+    };
+    println!("Reusing the browser identity from {} ({})", fingerprint.captured_at.format("%Y-%m-%d"), fingerprint.locale);
+    if !fingerprint.user_agent.is_empty() {
+        caps.add_arg(&format!("--user-agent={}", fingerprint.user_agent))?;
+    }
+    if !fingerprint.locale.is_empty() {
+        caps.add_arg(&format!("--lang={}", fingerprint.locale))?;
+        caps.add_experimental_option("prefs", json!({ "intl.accept_languages": fingerprint.locale }))?;
+    }
+    Ok(())
+}
+
+// Where Dice has sent expired sessions: its own login pages, and the
+// separate sign-in host it has used for single sign-on
+const LOGIN_PATHS: [&str; 4] = ["/login", "/signin", "/sign-in", "/auth/"];
+const LOGIN_HOST_PREFIXES: [&str; 3] = ["login.", "auth.", "sso."];
+
+fn is_login_url(url: &Url) -> bool {
+    let path = url.path().to_lowercase();
+    let host = url.host_str().unwrap_or_default();
+    LOGIN_PATHS.iter().any(|login| path.contains(login)) || LOGIN_HOST_PREFIXES.iter().any(|prefix| host.starts_with(prefix))
+}
+
+// Dice sends expired sessions to its login page
+pub async fn is_login_page(driver: &WebDriver) -> WebDriverResult<bool> {
+    let url = driver.current_url().await?;
+    Ok(is_login_url(&url))
+}
+
+// The page and scroll offset being scraped, so a re-login can put the
+// browser back where it was instead of starting the query over
+pub struct BrowserPosition {
+    pub url: String,
+    pub scroll_y: f64,
+}
+
+impl BrowserPosition {
+    pub async fn capture(driver: &WebDriver) -> WebDriverResult<BrowserPosition> {
+        let url = driver.current_url().await?.to_string();
+        let scroll_y = driver.execute("return window.scrollY;", vec![]).await?.json().as_f64().unwrap_or(0.0);
+        Ok(BrowserPosition { url, scroll_y })
+    }
+
+    pub async fn restore(&self, driver: &WebDriver, timeouts: &TimeoutConfig) -> WebDriverResult<()> {
+        println!("Returning to {} (scrolled to {}px)", self.url, self.scroll_y);
+        driver.get(&self.url).await?;
+        wait_for_element(driver, By::Css("div"), timeouts.element_wait()).await?;
+        sleep(timeouts.page_load()).await;
+        driver.execute("window.scrollTo(0, arguments[0]);", vec![json!(self.scroll_y)]).await?;
+        Ok(())
+    }
+}
+
+// Logs in again and saves the fresh cookies
+async fn relogin(driver: &WebDriver) -> WebDriverResult<()> {
+    println!("Dice session expired; log in again in the browser window.");
+    login(driver).await?;
+    save_cookies(driver).await
+}
+
+// driver.get that survives an expired session: if Dice bounces us to the
+// login page, log in again and come back to `url`
+pub async fn navigate(driver: &WebDriver, timeouts: &TimeoutConfig, url: &str) -> WebDriverResult<()> {
+    driver.get(url).await?;
+    if !is_login_page(driver).await? {
+        return Ok(());
+    }
+    relogin(driver).await?;
+    BrowserPosition { url: url.to_string(), scroll_y: 0.0 }.restore(driver, timeouts).await?;
+    // Parsing the login page would look like an empty search or a job with
+    // no Easy Apply button, so stop instead
+    if is_login_page(driver).await? {
+        return Err(WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("Still on the login page after logging in again; could not open {}", url))));
+    }
+    Ok(())
+}
+
+// For a session that expires while a page is open: returns true if the
+// browser had to log in again and was put back at `position`
+pub async fn recover(driver: &WebDriver, timeouts: &TimeoutConfig, position: &BrowserPosition) -> WebDriverResult<bool> {
+    if !is_login_page(driver).await? {
+        return Ok(false);
+    }
+    relogin(driver).await?;
+    // Already on the login page when captured; nothing better to go back to
+    if Url::parse(&position.url).is_ok_and(|url| is_login_url(&url)) {
+        return Ok(true);
+    }
+    position.restore(driver, timeouts).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn login(url: &str) -> bool {
+        is_login_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn login_pages() {
+        assert!(login("https://www.dice.com/dashboard/login"));
+        assert!(login("https://www.dice.com/dashboard/login?redirectUrl=%2Fjobs"));
+        assert!(login("https://www.dice.com/SignIn"));
+        assert!(login("https://login.dice.com/authorize?client_id=abc"));
+    }
+
+    #[test]
+    fn ordinary_pages() {
+        assert!(!login("https://www.dice.com/jobs?q=Rust"));
+        assert!(!login("https://www.dice.com/job-detail/2b9d5bc5-3c4d-4b6b-9a1e-6c1e9f1a2b3c"));
+        assert!(!login("https://www.dice.com/jobs?q=Login+Engineer"));
+    }
+}
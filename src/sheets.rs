@@ -0,0 +1,132 @@
+use std::fs;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+
+use crate::history::{ApplicationRecord, History};
+
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+const SHEETS_API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+// Appends one row per application to a Google Sheet, authenticating as a
+// service account. Share the sheet with the service account's email first.
+#[derive(Serialize, Deserialize)]
+pub struct SheetsConfig {
+    pub spreadsheet_id: String,
+    // Sheet (tab) name, or an A1 range whose table rows are appended to
+    #[serde(default = "default_range")]
+    pub range: String,
+    // Service account key file downloaded from the Google Cloud console
+    pub credentials_file: String,
+}
+
+fn default_range() -> String {
+    "Applications".to_string()
+}
+
+// The fields we need from a service account key file
+#[derive(Deserialize)]
+struct ServiceAccount {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+fn sheets_error(message: String) -> WebDriverError {
+    WebDriverError::UnknownError(WebDriverErrorInfo::new(message))
+}
+
+// Exchanges a signed JWT for an OAuth access token (the service account flow)
+async fn access_token(client: &reqwest::Client, account: &ServiceAccount) -> WebDriverResult<String> {
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        iss: &account.client_email,
+        scope: SHEETS_SCOPE,
+        aud: &account.token_uri,
+        iat: now,
+        exp: now + 3600,
+    };
+    let key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+        .map_err(|e| sheets_error(format!("Service account private key is unusable: {}", e)))?;
+    let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| sheets_error(format!("Could not sign the token request: {}", e)))?;
+
+    let response: Value = client
+        .post(&account.token_uri)
+        .form(&[("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"), ("assertion", assertion.as_str())])
+        .send()
+        .await
+        .map_err(|e| sheets_error(format!("Token request failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| sheets_error(format!("Token response is not valid JSON: {}", e)))?;
+    match response["access_token"].as_str() {
+        Some(token) => Ok(token.to_string()),
+        None => Err(sheets_error(format!("Google refused the token request: {}", response["error_description"]))),
+    }
+}
+
+fn row(record: &ApplicationRecord) -> Value {
+    json!([
+        record.applied_at.to_rfc3339(),
+        record.job_title,
+        record.url,
+        record.category.as_deref().unwrap_or_default(),
+        record.resume_variant.as_deref().unwrap_or_default(),
+        record.applicants.map(|n| n.to_string()).unwrap_or_default(),
+        record.source.to_string(),
+        format!("{:?}", record.status).to_lowercase(),
+        record.summary.as_deref().unwrap_or_default(),
+    ])
+}
+
+// Appends the applications recorded since `since` (normally the start of
+// the run). Columns: applied at, title, URL, category, resume variant,
+// applicants, source, status, summary.
+pub async fn export_applications(config: &SheetsConfig, history: &History, since: DateTime<Utc>) -> WebDriverResult<()> {
+    let rows: Vec<Value> = history.records.iter().filter(|r| r.applied_at >= since).map(row).collect();
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let key_file = fs::read_to_string(&config.credentials_file)
+        .map_err(|e| sheets_error(format!("Could not read {}: {}", config.credentials_file, e)))?;
+    let account: ServiceAccount = serde_json::from_str(&key_file)
+        .map_err(|e| sheets_error(format!("{} is not a service account key: {}", config.credentials_file, e)))?;
+
+    let client = reqwest::Client::new();
+    let token = access_token(&client, &account).await?;
+    let mut url = reqwest::Url::parse(SHEETS_API).map_err(|e| sheets_error(e.to_string()))?;
+    url.path_segments_mut()
+        .map_err(|_| sheets_error(format!("{} can't take a path", SHEETS_API)))?
+        .push(&config.spreadsheet_id)
+        .push("values")
+        .push(&format!("{}:append", config.range));
+    let response = client
+        .post(url)
+        .bearer_auth(token)
+        .query(&[("valueInputOption", "RAW"), ("insertDataOption", "INSERT_ROWS")])
+        .json(&json!({ "values": rows }))
+        .send()
+        .await
+        .map_err(|e| sheets_error(format!("Could not reach the Sheets API: {}", e)))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(sheets_error(format!("Appending to the sheet failed with {}: {}", status, body)));
+    }
+    println!("Appended {} applications to Google Sheet {}", rows.len(), config.spreadsheet_id);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashMap};
+
+// Spelling -> canonical skill name. Keys are lowercase. Extended (and
+// overridden) by `skill_synonyms` in the config. Short words that also mean
+// something else ("node", "ts" as in TS/SCI, "js") are left out even as
+// whole words.
+const BUNDLED_SYNONYMS: &[(&str, &str)] = &[
+    ("k8s", "Kubernetes"),
+    ("kube", "Kubernetes"),
+    ("golang", "Go"),
+    ("ecmascript", "JavaScript"),
+    ("nodejs", "Node.js"),
+    ("node js", "Node.js"),
+    ("reactjs", "React"),
+    ("react.js", "React"),
+    ("vuejs", "Vue"),
+    ("vue.js", "Vue"),
+    ("angularjs", "Angular"),
+    ("python3", "Python"),
+    ("csharp", "C#"),
+    ("c sharp", "C#"),
+    ("dotnet", ".NET"),
+    (".net core", ".NET"),
+    ("asp.net", ".NET"),
+    ("postgres", "PostgreSQL"),
+    ("psql", "PostgreSQL"),
+    ("mssql", "SQL Server"),
+    ("ms sql", "SQL Server"),
+    ("mongo", "MongoDB"),
+    ("elastic search", "Elasticsearch"),
+    ("amazon web services", "AWS"),
+    ("gcp", "Google Cloud"),
+    ("google cloud platform", "Google Cloud"),
+    ("microsoft azure", "Azure"),
+    ("springboot", "Spring Boot"),
+    ("ci/cd", "CI/CD"),
+    ("cicd", "CI/CD"),
+    ("ml", "Machine Learning"),
+    ("restful", "REST"),
+    ("rest api", "REST"),
+];
+
+// Maps the many ways postings spell a skill onto one name, so "K8s" and
+// "Kubernetes" count as the same skill in filters and reports
+pub struct SkillTaxonomy {
+    synonyms: HashMap<String, String>,
+}
+
+fn key(skill: &str) -> String {
+    skill.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+// Whole-term match, so short names like "Go" don't match inside "Google"
+pub fn contains_term(haystack: &str, term: &str) -> bool {
+    if term.is_empty() {
+        return false;
+    }
+    let is_word = |c: char| c.is_alphanumeric();
+    haystack.match_indices(term).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + term.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+impl SkillTaxonomy {
+    pub fn new(user_synonyms: &BTreeMap<String, String>) -> SkillTaxonomy {
+        let mut synonyms: HashMap<String, String> = BUNDLED_SYNONYMS
+            .iter()
+            .map(|(spelling, canonical)| (spelling.to_string(), canonical.to_string()))
+            .collect();
+        for (spelling, canonical) in user_synonyms {
+            synonyms.insert(key(spelling), canonical.trim().to_string());
+        }
+        SkillTaxonomy { synonyms }
+    }
+
+    // Unknown skills keep their own spelling, minus stray whitespace
+    pub fn canonical(&self, skill: &str) -> String {
+        let skill = skill.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.synonyms.get(&skill.to_lowercase()).cloned().unwrap_or(skill)
+    }
+
+    // The canonical name and every synonym for it, lowercase
+    fn spellings(&self, skill: &str) -> Vec<String> {
+        let canonical = self.canonical(skill);
+        let mut spellings = vec![canonical.to_lowercase()];
+        spellings.extend(
+            self.synonyms
+                .iter()
+                .filter(|(_, name)| **name == canonical)
+                .map(|(spelling, _)| spelling.clone()),
+        );
+        spellings
+    }
+
+    // Whether lowercase `text` mentions the skill under any of its names
+    pub fn mentioned_in(&self, skill: &str, text: &str) -> bool {
+        self.spellings(skill).iter().any(|spelling| contains_term(text, spelling))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn taxonomy() -> SkillTaxonomy {
+        SkillTaxonomy::new(&BTreeMap::new())
+    }
+
+    #[test]
+    fn bundled_synonyms() {
+        let taxonomy = taxonomy();
+        assert_eq!(taxonomy.canonical("K8s"), "Kubernetes");
+        assert_eq!(taxonomy.canonical("Golang"), "Go");
+        assert_eq!(taxonomy.canonical(" node  js "), "Node.js");
+    }
+
+    #[test]
+    fn unknown_skills_keep_their_spelling() {
+        assert_eq!(taxonomy().canonical("Rust"), "Rust");
+    }
+
+    #[test]
+    fn user_synonyms_extend_and_override() {
+        let user = BTreeMap::from([("RoR".to_string(), "Ruby on Rails".to_string()), ("ml".to_string(), "ML".to_string())]);
+        let taxonomy = SkillTaxonomy::new(&user);
+        assert_eq!(taxonomy.canonical("ror"), "Ruby on Rails");
+        assert_eq!(taxonomy.canonical("ML"), "ML");
+    }
+
+    #[test]
+    fn mentions_match_any_spelling_as_a_whole_term() {
+        let taxonomy = taxonomy();
+        assert!(taxonomy.mentioned_in("Kubernetes", "experience running k8s clusters"));
+        assert!(taxonomy.mentioned_in("Go", "services written in golang"));
+        assert!(!taxonomy.mentioned_in("Go", "google cloud and good habits"));
+        assert!(taxonomy.mentioned_in("C#", "c#, .net and sql"));
+        assert!(taxonomy.mentioned_in("Node.js", "node.js and nodejs services"));
+        assert!(!taxonomy.mentioned_in("TypeScript", "active ts/sci clearance"));
+        assert!(!taxonomy.mentioned_in("Node.js", "each node in the cluster"));
+        assert!(!taxonomy.mentioned_in("JavaScript", "assets under js/ and css/"));
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::PathBuf;
+use base64::{decode_config, STANDARD};
+use serde_json::json;
+use thirtyfour::extensions::cdp::ChromeDevTools;
+use thirtyfour::prelude::*;
+
+use crate::{job_id_from_url, paths, state_file, Job};
+
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+
+// Chrome only prints to PDF headless; a headful run saves the page as
+// MHTML instead. Returns the file's contents and extension.
+async fn capture(driver: &WebDriver, job: &Job) -> Option<(Vec<u8>, &'static str)> {
+    let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    let pdf_error = match dev_tools.execute_cdp_with_params("Page.printToPDF", json!({ "printBackground": true })).await {
+        Ok(printed) => match printed["data"].as_str().and_then(|data| decode_config(data, STANDARD).ok()) {
+            Some(pdf) => return Some((pdf, "pdf")),
+            None => "Chrome returned no PDF".to_string(),
+        },
+        Err(e) => e.to_string(),
+    };
+    match dev_tools.execute_cdp_with_params("Page.captureSnapshot", json!({ "format": "mhtml" })).await {
+        Ok(snapshot) => snapshot["data"].as_str().map(|mhtml| (mhtml.as_bytes().to_vec(), "mhtml")),
+        Err(e) => {
+            println!("Could not snapshot {} (PDF: {}; MHTML: {})", job.job_title, pdf_error, e);
+            None
+        }
+    }
+}
+
+// Postings are often taken down before the interview, so the job page is
+// saved as it was when applying, to snapshots/<date>-<job id>.pdf (or
+// .mhtml); best effort, so a failed snapshot is logged and the application
+// goes ahead.
+pub async fn save_job_pdf(driver: &WebDriver, job: &Job) -> Option<PathBuf> {
+    let (contents, extension) = capture(driver, job).await?;
+    let path = paths::state(SNAPSHOTS_DIR).join(format!("{}-{}.{}", chrono::Utc::now().format("%Y%m%d"), job_id_from_url(&job.url), extension));
+    match fs::create_dir_all(paths::state(SNAPSHOTS_DIR)).and_then(|_| state_file::write_atomic(&path, &contents)) {
+        Ok(()) => {
+            println!("Saved a snapshot of the posting to {}", path.display());
+            Some(path)
+        }
+        Err(e) => {
+            println!("Could not save the posting snapshot: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{Result, Write};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+// Every state file is written through here: under an advisory lock on a
+// `.lock` file next to it, via a temp file renamed into place, so neither a
+// crash nor a second dice_blast process can leave a half-written file.
+// Callers that read a file and write it back hold the lock across both.
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// Held across a load and the save that follows it, so another process
+// can't write in between; dropping it releases the lock
+pub struct StateLock {
+    _file: File,
+    path: PathBuf,
+}
+
+// Blocks until no other process holds `path`
+pub fn lock(path: &Path) -> Result<StateLock> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(sibling(path, ".lock"))?;
+    file.lock()?;
+    Ok(StateLock { _file: file, path: path.to_path_buf() })
+}
+
+impl StateLock {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&self, contents: &[u8]) -> Result<()> {
+        let temp = sibling(&self.path, &format!(".tmp-{}", std::process::id()));
+        let result = (|| {
+            let mut file = File::create(&temp)?;
+            file.write_all(contents)?;
+            file.sync_all()?;
+            fs::rename(&temp, &self.path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result
+    }
+
+    pub fn write_json<T: Serialize + ?Sized>(&self, value: &T) -> Result<()> {
+        self.write(&serde_json::to_vec_pretty(value)?)
+    }
+}
+
+// For files written without reading them first
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    lock(path)?.write(contents)
+}
+
+pub fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    lock(path)?.write_json(value)
+}
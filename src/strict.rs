@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
+
+use crate::alerts::{self, AlertConfig};
+use crate::Job;
+
+// Strict mode: a failed application no longer ends the run by itself.
+// Instead the run halts, with an alert, once more than
+// `max_failure_percent` of the last `window` applications have failed,
+// which is what selector drift, a ban or a site outage look like.
+#[derive(Serialize, Deserialize)]
+pub struct StrictMode {
+    #[serde(default = "default_window")]
+    pub window: usize,
+    #[serde(default = "default_max_failure_percent")]
+    pub max_failure_percent: u32,
+}
+
+fn default_window() -> usize {
+    10
+}
+
+fn default_max_failure_percent() -> u32 {
+    50
+}
+
+pub struct FailureRate {
+    window: usize,
+    max_failure_percent: u32,
+    // Most recent last; true for a failure
+    recent: VecDeque<bool>,
+}
+
+impl FailureRate {
+    pub fn new(strict: &StrictMode) -> FailureRate {
+        FailureRate {
+            window: strict.window.max(1),
+            max_failure_percent: strict.max_failure_percent,
+            recent: VecDeque::new(),
+        }
+    }
+
+    // Records an attempt and returns why the run should halt, if it should.
+    // Measured against the whole window, so the first few attempts can't
+    // trip it unless they alone are over the threshold.
+    pub fn record(&mut self, failed: bool) -> Option<String> {
+        self.recent.push_back(failed);
+        if self.recent.len() > self.window {
+            self.recent.pop_front();
+        }
+        let failures = self.recent.iter().filter(|failed| **failed).count();
+        if failures * 100 > self.max_failure_percent as usize * self.window {
+            Some(format!("{} of the last {} applications failed (max_failure_percent {})", failures, self.recent.len(), self.max_failure_percent))
+        } else {
+            None
+        }
+    }
+}
+
+// What to do after an application failed: Ok to go on to the next job,
+// or the error to end the run with
+pub async fn on_failure(failure_rate: Option<&mut FailureRate>, alert: Option<&AlertConfig>, job: &Job, error: WebDriverError) -> Result<(), WebDriverError> {
+    let Some(failure_rate) = failure_rate else {
+        return Err(error);
+    };
+    println!("Could not apply to {}: {}", job.job_title, error);
+    let Some(reason) = failure_rate.record(true) else {
+        return Ok(());
+    };
+    alerts::send(alert, &format!("dice_blast stopped applying: {}. Last error: {}", reason, error)).await;
+    Err(WebDriverError::UnknownError(WebDriverErrorInfo::new(format!("Halted by strict mode: {}", reason))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate() -> FailureRate {
+        FailureRate::new(&StrictMode { window: 4, max_failure_percent: 50 })
+    }
+
+    #[test]
+    fn halts_once_over_the_threshold() {
+        let mut rate = rate();
+        assert!(rate.record(true).is_none());
+        assert!(rate.record(true).is_none());
+        assert!(rate.record(true).is_some());
+    }
+
+    #[test]
+    fn old_failures_leave_the_window() {
+        let mut rate = rate();
+        for failed in [true, true, false, false, false, false, true, false] {
+            assert!(rate.record(failed).is_none());
+        }
+        assert!(rate.record(true).is_none());
+        assert!(rate.record(true).is_some());
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
+use thirtyfour::prelude::*;
+
+use crate::alerts::{self, AlertConfig};
+
+// What a run has to achieve to count as a success. A run that finishes
+// without meeting every threshold set here sends an alert and exits
+// nonzero, so a scheduler can tell "ran but did nothing" from a good run.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SuccessCriteria {
+    #[serde(default)]
+    pub min_applications: Option<usize>,
+    #[serde(default)]
+    pub min_jobs_found: Option<usize>,
+}
+
+// Each threshold the run fell short of
+fn shortfalls(criteria: &SuccessCriteria, applications: usize, jobs_found: usize) -> Vec<String> {
+    let mut shortfalls = Vec::new();
+    if let Some(min) = criteria.min_applications.filter(|min| applications < *min) {
+        shortfalls.push(format!("{} applications (min_applications {})", applications, min));
+    }
+    if let Some(min) = criteria.min_jobs_found.filter(|min| jobs_found < *min) {
+        shortfalls.push(format!("{} jobs found (min_jobs_found {})", jobs_found, min));
+    }
+    shortfalls
+}
+
+pub async fn check(criteria: Option<&SuccessCriteria>, alerts: Option<&AlertConfig>, applications: usize, jobs_found: usize) -> WebDriverResult<()> {
+    let Some(criteria) = criteria else {
+        return Ok(());
+    };
+    let shortfalls = shortfalls(criteria, applications, jobs_found);
+    if shortfalls.is_empty() {
+        return Ok(());
+    }
+    let message = format!("Run did not meet its success criteria: {}", shortfalls.join(", "));
+    alerts::send(alerts, &message).await;
+    Err(WebDriverError::UnknownError(WebDriverErrorInfo::new(message)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_set_thresholds_count() {
+        let criteria = SuccessCriteria {
+            min_applications: Some(10),
+            min_jobs_found: None,
+        };
+        assert!(shortfalls(&criteria, 10, 0).is_empty());
+        assert_eq!(shortfalls(&criteria, 3, 0), vec!["3 applications (min_applications 10)"]);
+
+        let criteria = SuccessCriteria {
+            min_applications: Some(1),
+            min_jobs_found: Some(50),
+        };
+        assert_eq!(shortfalls(&criteria, 0, 20).len(), 2);
+    }
+}
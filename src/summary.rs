@@ -0,0 +1,71 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::message::{self, LlmConfig};
+use crate::job_posting;
+use crate::Job;
+
+const SUMMARY_SENTENCES: usize = 3;
+
+// Short description summaries stored with each application, so reports and
+// follow-up reminders say what the job was
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SummaryConfig {
+    pub enabled: bool,
+    // Summarize with an OpenAI-compatible endpoint instead of picking sentences
+    pub llm: Option<LlmConfig>,
+}
+
+async fn fetch_description(job: &Job) -> Option<String> {
+    let html = reqwest::get(&job.url).await.ok()?.text().await.ok()?;
+    job_posting::details(&job_posting::from_html(&html)?).description
+}
+
+// Picks the sentences that mention the most title words and profile skills,
+// kept in their original order
+fn extractive_summary(description: &str, job_title: &str, skills: &[String]) -> String {
+    let sentence_end = Regex::new(r"[.!?]\s+").unwrap();
+    let sentences: Vec<&str> = sentence_end
+        .split(description)
+        .map(str::trim)
+        .filter(|s| s.split_whitespace().count() >= 5)
+        .collect();
+
+    let keywords: Vec<String> = job_title
+        .split_whitespace()
+        .filter(|word| word.len() > 3)
+        .map(str::to_lowercase)
+        .chain(skills.iter().map(|skill| skill.to_lowercase()))
+        .collect();
+    let score = |sentence: &str| {
+        let lower = sentence.to_lowercase();
+        keywords.iter().filter(|k| lower.contains(k.as_str())).count()
+    };
+
+    let mut ranked: Vec<usize> = (0..sentences.len()).collect();
+    // Stable sort, so earlier sentences win ties
+    ranked.sort_by_key(|&i| std::cmp::Reverse(score(sentences[i])));
+    let mut picked: Vec<usize> = ranked.into_iter().take(SUMMARY_SENTENCES).collect();
+    picked.sort();
+    picked.iter().map(|&i| format!("{}.", sentences[i].trim_end_matches('.'))).collect::<Vec<_>>().join(" ")
+}
+
+pub async fn summarize_job(config: &SummaryConfig, skills: &[String], job: &Job) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    let description = fetch_description(job).await?;
+    if let Some(llm) = &config.llm {
+        let prompt = format!(
+            "Summarize this job posting for \"{}\" in 2-3 plain sentences: what the role does, \
+             the main technologies, and anything unusual about it.\n\n{}",
+            job.job_title, description
+        );
+        if let Some(summary) = message::chat_completion(llm, &prompt).await {
+            return Some(summary);
+        }
+        println!("Falling back to an extractive summary for {}", job.job_title);
+    }
+    Some(extractive_summary(&description, &job.job_title, skills)).filter(|summary| !summary.is_empty())
+}
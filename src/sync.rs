@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use thirtyfour::error::WebDriverErrorInfo;
+use thirtyfour::prelude::*;
+
+use crate::history::History;
+
+// Remote copy of the applied-jobs set, shared between machines. `url` is read
+// with GET and written with PUT, which covers WebDAV servers directly; for S3,
+// point `url` and `put_url` at pre-signed GET and PUT URLs.
+#[derive(Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub url: String,
+    #[serde(default)]
+    pub put_url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    // Environment variable holding the password, kept out of config.json
+    #[serde(default)]
+    pub password_env: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct AppliedSet {
+    job_ids: BTreeSet<String>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+fn sync_error(message: String) -> WebDriverError {
+    WebDriverError::UnknownError(WebDriverErrorInfo::new(message))
+}
+
+fn with_auth(request: RequestBuilder, config: &SyncConfig) -> RequestBuilder {
+    match &config.username {
+        Some(username) => {
+            let password = config.password_env.as_ref().and_then(|var| std::env::var(var).ok());
+            request.basic_auth(username, password)
+        }
+        None => request,
+    }
+}
+
+// Job IDs applied to from any machine. A missing remote file is treated as
+// empty; any other failure aborts so we never apply blind.
+pub async fn pull(config: &SyncConfig) -> WebDriverResult<BTreeSet<String>> {
+    let client = reqwest::Client::new();
+    let response = with_auth(client.get(&config.url), config)
+        .send()
+        .await
+        .map_err(|e| sync_error(format!("Could not fetch applied set from {}: {}", config.url, e)))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        println!("No remote applied set yet at {}", config.url);
+        return Ok(BTreeSet::new());
+    }
+    if !response.status().is_success() {
+        return Err(sync_error(format!("Fetching applied set failed with {}", response.status())));
+    }
+    let set: AppliedSet = response
+        .json()
+        .await
+        .map_err(|e| sync_error(format!("Remote applied set is not valid JSON: {}", e)))?;
+    println!("Pulled {} applied jobs from {}", set.job_ids.len(), config.url);
+    Ok(set.job_ids)
+}
+
+// Uploads the union of the remote set and everything in local history
+pub async fn push(config: &SyncConfig, remote: &BTreeSet<String>, history: &History) -> WebDriverResult<()> {
+    let mut job_ids = remote.clone();
+    job_ids.extend(history.records.iter().map(|record| record.job_id.clone()));
+    let set = AppliedSet {
+        job_ids,
+        updated_at: Some(Utc::now()),
+    };
+
+    let url = config.put_url.as_ref().unwrap_or(&config.url);
+    let client = reqwest::Client::new();
+    let response = with_auth(client.put(url), config)
+        .json(&set)
+        .send()
+        .await
+        .map_err(|e| sync_error(format!("Could not upload applied set to {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(sync_error(format!("Uploading applied set failed with {}", response.status())));
+    }
+    println!("Pushed {} applied jobs to {}", set.job_ids.len(), url);
+    Ok(())
+}
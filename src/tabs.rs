@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+use thirtyfour::prelude::*;
+
+use crate::timeouts::TimeoutConfig;
+use crate::{build_search_url, get_job_detail_ids, Job, SearchQuery};
+
+const TAB_NAME_PREFIX: &str = "dice_blast_search_";
+
+// Scrapes several searches by opening them in background tabs, at most
+// `max_tabs` at a time. The tabs load concurrently; each is then scraped in
+// turn and closed. Results are returned in no particular order, each tagged
+// with the search that found it.
+pub async fn scrape_in_tabs(driver: &WebDriver, timeouts: &TimeoutConfig, searches: &[SearchQuery], max_tabs: usize) -> WebDriverResult<Vec<Job>> {
+    let original = driver.window().await?;
+    let mut jobs = Vec::new();
+
+    for (batch_number, batch) in searches.chunks(max_tabs.max(1)).enumerate() {
+        let offset = batch_number * max_tabs.max(1);
+        let existing: HashSet<WindowHandle> = driver.windows().await?.into_iter().collect();
+        for (i, search) in batch.iter().enumerate() {
+            // Window handles come back unordered, so name each tab after its search
+            let url = build_search_url(search);
+            let name = format!("{}{}", TAB_NAME_PREFIX, offset + i);
+            driver.execute("window.open(arguments[0], arguments[1]);", vec![serde_json::json!(url), serde_json::json!(name)]).await?;
+        }
+        let opened: Vec<WindowHandle> = driver
+            .windows()
+            .await?
+            .into_iter()
+            .filter(|handle| !existing.contains(handle))
+            .collect();
+        println!("Opened {} search tabs", opened.len());
+
+        for handle in opened {
+            driver.switch_to_window(handle).await?;
+            let tab_url = driver.current_url().await?;
+            let name: String = driver.execute("return window.name;", vec![]).await?.convert()?;
+            let search = name
+                .strip_prefix(TAB_NAME_PREFIX)
+                .and_then(|index| index.parse::<usize>().ok())
+                .and_then(|index| searches.get(index));
+            let result = get_job_detail_ids(driver, timeouts, 1).await;
+            // Close the tab even if scraping failed so tabs don't pile up
+            driver.close_window().await?;
+            match result {
+                Ok(found) if found.is_empty() => println!("Search {} returned no results", tab_url),
+                Ok(found) => jobs.extend(found.into_iter().map(|mut job| {
+                    job.query = search.map(|s| s.label().to_string());
+                    job
+                })),
+                Err(e) => println!("Search tab failed: {}", e),
+            }
+        }
+        driver.switch_to_window(original.clone()).await?;
+    }
+
+    Ok(jobs)
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use crate::Job;
+
+// The variables every text template (employer message, watch alerts) can
+// use. Placeholders are `{name}`, or `{name|fallback}` for fields a card
+// may not show, e.g. `{company|your team}`.
+pub struct TemplateContext {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl TemplateContext {
+    // `skills` are the profile skills the posting mentions, best match first
+    pub fn for_job(job: &Job, skills: &[&str]) -> TemplateContext {
+        let optional = |value: &Option<String>| value.as_deref().unwrap_or_default().trim().to_string();
+        let mut values = BTreeMap::new();
+        values.insert("job_title", job.job_title.trim().to_string());
+        values.insert("company", optional(&job.company));
+        values.insert("location", optional(&job.location));
+        values.insert("salary", optional(&job.salary));
+        values.insert("posted_age", optional(&job.posted_age));
+        values.insert("url", job.url.clone());
+        values.insert("skill", skills.first().map(|s| s.to_string()).unwrap_or_default());
+        values.insert("skills", skills.join(", "));
+        TemplateContext { values }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|value| value.as_str()).filter(|value| !value.is_empty())
+    }
+
+    // Unknown placeholders are left as written so typos show up in the output
+    pub fn render(&self, template: &str) -> String {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                rest = &rest[start..];
+                break;
+            };
+            let placeholder = &rest[start + 1..end];
+            let (name, fallback) = match placeholder.split_once('|') {
+                Some((name, fallback)) => (name.trim(), Some(fallback)),
+                None => (placeholder.trim(), None),
+            };
+            match (self.values.contains_key(name), self.get(name), fallback) {
+                (_, Some(value), _) => rendered.push_str(value),
+                (_, None, Some(fallback)) => rendered.push_str(fallback),
+                (true, None, None) => {}
+                (false, None, None) => rendered.push_str(&rest[start..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
@@ -0,0 +1,111 @@
+use crate::descriptions::PageFetcher;
+use crate::rng::RunRng;
+use crate::search_filters::{EmployerType, FilterList, FilterValue};
+use crate::skills::contains_term;
+use crate::{Config, Job};
+
+// Wording recruiters use for a client they place candidates with
+const DESCRIPTION_PHRASES: [&str; 7] = [
+    "our client",
+    "my client",
+    "on behalf of our client",
+    "w2 only through us",
+    "through our company",
+    "c2c",
+    "corp to corp",
+];
+
+const COMPANY_WORDS: [&str; 5] = ["staffing", "recruit", "talent solutions", "placement", "consultancy"];
+
+fn restrict(q: &str, employer_types: &mut FilterList<EmployerType>) {
+    if employer_types.is_empty() {
+        *employer_types = FilterList::only(EmployerType::DirectHire);
+    } else if !employer_types.contains(EmployerType::DirectHire) {
+        println!("Warning: search '{}' asks for {} postings but skip_third_party is set; leaving its employer type alone", q, employer_types);
+    } else if employer_types.0.len() > 1 {
+        println!("Narrowing search '{}' from {} to {} postings (skip_third_party)", q, employer_types, EmployerType::DirectHire.as_str());
+        *employer_types = FilterList::only(EmployerType::DirectHire);
+    }
+}
+
+// With skip_third_party set, every search asks Dice for direct-hire postings
+// only, keeping whatever the search already narrowed to
+pub fn restrict_employer_type(config: &mut Config) {
+    if !config.job_filters.skip_third_party {
+        return;
+    }
+    restrict(&config.search.q, &mut config.search.filters_employer_type);
+    for search in &mut config.searches {
+        restrict(&search.q, &mut search.filters_employer_type);
+    }
+}
+
+pub fn company_reason(job: &Job) -> Option<String> {
+    let company = job.company.as_deref()?.to_lowercase();
+    COMPANY_WORDS
+        .iter()
+        .find(|word| company.contains(*word))
+        .map(|word| format!("company name suggests a recruiter ('{}')", word))
+}
+
+// Only the description itself is searched, as whole words, so "c2c" in a
+// page script or "our client" in the site footer don't count
+fn phrase_reason(description: &str) -> Option<String> {
+    let description = description.to_lowercase();
+    DESCRIPTION_PHRASES
+        .iter()
+        .find(|phrase| contains_term(&description, phrase))
+        .map(|phrase| format!("description mentions '{}'", phrase))
+}
+
+async fn description_reason(fetcher: &mut PageFetcher<'_>, job: &Job) -> Option<String> {
+    phrase_reason(&fetcher.description(job).await?)
+}
+
+// Catches the recruiter postings Dice's employer-type filter lets through,
+// by company name first and then by fetching the description
+pub async fn drop_third_party(config: &Config, rng: &mut RunRng, jobs: Vec<Job>) -> Vec<Job> {
+    if !config.job_filters.skip_third_party {
+        return jobs;
+    }
+    let mut fetcher = PageFetcher::new(&config.timeouts, rng);
+    let mut kept = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        let reason = match company_reason(&job) {
+            Some(reason) => Some(reason),
+            None => description_reason(&mut fetcher, &job).await,
+        };
+        match reason {
+            Some(reason) => println!("Skipping {}: third-party posting ({})", job.job_title, reason),
+            None => kept.push(job),
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_a_narrower_employer_type() {
+        let mut unset = FilterList::default();
+        restrict("rust", &mut unset);
+        assert_eq!(unset.to_string(), "Direct Hire");
+
+        let mut both = FilterList(vec![EmployerType::DirectHire, EmployerType::Recruiter]);
+        restrict("rust", &mut both);
+        assert_eq!(both.to_string(), "Direct Hire");
+
+        let mut recruiters = FilterList::only(EmployerType::Recruiter);
+        restrict("rust", &mut recruiters);
+        assert_eq!(recruiters.to_string(), "Recruiter");
+    }
+
+    #[test]
+    fn phrases_match_whole_words() {
+        assert!(phrase_reason("Hiring on behalf of our client, a bank").is_some());
+        assert!(phrase_reason("C2C welcome").is_some());
+        assert!(phrase_reason("We build abc2c tooling for our clientele").is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use tokio::time::Duration;
+
+// All waits used while scraping and applying, in seconds. Any field left out
+// of the `timeouts` block in config.json keeps its default.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimeoutConfig {
+    // Settle time after a search results page has loaded
+    pub page_load_secs: u64,
+    // Upper bound for wait_for_element polling
+    pub element_wait_secs: u64,
+    // Pause before clicking a button that has just appeared
+    pub clickable_wait_secs: u64,
+    // Time allowed for each step of the Easy Apply wizard to load
+    pub apply_step_secs: u64,
+    // Pause between consecutive job navigations
+    pub navigation_secs: u64,
+    // Random extra delay (milliseconds) added to the navigation pause
+    pub jitter_ms: u64,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        TimeoutConfig {
+            page_load_secs: 5,
+            element_wait_secs: 30,
+            clickable_wait_secs: 2,
+            apply_step_secs: 10,
+            navigation_secs: 2,
+            jitter_ms: 1500,
+        }
+    }
+}
+
+impl TimeoutConfig {
+    pub fn page_load(&self) -> Duration {
+        Duration::from_secs(self.page_load_secs)
+    }
+
+    pub fn element_wait(&self) -> Duration {
+        Duration::from_secs(self.element_wait_secs)
+    }
+
+    pub fn clickable_wait(&self) -> Duration {
+        Duration::from_secs(self.clickable_wait_secs)
+    }
+
+    pub fn apply_step(&self) -> Duration {
+        Duration::from_secs(self.apply_step_secs)
+    }
+
+    pub fn navigation(&self) -> Duration {
+        Duration::from_secs(self.navigation_secs)
+    }
+
+    pub fn jitter(&self) -> Duration {
+        Duration::from_millis(self.jitter_ms)
+    }
+}
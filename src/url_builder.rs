@@ -0,0 +1,289 @@
+use url::Url;
+
+use crate::search_filters::{FilterValue, RadiusUnit};
+use crate::SearchQuery;
+
+const SEARCH_BASE: &str = "https://www.dice.com/jobs";
+
+// Composes a Dice search results URL from a SearchQuery. Values are
+// percent-encoded by the url crate; empty or default filters are left out so
+// Dice applies its own defaults.
+pub struct DiceUrlBuilder<'a> {
+    search: &'a SearchQuery,
+    page: usize,
+    page_size: Option<u32>,
+}
+
+impl<'a> DiceUrlBuilder<'a> {
+    pub fn new(search: &'a SearchQuery) -> DiceUrlBuilder<'a> {
+        DiceUrlBuilder {
+            search,
+            page: 1,
+            page_size: search.page_size,
+        }
+    }
+
+    // 1-based, like Dice's own pagination
+    pub fn page(mut self, page: usize) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    pub fn build(&self) -> Url {
+        let search = self.search;
+        let mut url = Url::parse(SEARCH_BASE).expect("SEARCH_BASE is a valid URL");
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("q", &search.q);
+            if !search.location.is_empty() {
+                query.append_pair("location", &search.location);
+            }
+            if let Some(radius) = search.radius {
+                query.append_pair("radius", &radius.to_string());
+                query.append_pair("radiusUnit", search.radius_unit.unwrap_or(RadiusUnit::Mi).as_str());
+            }
+            if !search.country_code.is_empty() {
+                query.append_pair("countryCode", &search.country_code);
+            }
+            if !search.filters_employment_type.0.is_empty() {
+                query.append_pair("filters.employmentType", &search.filters_employment_type.to_string());
+            }
+            if !search.filters_employer_type.0.is_empty() {
+                query.append_pair("filters.employerType", &search.filters_employer_type.to_string());
+            }
+            if search.filters_easy_apply {
+                query.append_pair("filters.easyApply", "true");
+            }
+            if let Some(posted_date) = search.posted_date {
+                query.append_pair("filters.postedDate", posted_date.as_str());
+            }
+            if !search.workplace_types.is_empty() {
+                query.append_pair("filters.workplaceTypes", &search.workplace_types.to_string());
+            }
+            if !search.language.is_empty() {
+                query.append_pair("language", &search.language);
+            }
+            if self.page > 1 {
+                query.append_pair("page", &self.page.to_string());
+            }
+            if let Some(page_size) = self.page_size {
+                query.append_pair("pageSize", &page_size.to_string());
+            }
+            if let Some(sort) = search.sort {
+                query.append_pair("sort", sort.as_str());
+            }
+        }
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search_filters::{EmployerType, EmploymentType, FilterList, PostedDate, SortOrder, WorkplaceType};
+
+    fn search() -> SearchQuery {
+        SearchQuery {
+            q: "Devops".to_string(),
+            location: String::new(),
+            radius: None,
+            radius_unit: None,
+            locations: Vec::new(),
+            country_code: String::new(),
+            filters_employment_type: FilterList(Vec::new()),
+            filters_employer_type: FilterList(Vec::new()),
+            filters_easy_apply: false,
+            posted_date: None,
+            workplace_types: FilterList(Vec::new()),
+            language: String::new(),
+            max_applications: None,
+            page_size: None,
+            sort: None,
+            label: String::new(),
+        }
+    }
+
+    fn query(url: &Url) -> Vec<(String, String)> {
+        url.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect()
+    }
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn minimal_search_only_has_q() {
+        let url = DiceUrlBuilder::new(&search()).build();
+        assert_eq!(url.as_str(), "https://www.dice.com/jobs?q=Devops");
+    }
+
+    #[test]
+    fn never_embeds_debug_formatting() {
+        let url = DiceUrlBuilder::new(&search()).build();
+        assert!(!url.as_str().contains("Ok("));
+        assert!(!url.as_str().contains('"'));
+    }
+
+    #[test]
+    fn location_is_encoded() {
+        let mut s = search();
+        s.location = "Austin, Texas".to_string();
+        let url = DiceUrlBuilder::new(&s).build();
+        assert_eq!(url.as_str(), "https://www.dice.com/jobs?q=Devops&location=Austin%2C+Texas");
+    }
+
+    #[test]
+    fn radius_defaults_to_miles() {
+        let mut s = search();
+        s.location = "Dallas, TX".to_string();
+        s.radius = Some(50);
+        let expected = vec![pair("q", "Devops"), pair("location", "Dallas, TX"), pair("radius", "50"), pair("radiusUnit", "mi")];
+        assert_eq!(query(&DiceUrlBuilder::new(&s).build()), expected);
+        s.radius_unit = Some(RadiusUnit::Km);
+        assert!(DiceUrlBuilder::new(&s).build().as_str().ends_with("radius=50&radiusUnit=km"));
+    }
+
+    #[test]
+    fn country_code() {
+        let mut s = search();
+        s.country_code = "US".to_string();
+        assert_eq!(query(&DiceUrlBuilder::new(&s).build()), vec![pair("q", "Devops"), pair("countryCode", "US")]);
+    }
+
+    #[test]
+    fn each_employment_type() {
+        for value in [EmploymentType::Fulltime, EmploymentType::Parttime, EmploymentType::Contracts, EmploymentType::ThirdParty] {
+            let mut s = search();
+            s.filters_employment_type = FilterList::only(value);
+            let expected = vec![pair("q", "Devops"), pair("filters.employmentType", value.as_str())];
+            assert_eq!(query(&DiceUrlBuilder::new(&s).build()), expected);
+        }
+    }
+
+    #[test]
+    fn several_employment_types_are_pipe_joined() {
+        let mut s = search();
+        s.filters_employment_type = FilterList(vec![EmploymentType::Fulltime, EmploymentType::Contracts]);
+        let url = DiceUrlBuilder::new(&s).build();
+        assert!(url.as_str().contains("filters.employmentType=FULLTIME%7CCONTRACTS"));
+    }
+
+    #[test]
+    fn each_employer_type() {
+        for value in [EmployerType::DirectHire, EmployerType::Recruiter, EmployerType::Other] {
+            let mut s = search();
+            s.filters_employer_type = FilterList::only(value);
+            let expected = vec![pair("q", "Devops"), pair("filters.employerType", value.as_str())];
+            assert_eq!(query(&DiceUrlBuilder::new(&s).build()), expected);
+        }
+    }
+
+    #[test]
+    fn several_employer_types() {
+        let mut s = search();
+        s.filters_employer_type = FilterList(vec![EmployerType::DirectHire, EmployerType::Recruiter]);
+        let url = DiceUrlBuilder::new(&s).build();
+        assert!(url.as_str().contains("filters.employerType=Direct+Hire%7CRecruiter"));
+    }
+
+    #[test]
+    fn easy_apply_only_when_set() {
+        let mut s = search();
+        assert!(!DiceUrlBuilder::new(&s).build().as_str().contains("easyApply"));
+        s.filters_easy_apply = true;
+        assert_eq!(query(&DiceUrlBuilder::new(&s).build()), vec![pair("q", "Devops"), pair("filters.easyApply", "true")]);
+    }
+
+    #[test]
+    fn each_posted_date() {
+        for value in [PostedDate::OneDay, PostedDate::ThreeDays, PostedDate::SevenDays] {
+            let mut s = search();
+            s.posted_date = Some(value);
+            let expected = vec![pair("q", "Devops"), pair("filters.postedDate", value.as_str())];
+            assert_eq!(query(&DiceUrlBuilder::new(&s).build()), expected);
+        }
+    }
+
+    #[test]
+    fn workplace_types_are_pipe_joined() {
+        let mut s = search();
+        s.workplace_types = FilterList(vec![WorkplaceType::Remote, WorkplaceType::Hybrid]);
+        let url = DiceUrlBuilder::new(&s).build();
+        assert_eq!(query(&url), vec![pair("q", "Devops"), pair("filters.workplaceTypes", "Remote|Hybrid")]);
+        assert!(url.as_str().contains("filters.workplaceTypes=Remote%7CHybrid"));
+    }
+
+    #[test]
+    fn on_site_keeps_its_hyphen() {
+        let mut s = search();
+        s.workplace_types = FilterList::only(WorkplaceType::OnSite);
+        assert!(DiceUrlBuilder::new(&s).build().as_str().ends_with("filters.workplaceTypes=On-Site"));
+    }
+
+    #[test]
+    fn language() {
+        let mut s = search();
+        s.language = "en".to_string();
+        assert_eq!(query(&DiceUrlBuilder::new(&s).build()), vec![pair("q", "Devops"), pair("language", "en")]);
+    }
+
+    #[test]
+    fn first_page_has_no_page_param() {
+        let url = DiceUrlBuilder::new(&search()).page(1).build();
+        assert!(!url.as_str().contains("page="));
+        let url = DiceUrlBuilder::new(&search()).page(0).build();
+        assert!(!url.as_str().contains("page="));
+    }
+
+    #[test]
+    fn later_pages() {
+        let url = DiceUrlBuilder::new(&search()).page(3).build();
+        assert_eq!(query(&url), vec![pair("q", "Devops"), pair("page", "3")]);
+    }
+
+    #[test]
+    fn page_size() {
+        let mut s = search();
+        s.page_size = Some(50);
+        assert_eq!(query(&DiceUrlBuilder::new(&s).build()), vec![pair("q", "Devops"), pair("pageSize", "50")]);
+    }
+
+    #[test]
+    fn each_sort_order() {
+        for value in [SortOrder::Relevance, SortOrder::Date] {
+            let mut s = search();
+            s.sort = Some(value);
+            assert_eq!(query(&DiceUrlBuilder::new(&s).build()), vec![pair("q", "Devops"), pair("sort", value.as_str())]);
+        }
+    }
+
+    #[test]
+    fn every_filter_together() {
+        let s = SearchQuery {
+            q: "Site Reliability".to_string(),
+            location: "Austin Texas".to_string(),
+            radius: Some(25),
+            radius_unit: None,
+            locations: Vec::new(),
+            country_code: "US".to_string(),
+            filters_employment_type: FilterList::only(EmploymentType::Fulltime),
+            filters_employer_type: FilterList(vec![EmployerType::DirectHire, EmployerType::Recruiter]),
+            filters_easy_apply: true,
+            posted_date: Some(PostedDate::ThreeDays),
+            workplace_types: FilterList::only(WorkplaceType::Remote),
+            language: "en".to_string(),
+            max_applications: Some(5),
+            page_size: Some(20),
+            sort: Some(SortOrder::Date),
+            label: String::new(),
+        };
+        let url = DiceUrlBuilder::new(&s).page(2).build();
+        assert_eq!(
+            url.as_str(),
+            "https://www.dice.com/jobs?q=Site+Reliability&location=Austin+Texas&radius=25&radiusUnit=mi&countryCode=US\
+             &filters.employmentType=FULLTIME&filters.employerType=Direct+Hire%7CRecruiter\
+             &filters.easyApply=true&filters.postedDate=THREE&filters.workplaceTypes=Remote\
+             &language=en&page=2&pageSize=20&sort=date"
+        );
+    }
+}
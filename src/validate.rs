@@ -0,0 +1,418 @@
+use std::io;
+use serde_json::Value;
+
+use crate::{env_overrides, filters};
+use crate::search_filters::{EmployerType, EmploymentType, FilterValue, PostedDate, RadiusUnit, SortOrder, WorkplaceType};
+
+// Fields every search needs, top level or under `searches`, and the JSON
+// type each one takes
+const REQUIRED_SEARCH_FIELDS: [(&str, &str); 7] = [
+    ("q", "string"),
+    ("location", "string"),
+    ("countryCode", "string"),
+    ("filters.employmentType", "string"),
+    ("filters.employerType", "string"),
+    ("filters.easyApply", "boolean"),
+    ("language", "string"),
+];
+
+pub const KNOWN_TOP_LEVEL: [&str; 57] = [
+    "q",
+    "location",
+    "radius",
+    "radiusUnit",
+    "locations",
+    "countryCode",
+    "filters.employmentType",
+    "filters.employerType",
+    "filters.easyApply",
+    "filters.postedDate",
+    "posted_date",
+    "filters.workplaceTypes",
+    "workplace_type",
+    "language",
+    "max_applications",
+    "max_applications_per_run",
+    "pageSize",
+    "sort",
+    "repost_policy",
+    "categories",
+    "prelude",
+    "summary",
+    "watch",
+    "zero_result_quiet_mins",
+    "searches",
+    "max_search_tabs",
+    "max_pages",
+    "max_jobs",
+    "low_memory",
+    "pipeline",
+    "assist_tabs",
+    "bookmark",
+    "profile",
+    "skill_synonyms",
+    "message",
+    "resume",
+    "job_filters",
+    "blacklist",
+    "whitelist",
+    "company_cap",
+    "timeouts",
+    "visual_diff",
+    "capture_har",
+    "strict",
+    "alerts",
+    "success",
+    "snapshot_pdf",
+    "discovery",
+    "seed",
+    "shuffle_jobs",
+    "redis",
+    "scrape_only",
+    "sync",
+    "sheets",
+    "airtable",
+    "api",
+    "webdriver_url",
+];
+
+// Dice ignores larger page sizes
+const MAX_PAGE_SIZE: u64 = 100;
+
+const REPOST_POLICIES: [&str; 3] = ["skip", "reapply", "flag"];
+const SENIORITIES: [&str; 6] = ["intern", "junior", "mid", "senior", "lead", "principal"];
+const GUARD_ACTIONS: [&str; 2] = ["warn", "skip"];
+
+#[derive(PartialEq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+struct Problem {
+    severity: Severity,
+    field: String,
+    message: String,
+    suggestion: Option<String>,
+}
+
+#[derive(Default)]
+struct Problems(Vec<Problem>);
+
+impl Problems {
+    fn error(&mut self, field: &str, message: String, suggestion: Option<String>) {
+        self.0.push(Problem { severity: Severity::Error, field: field.to_string(), message, suggestion });
+    }
+
+    fn warning(&mut self, field: &str, message: String, suggestion: Option<String>) {
+        self.0.push(Problem { severity: Severity::Warning, field: field.to_string(), message, suggestion });
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// The closest candidate, if it's near enough to be a plausible typo
+fn closest<'a>(value: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(value, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 3.max(value.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn did_you_mean(value: &str, candidates: &[&str]) -> Option<String> {
+    closest(value, candidates).map(|candidate| format!("did you mean \"{}\"?", candidate))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check_search(problems: &mut Problems, prefix: &str, search: &Value, top_level: bool) {
+    for (field, expected) in REQUIRED_SEARCH_FIELDS {
+        let path = format!("{}{}", prefix, field);
+        if field == "location" && search.get("locations").is_some() {
+            continue;
+        }
+        match search.get(field) {
+            None => {
+                let hint = if top_level { format!("add it to the config file or set {}", env_overrides::var_for(field)) } else { "add it to this search".to_string() };
+                problems.error(&path, "is required".to_string(), Some(hint));
+            }
+            Some(value) if type_name(value) != expected => {
+                problems.error(&path, format!("must be a {}, found a {}", expected, type_name(value)), None);
+            }
+            Some(_) => {}
+        }
+    }
+
+    check_filter::<EmploymentType>(problems, &format!("{}filters.employmentType", prefix), search.get("filters.employmentType"));
+    check_filter::<EmployerType>(problems, &format!("{}filters.employerType", prefix), search.get("filters.employerType"));
+    let posted_date = search.get("filters.postedDate").or_else(|| search.get("posted_date"));
+    let mut posted_dates = PostedDate::names();
+    posted_dates.extend(["1", "3", "7"]);
+    check_choice(problems, &format!("{}filters.postedDate", prefix), posted_date, &posted_dates);
+    let workplace_types = search.get("filters.workplaceTypes").or_else(|| search.get("workplace_type"));
+    check_filter::<WorkplaceType>(problems, &format!("{}filters.workplaceTypes", prefix), workplace_types);
+    check_choice(problems, &format!("{}sort", prefix), search.get("sort"), &SortOrder::names());
+    check_choice(problems, &format!("{}radiusUnit", prefix), search.get("radiusUnit"), &RadiusUnit::names());
+    for (i, place) in search["locations"].as_array().into_iter().flatten().enumerate() {
+        let field = format!("{}locations[{}]", prefix, i);
+        if !place.is_string() && !place["location"].is_string() {
+            problems.error(&field, "must be a place name or {\"location\": ..., \"radius\": ...}".to_string(), None);
+        }
+        check_choice(problems, &format!("{}.radius_unit", field), place.get("radius_unit"), &RadiusUnit::names());
+    }
+    if let Some(page_size) = search.get("pageSize") {
+        if !page_size.as_u64().is_some_and(|size| (1..=MAX_PAGE_SIZE).contains(&size)) {
+            problems.error(&format!("{}pageSize", prefix), format!("{} is not a page size Dice accepts", page_size), Some(format!("use 1 to {}", MAX_PAGE_SIZE)));
+        }
+    }
+    if let Some(code) = search.get("countryCode").and_then(Value::as_str) {
+        let two_letters = code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic());
+        if !two_letters || code != code.to_uppercase() {
+            let suggestion = if two_letters {
+                format!("use \"{}\"", code.to_uppercase())
+            } else {
+                "use a two-letter ISO 3166 code such as \"US\"".to_string()
+            };
+            problems.error(&format!("{}countryCode", prefix), format!("\"{}\" is not a country code", code), Some(suggestion));
+        }
+    }
+}
+
+// Dice's multi-value filters take several values joined with `|`
+fn check_filter<T: FilterValue>(problems: &mut Problems, field: &str, value: Option<&Value>) {
+    let Some(types) = value.and_then(Value::as_str) else {
+        return;
+    };
+    let names = T::names();
+    for value in types.split('|').map(str::trim) {
+        if T::parse(value).is_none() {
+            let suggestion = did_you_mean(value, &names).or_else(|| Some(format!("use one or more of {}", names.join("|"))));
+            problems.error(field, format!("\"{}\" is not an {} Dice knows", value, T::NAME), suggestion);
+        }
+    }
+}
+
+fn check_choice(problems: &mut Problems, field: &str, value: Option<&Value>, choices: &[&str]) {
+    let Some(value) = value.and_then(Value::as_str) else {
+        return;
+    };
+    if !choices.contains(&value) {
+        let suggestion = did_you_mean(value, choices).or_else(|| Some(format!("use one of {}", choices.join(", "))));
+        problems.error(field, format!("\"{}\" is not a valid value", value), suggestion);
+    }
+}
+
+fn check_resume_rules(problems: &mut Problems, resume: &Value) {
+    let variants: Vec<&str> = resume["variants"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|variant| variant["name"].as_str())
+        .collect();
+    for (i, rule) in resume["rules"].as_array().into_iter().flatten().enumerate() {
+        let Some(name) = rule["variant"].as_str() else {
+            continue;
+        };
+        if !variants.contains(&name) {
+            let suggestion = did_you_mean(name, &variants).or_else(|| Some("add it under resume.variants".to_string()));
+            problems.error(&format!("resume.rules[{}].variant", i), format!("no resume variant is named \"{}\"", name), suggestion);
+        }
+    }
+}
+
+// Each entry of a list of regexes must compile
+fn check_patterns(problems: &mut Problems, field: &str, value: &Value) {
+    for (i, pattern) in value.as_array().into_iter().flatten().enumerate() {
+        let Some(pattern) = pattern.as_str() else {
+            problems.error(&format!("{}[{}]", field, i), format!("must be a string, found a {}", type_name(pattern)), None);
+            continue;
+        };
+        if let Err(e) = filters::compile_pattern(pattern) {
+            problems.error(&format!("{}[{}]", field, i), format!("is not a valid regex: {}", e), None);
+        }
+    }
+}
+
+// Options that contradict each other
+fn check_conflicts(problems: &mut Problems, document: &Value) {
+    let skip_third_party = document["job_filters"]["skip_third_party"].as_bool().unwrap_or(false);
+    if let Some(types) = document.get("filters.employerType").and_then(Value::as_str) {
+        // Not an error: the search keeps its employer type and the
+        // description and company checks still drop third-party postings
+        if skip_third_party && !types.split('|').any(|t| t.trim() == EmployerType::DirectHire.as_str()) {
+            problems.warning(
+                "job_filters.skip_third_party",
+                format!("is set, but filters.employerType asks for \"{}\"; most of what that search finds will be skipped", types),
+                Some("turn skip_third_party off or add \"Direct Hire\" to filters.employerType".to_string()),
+            );
+        }
+    }
+    if !document["message"]["llm"].is_null() && !document["message"]["enabled"].as_bool().unwrap_or(false) {
+        problems.warning("message.llm", "is set but message.enabled is false, so no message is sent".to_string(), Some("set message.enabled to true".to_string()));
+    }
+    if !document["assist_tabs"].is_null() && document["bookmark"].as_bool().unwrap_or(false) {
+        problems.warning("bookmark", "is ignored while assist_tabs is set".to_string(), Some("remove one of them".to_string()));
+    }
+    if document["searches"].as_array().is_some_and(|searches| !searches.is_empty()) && document["max_search_tabs"].as_u64() == Some(0) {
+        problems.error("max_search_tabs", "is 0, so the extra searches never run".to_string(), Some("set it to at least 1 or remove searches".to_string()));
+    }
+}
+
+fn collect_problems(document: &Value) -> Problems {
+    let mut problems = Problems::default();
+    let Some(object) = document.as_object() else {
+        problems.error("(root)", format!("must be an object, found a {}", type_name(document)), None);
+        return problems;
+    };
+
+    for key in object.keys() {
+        if !KNOWN_TOP_LEVEL.contains(&key.as_str()) {
+            problems.warning(key, "is not a config option and will be ignored".to_string(), did_you_mean(key, &KNOWN_TOP_LEVEL));
+        }
+    }
+
+    check_search(&mut problems, "", document, true);
+    for (i, search) in document["searches"].as_array().into_iter().flatten().enumerate() {
+        check_search(&mut problems, &format!("searches[{}].", i), search, false);
+    }
+
+    check_choice(&mut problems, "repost_policy", document.get("repost_policy"), &REPOST_POLICIES);
+    check_choice(&mut problems, "profile.seniority", document["profile"].get("seniority"), &SENIORITIES);
+    check_choice(&mut problems, "job_filters.seniority.action", document["job_filters"]["seniority"].get("action"), &GUARD_ACTIONS);
+    check_resume_rules(&mut problems, &document["resume"]);
+    check_patterns(&mut problems, "whitelist.title_patterns", &document["whitelist"]["title_patterns"]);
+    check_patterns(&mut problems, "job_filters.title_include", &document["job_filters"]["title_include"]);
+    check_patterns(&mut problems, "job_filters.title_exclude", &document["job_filters"]["title_exclude"]);
+    check_conflicts(&mut problems, document);
+    if document["scrape_only"].get("max_pages").is_some() {
+        problems.warning("scrape_only.max_pages", "is no longer read; scrape-only walks the top-level max_pages".to_string(), None);
+    }
+    problems
+}
+
+// Runs before anything is deserialized or a browser is started, so a bad
+// config fails with every problem listed instead of the first serde error
+pub fn check(document: &Value) -> io::Result<()> {
+    let problems = collect_problems(document);
+    let mut errors = 0;
+    for problem in &problems.0 {
+        let label = match problem.severity {
+            Severity::Error => {
+                errors += 1;
+                "error"
+            }
+            Severity::Warning => "warning",
+        };
+        match &problem.suggestion {
+            Some(suggestion) => println!("Config {}: {} {} ({})", label, problem.field, problem.message, suggestion),
+            None => println!("Config {}: {} {}", label, problem.field, problem.message),
+        }
+    }
+    if errors > 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("config has {} errors; see above", errors)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn valid() -> Value {
+        json!({
+            "q": "rust",
+            "location": "Austin, TX",
+            "countryCode": "US",
+            "filters.employmentType": "FULLTIME",
+            "filters.employerType": "Direct Hire",
+            "filters.easyApply": true,
+            "language": "en",
+        })
+    }
+
+    fn with(extra: Value) -> Value {
+        let mut document = valid();
+        document.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        document
+    }
+
+    fn find<'a>(problems: &'a Problems, field: &str) -> &'a Problem {
+        problems.0.iter().find(|problem| problem.field == field).unwrap_or_else(|| panic!("no problem with {}", field))
+    }
+
+    #[test]
+    fn a_complete_search_has_no_problems() {
+        assert!(collect_problems(&valid()).0.is_empty());
+    }
+
+    #[test]
+    fn missing_fields_name_the_variable_that_sets_them() {
+        let mut document = valid();
+        document.as_object_mut().unwrap().remove("countryCode");
+        document.as_object_mut().unwrap().remove("filters.employmentType");
+        let problems = collect_problems(&document);
+        let country = find(&problems, "countryCode");
+        assert!(country.severity == Severity::Error);
+        assert_eq!(country.suggestion.as_deref(), Some("add it to the config file or set DICE_BLAST_COUNTRY_CODE"));
+        let employment = find(&problems, "filters.employmentType");
+        assert_eq!(employment.suggestion.as_deref(), Some("add it to the config file or set DICE_BLAST_FILTERS_EMPLOYMENT_TYPE"));
+
+        // Inside `searches` there's no variable to suggest
+        let problems = collect_problems(&with(json!({ "searches": [{ "q": "golang" }] })));
+        assert_eq!(find(&problems, "searches[0].countryCode").suggestion.as_deref(), Some("add it to this search"));
+    }
+
+    #[test]
+    fn bad_values_get_suggestions() {
+        let problems = collect_problems(&with(json!({ "filters.employmentType": "FULLTIM", "repost_policy": "skp", "qq": 1 })));
+        let employment = find(&problems, "filters.employmentType");
+        assert!(employment.severity == Severity::Error);
+        assert_eq!(employment.suggestion.as_deref(), Some("did you mean \"FULLTIME\"?"));
+        assert_eq!(find(&problems, "repost_policy").suggestion.as_deref(), Some("did you mean \"skip\"?"));
+        let unknown = find(&problems, "qq");
+        assert!(unknown.severity == Severity::Warning);
+        assert_eq!(unknown.suggestion.as_deref(), Some("did you mean \"q\"?"));
+
+        let problems = collect_problems(&with(json!({ "job_filters": { "title_include": ["rust("] } })));
+        assert!(find(&problems, "job_filters.title_include[0]").severity == Severity::Error);
+    }
+
+    #[test]
+    fn conflicting_options() {
+        let problems = collect_problems(&with(json!({ "assist_tabs": 3, "bookmark": true })));
+        assert!(find(&problems, "bookmark").severity == Severity::Warning);
+
+        let problems = collect_problems(&with(json!({ "searches": [valid()], "max_search_tabs": 0 })));
+        assert!(find(&problems, "max_search_tabs").severity == Severity::Error);
+
+        let problems = collect_problems(&with(json!({ "filters.employerType": "Recruiter", "job_filters": { "skip_third_party": true } })));
+        assert!(find(&problems, "job_filters.skip_third_party").severity == Severity::Warning);
+        assert!(check(&with(json!({ "filters.employerType": "Recruiter", "job_filters": { "skip_third_party": true } }))).is_ok());
+        assert!(check(&with(json!({ "searches": [valid()], "max_search_tabs": 0 }))).is_err());
+    }
+}
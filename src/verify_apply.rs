@@ -0,0 +1,99 @@
+use base64::{decode_config, STANDARD, URL_SAFE};
+use serde_json::Value;
+use thirtyfour::extensions::cdp::ChromeDevTools;
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+
+use crate::timeouts::TimeoutConfig;
+use crate::{canonical_job_url, click_easy_apply_button, generate_encoded_url, job_id_from_url, wait_for_element};
+
+const APPLY_URL_PREFIX: &str = "https://www.dice.com/apply?";
+
+// The JSON payload carried in an encoded apply URL
+fn decode_apply_url(url: &str) -> Option<Value> {
+    let encoded = url.split_once('?')?.1;
+    let encoded = encoded.split(['&', '#']).next().unwrap_or(encoded);
+    let bytes = decode_config(encoded, URL_SAFE).or_else(|_| decode_config(encoded, STANDARD)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+// The apply URL the site navigated to, read from Chrome's navigation history
+// over CDP so client-side redirects that never settle are still caught
+async fn captured_apply_url(driver: &WebDriver) -> WebDriverResult<Option<String>> {
+    let dev_tools = ChromeDevTools::new(driver.handle.clone());
+    let history = dev_tools.execute_cdp("Page.getNavigationHistory").await?;
+    let url = history["entries"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .rev()
+        .filter_map(|entry| entry["url"].as_str())
+        .find(|url| url.starts_with(APPLY_URL_PREFIX))
+        .map(str::to_string);
+    Ok(url)
+}
+
+fn print_field_diff(local: &Value, site: &Value) {
+    let empty = serde_json::Map::new();
+    let local = local.as_object().unwrap_or(&empty);
+    let site = site.as_object().unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = local.keys().chain(site.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut differences = 0;
+    for key in keys {
+        match (local.get(key), site.get(key)) {
+            (Some(ours), Some(theirs)) if ours == theirs => println!("  same     {}", key),
+            (Some(ours), Some(theirs)) => {
+                println!("  DIFFERS  {}: local {} / site {}", key, ours, theirs);
+                differences += 1;
+            }
+            (Some(ours), None) => {
+                println!("  LOCAL    {}: {} (site doesn't send it)", key, ours);
+                differences += 1;
+            }
+            (None, Some(theirs)) => {
+                println!("  SITE     {}: {} (builder doesn't send it)", key, theirs);
+                differences += 1;
+            }
+            (None, None) => {}
+        }
+    }
+    println!("{} field differences", differences);
+}
+
+// `dice_blast verify-apply-url <job id or URL>`: opens the job, lets the
+// site build its apply URL, and compares its payload with generate_encoded_url
+pub async fn verify_apply_url(driver: &WebDriver, timeouts: &TimeoutConfig, job: &str) -> WebDriverResult<()> {
+    let job_id = job_id_from_url(job);
+    let job_url = canonical_job_url(job_id);
+    driver.get(&job_url).await?;
+    wait_for_element(driver, By::Css("h1"), timeouts.element_wait()).await?;
+    sleep(timeouts.page_load()).await;
+    let title: String = driver
+        .execute("const h1 = document.querySelector('h1'); return h1 ? h1.innerText.trim() : '';", vec![])
+        .await?
+        .convert()?;
+
+    click_easy_apply_button(driver, timeouts).await?;
+    sleep(timeouts.apply_step()).await;
+
+    let Some(site_url) = captured_apply_url(driver).await? else {
+        println!("The site never navigated to an apply URL for job {}", job_id);
+        return Ok(());
+    };
+    // Same arguments apply_to_job gets from open_job_urls: the stored job URL
+    // and no search parameters
+    let local_url = generate_encoded_url(&job_url, &title, "");
+    println!("Local: {}", local_url);
+    println!("Site:  {}", site_url);
+
+    match (decode_apply_url(&local_url), decode_apply_url(&site_url)) {
+        (Some(local), Some(site)) => print_field_diff(&local, &site),
+        (_, None) => println!("Could not decode the site's apply payload"),
+        (None, _) => println!("Could not decode the local apply payload"),
+    }
+    Ok(())
+}
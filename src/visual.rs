@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use serde_json::from_reader;
+use thirtyfour::prelude::*;
+
+use crate::paths;
+use crate::state_file::{self, StateLock};
+
+pub const REFERENCE_DIR: &str = "visual_refs";
+const REFERENCE_FILE: &str = "visual_refs/hashes.json";
+
+// Compares screenshots of key pages against stored references using a
+// perceptual (difference) hash, to warn when Dice's UI changes before
+// selectors start failing everywhere.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisualDiffConfig {
+    pub enabled: bool,
+    // Hamming distance (out of 64 bits) above which a page counts as changed
+    pub threshold: u32,
+    // Minimum time between checks of the same page
+    pub interval_hours: i64,
+    // Replace a matching reference once it is this old
+    pub refresh_days: i64,
+}
+
+impl Default for VisualDiffConfig {
+    fn default() -> Self {
+        VisualDiffConfig {
+            enabled: false,
+            threshold: 12,
+            interval_hours: 24,
+            refresh_days: 14,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Reference {
+    hash: u64,
+    captured_at: DateTime<Utc>,
+    last_checked: DateTime<Utc>,
+}
+
+fn load_references() -> HashMap<String, Reference> {
+    File::open(paths::state(REFERENCE_FILE))
+        .ok()
+        .and_then(|file| from_reader(BufReader::new(file)).ok())
+        .unwrap_or_default()
+}
+
+fn save_references(lock: &StateLock, references: &HashMap<String, Reference>) -> std::io::Result<()> {
+    lock.write_json(references)
+}
+
+// 64-bit difference hash: shrink to 9x8 grayscale and compare neighbours
+fn dhash(png: &[u8]) -> Option<u64> {
+    let image = image::load_from_memory(png).ok()?;
+    let small = image.grayscale().resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    Some(hash)
+}
+
+pub async fn check_page(driver: &WebDriver, config: &VisualDiffConfig, page: &str) -> WebDriverResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    fs::create_dir_all(paths::state(REFERENCE_DIR))?;
+    let lock = state_file::lock(&paths::state(REFERENCE_FILE))?;
+    let mut references = load_references();
+    let now = Utc::now();
+    if let Some(reference) = references.get(page) {
+        if now - reference.last_checked < ChronoDuration::hours(config.interval_hours) {
+            return Ok(());
+        }
+    }
+
+    let png = driver.screenshot_as_png().await?;
+    let Some(hash) = dhash(&png) else {
+        println!("Could not decode screenshot of {} page for visual diff", page);
+        return Ok(());
+    };
+
+    match references.get_mut(page) {
+        None => {
+            println!("Captured visual reference for {} page", page);
+            state_file::write_atomic(&paths::state(REFERENCE_DIR).join(format!("{}.png", page)), &png)?;
+            references.insert(page.to_string(), Reference { hash, captured_at: now, last_checked: now });
+        }
+        Some(reference) => {
+            let distance = (reference.hash ^ hash).count_ones();
+            reference.last_checked = now;
+            if distance > config.threshold {
+                // Keep the old reference so the warning repeats until someone looks
+                println!(
+                    "WARNING: {} page looks different from its reference (distance {} > {}). Dice may have changed its UI; selectors could start failing.",
+                    page, distance, config.threshold
+                );
+                state_file::write_atomic(&paths::state(REFERENCE_DIR).join(format!("{}.latest.png", page)), &png)?;
+            } else if now - reference.captured_at > ChronoDuration::days(config.refresh_days) {
+                println!("Refreshing visual reference for {} page", page);
+                state_file::write_atomic(&paths::state(REFERENCE_DIR).join(format!("{}.png", page)), &png)?;
+                reference.hash = hash;
+                reference.captured_at = now;
+            }
+        }
+    }
+
+    save_references(&lock, &references)?;
+    Ok(())
+}
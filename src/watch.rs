@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thirtyfour::prelude::*;
+use thirtyfour::support::sleep;
+
+use crate::paths;
+use crate::schema::{self, Migration};
+use crate::state_file;
+use crate::skills::SkillTaxonomy;
+use crate::template::TemplateContext;
+use crate::{build_search_url, discovery, filters, get_job_detail_ids, job_id_from_url, message, session, Config, Job};
+
+pub const SEEN_FILE: &str = "seen.json";
+
+// v1: versioned envelope from the start
+const MIGRATIONS: &[Migration] = &[];
+
+// `dice_blast watch`: re-run the searches on an interval and announce jobs
+// that haven't been announced within the retention window
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    pub interval_mins: u64,
+    // A job (or a repost of it) alerts again once it's been this long since
+    // its last alert
+    pub seen_retention_days: i64,
+    // Printed for each new job; placeholders are listed in template.rs
+    pub alert_template: String,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        WatchConfig {
+            interval_mins: 60,
+            seen_retention_days: 14,
+            alert_template: "New job: {job_title} at {company|unknown company} ({url})".to_string(),
+        }
+    }
+}
+
+// Reposts get a new ID, so jobs with a known company are keyed by company
+// and title instead
+fn seen_key(job: &Job) -> String {
+    match &job.company {
+        Some(company) => format!("{}|{}", company.trim().to_lowercase(), job.job_title.trim().to_lowercase()),
+        None => job_id_from_url(&job.url).to_string(),
+    }
+}
+
+async fn scrape_all(driver: &WebDriver, config: &Config) -> WebDriverResult<Vec<Job>> {
+    let mut jobs = Vec::new();
+    for search in std::iter::once(&config.search).chain(&config.searches) {
+        session::navigate(driver, &config.timeouts, &build_search_url(search)).await?;
+        let found = get_job_detail_ids(driver, &config.timeouts, 1).await?;
+        if found.is_empty() {
+            println!("Search '{}' returned no results", search.q);
+        }
+        discovery::merge_jobs(&mut jobs, found);
+    }
+    Ok(filters::apply_filters(&config.job_filters, jobs))
+}
+
+pub async fn run(driver: &WebDriver, config: &Config) -> WebDriverResult<()> {
+    let settings = &config.watch;
+    let path = &paths::state(SEEN_FILE);
+    let mut seen: BTreeMap<String, DateTime<Utc>> = schema::read_versioned(path, MIGRATIONS)?.unwrap_or_default();
+    let retention = Duration::days(settings.seen_retention_days);
+
+    let taxonomy = SkillTaxonomy::new(&config.skill_synonyms);
+    loop {
+        let now = Utc::now();
+        // Forget anything past the window so it can alert again
+        seen.retain(|_, alerted_at| now - *alerted_at < retention);
+
+        let mut alerts = 0;
+        for job in scrape_all(driver, config).await? {
+            let key = seen_key(&job);
+            if seen.contains_key(&key) {
+                continue;
+            }
+            let skills = message::matching_skills(&config.profile.skills, &taxonomy, &job.job_title, "");
+            println!("{}", TemplateContext::for_job(&job, &skills).render(&settings.alert_template));
+            seen.insert(key, now);
+            alerts += 1;
+        }
+        // Keeps what another watcher alerted on since this one last saved
+        let lock = state_file::lock(path)?;
+        let stored: BTreeMap<String, DateTime<Utc>> = schema::read_versioned_locked(&lock, MIGRATIONS)?.unwrap_or_default();
+        for (key, alerted_at) in stored {
+            seen.entry(key).or_insert(alerted_at);
+        }
+        schema::write_versioned_locked(&lock, MIGRATIONS, &seen)?;
+        drop(lock);
+
+        println!("{} new jobs; checking again in {} minutes", alerts, settings.interval_mins);
+        sleep(std::time::Duration::from_secs(settings.interval_mins * 60)).await;
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::blacklist::first_match;
+use crate::filters::Patterns;
+use crate::Job;
+
+// Targeted campaigns: when a whitelist is set, only jobs from one of
+// `companies`, or with a title matching one of `title_patterns`, are kept.
+// Companies match like the blacklist's; patterns are case-insensitive
+// regexes. Jobs whose card doesn't name the company can only get in by
+// title.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Whitelist {
+    #[serde(default)]
+    pub companies: Vec<String>,
+    #[serde(default)]
+    pub title_patterns: Patterns,
+}
+
+fn listed(whitelist: &Whitelist, job: &Job) -> bool {
+    let company_listed = job.company.as_deref().is_some_and(|company| first_match(&whitelist.companies, company).is_some());
+    company_listed || whitelist.title_patterns.first_match(&job.job_title).is_some()
+}
+
+pub fn whitelist_reason(whitelist: &Whitelist, job: &Job) -> Option<String> {
+    (!listed(whitelist, job)).then(|| "not on the whitelist".to_string())
+}
+
+pub fn apply_whitelist(whitelist: Option<&Whitelist>, jobs: Vec<Job>) -> Vec<Job> {
+    let Some(whitelist) = whitelist else {
+        return jobs;
+    };
+    let total = jobs.len();
+    let kept: Vec<Job> = jobs
+        .into_iter()
+        .filter(|job| match whitelist_reason(whitelist, job) {
+            Some(reason) => {
+                println!("Skipping {}: {}", job.job_title, reason);
+                false
+            }
+            None => true,
+        })
+        .collect();
+    println!("{} of {} jobs are on the whitelist", kept.len(), total);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{discovered_job, JobSource};
+
+    fn job(title: &str, company: Option<&str>) -> Job {
+        let mut job = discovered_job("2b9d5bc5-3c4d-4b6b-9a1e-6c1e9f1a2b3c", title.to_string(), JobSource::Search);
+        job.company = company.map(str::to_string);
+        job
+    }
+
+    #[test]
+    fn company_or_title_gets_a_job_in() {
+        let whitelist = Whitelist {
+            companies: vec!["Oxide Computer".to_string()],
+            title_patterns: Patterns::new(&[r"\brust\b"]).unwrap(),
+        };
+        let jobs = vec![
+            job("Platform Engineer", Some("Oxide Computer Company")),
+            job("Senior Rust Engineer", None),
+            job("Salesforce Administrator", Some("Initech")),
+            job("Trust and Safety Analyst", Some("Initech")),
+        ];
+        let kept: Vec<String> = apply_whitelist(Some(&whitelist), jobs).into_iter().map(|job| job.job_title).collect();
+        assert_eq!(kept, vec!["Platform Engineer", "Senior Rust Engineer"]);
+    }
+}
@@ -0,0 +1,69 @@
+use thirtyfour::prelude::*;
+
+use crate::search_filters::{FilterList, FilterValue, WorkplaceType};
+use crate::{Config, Job};
+
+// The job header, where Dice shows the workplace badge next to the location.
+// Null when none of it is on the page.
+const HEADER_SCRIPT: &str = r#"
+    const selectors = ['[data-cy="locationDetails"]', '[data-testid*="workplace"]', '[data-testid*="location"]', '[data-testid="job-detail-header-card"]'];
+    const texts = selectors.flatMap(s => Array.from(document.querySelectorAll(s))).map(el => el.innerText);
+    return texts.length ? texts.join('\n') : null;
+"#;
+
+const ON_SITE_WORDS: [&str; 4] = ["on-site", "onsite", "on site", "in office"];
+
+// Hybrid postings often say "remote" too, so hybrid is checked first
+fn detect(text: &str) -> Option<WorkplaceType> {
+    let text = text.to_lowercase();
+    if text.contains("hybrid") {
+        Some(WorkplaceType::Hybrid)
+    } else if text.contains("remote") {
+        Some(WorkplaceType::Remote)
+    } else if ON_SITE_WORDS.iter().any(|word| text.contains(word)) {
+        Some(WorkplaceType::OnSite)
+    } else {
+        None
+    }
+}
+
+// The workplace filter of the search that found the job; jobs from other
+// sources use the main search's
+pub fn filter_for<'a>(config: &'a Config, job: &Job) -> &'a FilterList<WorkplaceType> {
+    let search = std::iter::once(&config.search)
+        .chain(&config.searches)
+        .find(|search| job.query.as_deref() == Some(search.label()))
+        .unwrap_or(&config.search);
+    &search.workplace_types
+}
+
+// Dice's workplace filter lets some postings through, so the open job page
+// is checked before applying. A page whose workplace type can't be read is
+// kept rather than guessed at.
+pub async fn mismatch(driver: &WebDriver, config: &Config, job: &Job) -> WebDriverResult<Option<String>> {
+    let wanted = filter_for(config, job);
+    if wanted.is_empty() {
+        return Ok(None);
+    }
+    let header: Option<String> = driver.execute(HEADER_SCRIPT, vec![]).await?.convert()?;
+    let reason = header
+        .as_deref()
+        .and_then(detect)
+        .filter(|found| !wanted.contains(*found))
+        .map(|found| format!("workplace is {}, searching for {}", found.as_str(), wanted));
+    Ok(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_the_workplace_badge() {
+        assert_eq!(detect("Austin, TX\nRemote"), Some(WorkplaceType::Remote));
+        assert_eq!(detect("Hybrid in Austin, TX (remote 2 days a week)"), Some(WorkplaceType::Hybrid));
+        assert_eq!(detect("ON-SITE\nDenver, CO"), Some(WorkplaceType::OnSite));
+        assert_eq!(detect("Work in office, Denver"), Some(WorkplaceType::OnSite));
+        assert_eq!(detect("Denver, CO"), None);
+    }
+}
@@ -0,0 +1,43 @@
+use thirtyfour::prelude::*;
+
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+
+// Quotes `text` as an XPath string literal. XPath 1.0 has no escape syntax,
+// so text containing both quote kinds is split up with concat().
+pub fn literal(text: &str) -> String {
+    if !text.contains('\'') {
+        return format!("'{}'", text);
+    }
+    if !text.contains('"') {
+        return format!("\"{}\"", text);
+    }
+    let parts: Vec<String> = text.split('\'').map(|part| format!("'{}'", part)).collect();
+    format!("concat({})", parts.join(", \"'\", "))
+}
+
+// normalize-space(.) lowercased, for case-insensitive comparisons
+fn normalized_text() -> String {
+    format!("translate(normalize-space(.), '{}', '{}')", UPPER, LOWER)
+}
+
+// Element whose whole visible text equals `text`, ignoring case and whitespace
+pub fn with_text(tag: &str, text: &str) -> By {
+    By::XPath(format!("//{}[{} = {}]", tag, normalized_text(), literal(&text.trim().to_lowercase())))
+}
+
+pub fn button_with_text(text: &str) -> By {
+    with_text("button", text)
+}
+
+// Clicks the first button labelled `text` through JavaScript, which isn't
+// blocked by overlays the way a native click is. Returns false if no such
+// button exists.
+pub async fn click_button_with_text(driver: &WebDriver, text: &str) -> WebDriverResult<bool> {
+    let button = match driver.find(button_with_text(text)).await {
+        Ok(button) => button,
+        Err(_) => return Ok(false),
+    };
+    driver.execute("arguments[0].click();", vec![button.to_json()?]).await?;
+    Ok(true)
+}
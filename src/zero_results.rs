@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// When each search URL last came back empty, so a search `serve` or
+// `daemon` repeats doesn't repeat the same notice within the quiet period
+pub struct ZeroResultLog {
+    quiet_period: Duration,
+    last_reported: HashMap<String, Instant>,
+}
+
+impl ZeroResultLog {
+    pub fn new(quiet_mins: u64) -> ZeroResultLog {
+        ZeroResultLog {
+            quiet_period: Duration::from_secs(quiet_mins * 60),
+            last_reported: HashMap::new(),
+        }
+    }
+
+    pub fn should_report(&mut self, url: &str) -> bool {
+        self.should_report_at(url, Instant::now())
+    }
+
+    fn should_report_at(&mut self, url: &str, now: Instant) -> bool {
+        match self.last_reported.get(url) {
+            Some(last) if now.duration_since(*last) < self.quiet_period => false,
+            _ => {
+                self.last_reported.insert(url.to_string(), now);
+                true
+            }
+        }
+    }
+
+    // Once a search has results again, its next empty run is news
+    pub fn clear(&mut self, url: &str) {
+        self.last_reported.remove(url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_until_the_period_passes_or_results_return() {
+        let mut log = ZeroResultLog::new(60);
+        let start = Instant::now();
+        let minutes = |m: u64| start + Duration::from_secs(m * 60);
+
+        assert!(log.should_report_at("https://www.dice.com/jobs?q=rust", start));
+        assert!(!log.should_report_at("https://www.dice.com/jobs?q=rust", minutes(30)));
+        // Other searches have their own window
+        assert!(log.should_report_at("https://www.dice.com/jobs?q=go", minutes(30)));
+        assert!(!log.should_report_at("https://www.dice.com/jobs?q=rust", minutes(59)));
+        // The window starts again from each notice
+        assert!(log.should_report_at("https://www.dice.com/jobs?q=rust", minutes(60)));
+        assert!(!log.should_report_at("https://www.dice.com/jobs?q=rust", minutes(90)));
+
+        log.clear("https://www.dice.com/jobs?q=rust");
+        assert!(log.should_report_at("https://www.dice.com/jobs?q=rust", minutes(91)));
+        assert!(!log.should_report_at("https://www.dice.com/jobs?q=go", minutes(89)));
+    }
+}